@@ -0,0 +1,117 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+	ext::IdentExt,
+	parse::{Parse, ParseStream},
+	Error, Expr, Ident, LitStr, Token,
+};
+
+use did_common_syntax::is_param_name;
+
+// Structured form of the did! macro, building a DID from runtime parts:
+// did!(method = "example", id = id; service = "agent", "example:foo" = foo # "keys-1")
+pub struct DidParts {
+	method_name: Expr,
+	method_specific_id: Expr,
+	params: Vec<(LitStr, Expr)>,
+	fragment: Option<Expr>,
+}
+
+fn parse_field(input: ParseStream, field: &str) -> syn::Result<Expr> {
+	let ident: Ident = input.parse()?;
+	if ident != field {
+		return Err(Error::new(ident.span(), format!("expected `{}`", field)));
+	}
+	input.parse::<Token![=]>()?;
+	input.parse()
+}
+
+// Parameter names are identifiers, or string literals for namespaced names
+fn parse_param_name(input: ParseStream) -> syn::Result<LitStr> {
+	let name = if input.peek(LitStr) {
+		input.parse()?
+	} else {
+		let ident = input.call(Ident::parse_any)?.unraw();
+		LitStr::new(&ident.to_string(), ident.span())
+	};
+	let value = name.value();
+	if !is_param_name(&value) {
+		return Err(Error::new(
+			name.span(),
+			format!("invalid DID parameter name `{}`", value),
+		));
+	}
+	Ok(name)
+}
+
+impl Parse for DidParts {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		// Runtime strings used to be accepted, point at their replacement
+		if !input
+			.fork()
+			.parse::<Ident>()
+			.is_ok_and(|ident| ident == "method")
+		{
+			return Err(input.error(
+				"expected a DID string literal or `method = ..., id = ...`; use `Did::parse` to parse runtime strings",
+			));
+		}
+		let method_name = parse_field(input, "method")?;
+		input.parse::<Token![,]>()?;
+		let method_specific_id = parse_field(input, "id")?;
+
+		let mut params: Vec<(LitStr, Expr)> = Vec::new();
+		if input.parse::<Option<Token![;]>>()?.is_some() {
+			loop {
+				let name = parse_param_name(input)?;
+				if params
+					.iter()
+					.any(|(other, _)| other.value() == name.value())
+				{
+					return Err(Error::new(
+						name.span(),
+						format!("duplicate DID parameter `{}`", name.value()),
+					));
+				}
+				input.parse::<Token![=]>()?;
+				params.push((name, input.parse()?));
+				if input.parse::<Option<Token![,]>>()?.is_none() {
+					break;
+				}
+			}
+		}
+
+		let fragment = match input.parse::<Option<Token![#]>>()? {
+			Some(_) => Some(input.parse()?),
+			None => None,
+		};
+		Ok(DidParts {
+			method_name,
+			method_specific_id,
+			params,
+			fragment,
+		})
+	}
+}
+
+impl DidParts {
+	pub fn to_builder(&self, krate: &TokenStream) -> TokenStream {
+		let DidParts {
+			method_name,
+			method_specific_id,
+			..
+		} = self;
+		let params = self.params.iter().map(|(name, value)| {
+			quote! { .with_param(#name, #value) }
+		});
+		let fragment = self.fragment.iter().map(|fragment| {
+			quote! { .with_fragment(#fragment) }
+		});
+		quote! {
+			#krate::did::DidBuilder::new(#method_name, #method_specific_id)
+				#(#params)*
+				#(#fragment)*
+				.try_build()
+		}
+	}
+}
@@ -0,0 +1,129 @@
+extern crate proc_macro;
+
+mod did_builder;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::LitStr;
+
+use did_common_syntax::{parse_did, DidParts, DidSyntaxError, DidSyntaxErrorKind};
+
+use did_builder::DidParts as DidBuilderParts;
+
+/// Expands the `did!` macro of the did_common crate, which passes its own path
+/// followed by `;` so that expansions resolve even when the crate is renamed.
+///
+/// DID literals are parsed at compile time and expand to a constant `Did<'static>`;
+/// an invalid DID is reported as a compile error giving the offset of the first
+/// invalid character, which is also pointed at when the compiler supports it.
+///
+/// DIDs can also be built from runtime parts, in which case the macro expands to
+/// `DidBuilder` calls returning a `Result<Did, DidBuildError>`:
+/// `did!(method = "example", id = id; service = "agent", hl = hl # "keys-1")`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn expand_did(input: TokenStream) -> TokenStream {
+	let (krate, input) = split_crate_path(input.into());
+	match syn::parse2::<LitStr>(input.clone()) {
+		Ok(literal) => did_literal(&krate, literal),
+		Err(_) => match syn::parse2::<DidBuilderParts>(input) {
+			Ok(parts) => parts.to_builder(&krate),
+			Err(err) => err.to_compile_error(),
+		},
+	}
+	.into()
+}
+
+// The crate path is made of the tokens before the first `;`
+fn split_crate_path(input: TokenStream2) -> (TokenStream2, TokenStream2) {
+	let mut tokens = input.into_iter();
+	let krate = tokens
+		.by_ref()
+		.take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ';'))
+		.collect();
+	(krate, tokens.collect())
+}
+
+fn did_literal(krate: &TokenStream2, literal: LitStr) -> TokenStream2 {
+	let did = literal.value();
+	match parse_did(&did) {
+		Ok(parts) => did_constant(krate, &parts),
+		Err(err) => syn::Error::new(
+			error_span(&literal, &did, err.offset),
+			format!("{} at offset {}", error_message(&did, &err), err.offset),
+		)
+		.to_compile_error(),
+	}
+}
+
+fn error_message(did: &str, err: &DidSyntaxError) -> String {
+	let rest = &did[err.offset..];
+	let param_name = || rest.split('=').next().unwrap_or(rest);
+	match err.kind {
+		DidSyntaxErrorKind::UnexpectedCharacter | DidSyntaxErrorKind::UnexpectedEnd => {
+			match rest.chars().next() {
+				Some(c) => format!("invalid DID: unexpected character {:?}", c),
+				None => "invalid DID: unexpected end of input".to_string(),
+			}
+		}
+		DidSyntaxErrorKind::ForeignParam => {
+			let method_name = did.split(':').nth(1).unwrap_or_default();
+			format!(
+				"invalid DID: parameter `{}` is not in the `{}` method namespace",
+				param_name(),
+				method_name
+			)
+		}
+		DidSyntaxErrorKind::DuplicateParam => {
+			format!("invalid DID: duplicate parameter `{}`", param_name())
+		}
+	}
+}
+
+fn did_constant(krate: &TokenStream2, parts: &DidParts) -> TokenStream2 {
+	let DidParts {
+		method_name,
+		method_specific_id,
+		..
+	} = parts;
+	let params = match &parts.params {
+		Some(params) => {
+			let params = params.iter().map(|(name, value)| {
+				quote! { #krate::did::DidParam::new(#name, ::core::option::Option::Some(#value)) }
+			});
+			quote! { ::core::option::Option::Some(&[#(#params),*]) }
+		}
+		None => quote! { ::core::option::Option::None },
+	};
+	let fragment = match parts.fragment {
+		Some(fragment) => quote! { ::core::option::Option::Some(#fragment) },
+		None => quote! { ::core::option::Option::None },
+	};
+	quote! {
+		{
+			const DID: #krate::did::Did<'static> = #krate::did::Did::from_parts(
+				#method_name,
+				#method_specific_id,
+				#params,
+				#fragment,
+			);
+			DID
+		}
+	}
+}
+
+// Points at the invalid character inside the literal, which is only possible
+// on compilers supporting subspans and for literals without escape sequences
+fn error_span(literal: &LitStr, value: &str, offset: usize) -> Span {
+	let token = literal.token();
+	let repr = token.to_string();
+	let start = match repr.find('"') {
+		Some(quote) if repr[quote + 1..].starts_with(value) => quote + 1 + offset,
+		_ => return literal.span(),
+	};
+	let len = value[offset..].chars().next().map_or(0, char::len_utf8);
+	token
+		.subspan(start..start + len.max(1))
+		.unwrap_or_else(|| literal.span())
+}
@@ -0,0 +1,419 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+// Implement a scanner for Decentralized Identifiers following the syntax defined at:
+// https://w3c-ccg.github.io/did-spec/#generic-did-syntax
+//
+// It is the single DID grammar of the did_common parser and the did! macro, and
+// reports the offset of the first invalid character so that macros can point at it.
+
+pub const DID_SCHEME: &str = "did";
+const COLON_SEP: char = ':';
+const SEMICOLON_SEP: char = ';';
+const EQUAL_SEP: char = '=';
+const FRAGMENT_SEP: char = '#';
+const PERCENT_SEP: char = '%';
+
+/// The parts of a syntactically valid DID, borrowed from the parsed input.
+#[derive(Debug, PartialEq)]
+pub struct DidParts<'a> {
+	pub method_name: &'a str,
+	pub method_specific_id: &'a str,
+	pub params: Option<Vec<(&'a str, &'a str)>>,
+	pub fragment: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DidSyntaxErrorKind {
+	UnexpectedCharacter,
+	UnexpectedEnd,
+	ForeignParam,
+	DuplicateParam,
+}
+
+/// A DID syntax error, at the offset of the invalid character or parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DidSyntaxError {
+	pub offset: usize,
+	pub kind: DidSyntaxErrorKind,
+}
+
+fn is_method_char(c: char) -> bool {
+	c.is_ascii_lowercase() || c.is_ascii_digit()
+}
+
+fn is_id_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_'
+}
+
+fn is_param_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_' || c == ':'
+}
+
+fn is_fragment_char(c: char) -> bool {
+	// 'unreserved', 'sub-delims' and additional 'pchar' chars
+	c.is_ascii_alphanumeric() || "._~!$&'()*+,;=:@-".contains(c)
+}
+
+fn is_pct_encoded(input: &str) -> bool {
+	let bytes = input.as_bytes();
+	bytes.len() >= 3
+		&& bytes[0] == PERCENT_SEP as u8
+		&& bytes[1].is_ascii_hexdigit()
+		&& bytes[2].is_ascii_hexdigit()
+}
+
+// Length of the longest prefix made of allowed chars and, optionally, pct-encoded octets
+fn span_len(input: &str, is_char: fn(char) -> bool, pct_encoded: bool) -> usize {
+	let mut len = 0;
+	while let Some(c) = input[len..].chars().next() {
+		if is_char(c) {
+			len += c.len_utf8();
+		} else if pct_encoded && is_pct_encoded(&input[len..]) {
+			len += 3;
+		} else {
+			break;
+		}
+	}
+	len
+}
+
+// Length of the longest prefix made of colon-separated, non-empty id segments
+fn method_specific_id_len(input: &str) -> usize {
+	let mut len = span_len(input, is_id_char, true);
+	while len > 0 {
+		let segment = match input[len..].strip_prefix(COLON_SEP) {
+			Some(next) => span_len(next, is_id_char, true),
+			None => 0,
+		};
+		if segment == 0 {
+			break;
+		}
+		len += segment + 1;
+	}
+	len
+}
+
+pub fn is_method_name(input: &str) -> bool {
+	!input.is_empty() && span_len(input, is_method_char, false) == input.len()
+}
+
+pub fn is_method_specific_id(input: &str) -> bool {
+	method_specific_id_len(input) == input.len()
+}
+
+pub fn is_param_name(input: &str) -> bool {
+	!input.is_empty() && span_len(input, is_param_char, false) == input.len()
+}
+
+pub fn is_param_value(input: &str) -> bool {
+	span_len(input, is_param_char, true) == input.len()
+}
+
+pub fn is_fragment(input: &str) -> bool {
+	span_len(input, is_fragment_char, false) == input.len()
+}
+
+/// Method-specific parameters must be namespaced by the method of the DID,
+/// e.g. example:foo:bar for did:example
+pub fn is_method_param(method_name: &str, name: &str) -> bool {
+	let namespace = name.find(COLON_SEP).map(|pos| &name[..pos]);
+	namespace.is_none_or(|namespace| namespace == method_name)
+}
+
+struct Scanner<'a> {
+	input: &'a str,
+	pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+	fn rest(&self) -> &'a str {
+		&self.input[self.pos..]
+	}
+
+	fn eat(&mut self, sep: char) -> bool {
+		let found = self.rest().starts_with(sep);
+		if found {
+			self.pos += sep.len_utf8();
+		}
+		found
+	}
+
+	fn take(&mut self, len: usize) -> &'a str {
+		let taken = &self.rest()[..len];
+		self.pos += len;
+		taken
+	}
+
+	fn unexpected(&self) -> DidSyntaxError {
+		DidSyntaxError {
+			offset: self.pos,
+			kind: if self.rest().is_empty() {
+				DidSyntaxErrorKind::UnexpectedEnd
+			} else {
+				DidSyntaxErrorKind::UnexpectedCharacter
+			},
+		}
+	}
+
+	fn param(&mut self) -> Option<(usize, &'a str, &'a str)> {
+		let start = self.pos;
+		let name_len = span_len(self.rest(), is_param_char, false);
+		if name_len == 0 || !self.rest()[name_len..].starts_with(EQUAL_SEP) {
+			return None;
+		}
+		let name = self.take(name_len);
+		self.eat(EQUAL_SEP);
+		let value_len = span_len(self.rest(), is_param_char, true);
+		Some((start, name, self.take(value_len)))
+	}
+
+	fn params(&mut self) -> Vec<(usize, &'a str, &'a str)> {
+		let mut params = Vec::new();
+		if let Some(param) = self.param() {
+			params.push(param);
+			loop {
+				let sep = self.pos;
+				if !self.eat(SEMICOLON_SEP) {
+					break;
+				}
+				match self.param() {
+					Some(param) => params.push(param),
+					None => {
+						self.pos = sep;
+						break;
+					}
+				}
+			}
+		}
+		params
+	}
+}
+
+pub fn parse_did(input: &str) -> Result<DidParts<'_>, DidSyntaxError> {
+	let mut scanner = Scanner { input, pos: 0 };
+	let prefix = DID_SCHEME.bytes().chain(Some(COLON_SEP as u8));
+	scanner.pos = prefix
+		.clone()
+		.zip(input.bytes())
+		.take_while(|(a, b)| a == b)
+		.count();
+	if scanner.pos < prefix.count() {
+		return Err(scanner.unexpected());
+	}
+
+	let method_name = scanner.take(span_len(scanner.rest(), is_method_char, false));
+	if method_name.is_empty() || !scanner.eat(COLON_SEP) {
+		return Err(scanner.unexpected());
+	}
+	let method_specific_id = scanner.take(method_specific_id_len(scanner.rest()));
+
+	let params = if scanner.eat(SEMICOLON_SEP) {
+		Some(scanner.params())
+	} else {
+		None
+	};
+	let fragment = if scanner.eat(FRAGMENT_SEP) {
+		Some(scanner.take(span_len(scanner.rest(), is_fragment_char, false)))
+	} else {
+		None
+	};
+	if !scanner.rest().is_empty() {
+		return Err(scanner.unexpected());
+	}
+
+	let params = match params {
+		Some(params) => {
+			validate_params(method_name, &params)?;
+			Some(
+				params
+					.into_iter()
+					.map(|(_, name, value)| (name, value))
+					.collect(),
+			)
+		}
+		None => None,
+	};
+
+	Ok(DidParts {
+		method_name,
+		method_specific_id,
+		params,
+		fragment,
+	})
+}
+
+// Parameter names must be unique, and namespaced by the method of the DID
+fn validate_params(
+	method_name: &str,
+	params: &[(usize, &str, &str)],
+) -> Result<(), DidSyntaxError> {
+	for (i, &(offset, name, _)) in params.iter().enumerate() {
+		let kind = if !is_method_param(method_name, name) {
+			DidSyntaxErrorKind::ForeignParam
+		} else if params[..i].iter().any(|(_, other, _)| *other == name) {
+			DidSyntaxErrorKind::DuplicateParam
+		} else {
+			continue;
+		};
+		return Err(DidSyntaxError { offset, kind });
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	fn did<'a>(
+		method_name: &'a str,
+		method_specific_id: &'a str,
+		params: Option<Vec<(&'a str, &'a str)>>,
+		fragment: Option<&'a str>,
+	) -> DidParts<'a> {
+		DidParts {
+			method_name,
+			method_specific_id,
+			params,
+			fragment,
+		}
+	}
+
+	fn error(offset: usize, kind: DidSyntaxErrorKind) -> Result<DidParts<'static>, DidSyntaxError> {
+		Err(DidSyntaxError { offset, kind })
+	}
+
+	#[test]
+	fn parse_generic_did() {
+		assert_eq!(
+			parse_did("did:example:1234"),
+			Ok(did("example", "1234", None, None))
+		);
+		assert_eq!(
+			parse_did("did:example:"),
+			Ok(did("example", "", None, None))
+		);
+		assert_eq!(
+			parse_did("did:example:123456789abcdefghi#keys-1"),
+			Ok(did("example", "123456789abcdefghi", None, Some("keys-1")))
+		);
+	}
+
+	#[test]
+	fn parse_method_dids() {
+		assert_eq!(
+			parse_did("did:btcr:xyv2-xzpq-q9wa-p7t#satoshi"),
+			Ok(did("btcr", "xyv2-xzpq-q9wa-p7t", None, Some("satoshi")))
+		);
+		assert_eq!(
+			parse_did("did:erc725:ropsten:2F2B37C890824242Cb9B0FE5614fA2221B79901E"),
+			Ok(did(
+				"erc725",
+				"ropsten:2F2B37C890824242Cb9B0FE5614fA2221B79901E",
+				None,
+				None
+			))
+		);
+		assert_eq!(
+			parse_did("did:web:example.com%3A8443:users:alice"),
+			Ok(did("web", "example.com%3A8443:users:alice", None, None))
+		);
+	}
+
+	#[test]
+	fn parse_did_with_params() {
+		assert_eq!(
+			parse_did("did:example:1234;service=agent;example:foo:bar=baz#keys-1"),
+			Ok(did(
+				"example",
+				"1234",
+				Some(vec![("service", "agent"), ("example:foo:bar", "baz")]),
+				Some("keys-1")
+			))
+		);
+		assert_eq!(
+			parse_did("did:example:1234;relativeRef=%2Fpath%2Fto%3Fq"),
+			Ok(did(
+				"example",
+				"1234",
+				Some(vec![("relativeRef", "%2Fpath%2Fto%3Fq")]),
+				None
+			))
+		);
+		assert_eq!(
+			parse_did("did:example:1234;service=agent;example:qux="),
+			Ok(did(
+				"example",
+				"1234",
+				Some(vec![("service", "agent"), ("example:qux", "")]),
+				None
+			))
+		);
+	}
+
+	#[test]
+	fn parse_invalid_did_fails() {
+		use DidSyntaxErrorKind::*;
+		assert_eq!(parse_did("di:example:1234"), error(2, UnexpectedCharacter));
+		assert_eq!(parse_did("did"), error(3, UnexpectedEnd));
+		assert_eq!(parse_did("did::123456"), error(4, UnexpectedCharacter));
+		assert_eq!(
+			parse_did("did:EXAMPLE:123456"),
+			error(4, UnexpectedCharacter)
+		);
+		assert_eq!(parse_did("did:example"), error(11, UnexpectedEnd));
+		assert_eq!(
+			parse_did("did:example:1234 extra"),
+			error(16, UnexpectedCharacter)
+		);
+		assert_eq!(
+			parse_did("did:example:1234#keys-1 trailing"),
+			error(23, UnexpectedCharacter)
+		);
+		assert_eq!(
+			parse_did("did:example:1234;relativeRef=%2"),
+			error(29, UnexpectedCharacter)
+		);
+		assert_eq!(
+			parse_did("did:example:1234;service=agent;"),
+			error(30, UnexpectedCharacter)
+		);
+	}
+
+	#[test]
+	fn parse_did_with_invalid_params_fails() {
+		use DidSyntaxErrorKind::*;
+		assert_eq!(
+			parse_did("did:example:1234;other:foo=bar"),
+			error(17, ForeignParam)
+		);
+		assert_eq!(
+			parse_did("did:example:1234;service=agent;service=files"),
+			error(31, DuplicateParam)
+		);
+		assert_eq!(
+			parse_did("did:example:1234;example:foo=bar;example:foo=baz"),
+			error(33, DuplicateParam)
+		);
+	}
+
+	#[test]
+	fn validate_did_parts() {
+		assert!(is_method_name("example"));
+		assert!(!is_method_name(""));
+		assert!(is_method_specific_id("ropsten:2F2B%3A"));
+		assert!(!is_method_specific_id("1234:"));
+		assert!(is_param_name("example:foo"));
+		assert!(!is_param_name(""));
+		assert!(is_param_value(""));
+		assert!(!is_param_value("%2"));
+		assert!(is_fragment("keys-1"));
+		assert!(!is_fragment("keys 1"));
+		assert!(is_method_param("example", "service"));
+		assert!(!is_method_param("example", "other:foo"));
+	}
+}
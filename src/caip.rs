@@ -0,0 +1,131 @@
+use crate::lib::std::fmt;
+
+use nom::{
+	bytes::complete::{tag, take_while_m_n},
+	combinator::{all_consuming, map},
+	error::ParseError,
+	sequence::separated_pair,
+	IResult,
+};
+
+// Implement parsers for blockchain identifiers following the syntax defined at:
+// https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-2.md
+// https://github.com/ChainAgnostic/CAIPs/blob/master/CAIPs/caip-10.md
+
+const COLON_SEP: &str = ":";
+
+fn is_namespace_char(c: char) -> bool {
+	c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'
+}
+
+fn is_reference_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+fn is_address_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '%'
+}
+
+fn chain_id<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, ChainId<'a>, E> {
+	map(
+		separated_pair(
+			take_while_m_n(3, 8, is_namespace_char),
+			tag(COLON_SEP),
+			take_while_m_n(1, 32, is_reference_char),
+		),
+		|(namespace, reference)| ChainId {
+			namespace,
+			reference,
+		},
+	)(input)
+}
+
+fn account_id<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, AccountId<'a>, E> {
+	map(
+		separated_pair(
+			chain_id,
+			tag(COLON_SEP),
+			take_while_m_n(1, 128, is_address_char),
+		),
+		|(chain_id, address)| AccountId { chain_id, address },
+	)(input)
+}
+
+/// A CAIP-2 blockchain id, e.g. `eip155:1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainId<'a> {
+	namespace: &'a str,
+	reference: &'a str,
+}
+
+impl<'a> ChainId<'a> {
+	pub fn new(namespace: &'a str, reference: &'a str) -> Self {
+		ChainId {
+			namespace,
+			reference,
+		}
+	}
+
+	pub fn namespace(&self) -> &'a str {
+		self.namespace
+	}
+
+	pub fn reference(&self) -> &'a str {
+		self.reference
+	}
+
+	pub fn is_valid(input: &'a str) -> bool {
+		Self::parse(input).is_ok()
+	}
+
+	pub fn parse(input: &'a str) -> Result<Self, &'static str> {
+		match all_consuming(chain_id::<(&str, nom::error::ErrorKind)>)(input) {
+			Ok((_, chain_id)) => Ok(chain_id),
+			Err(_) => Err("invalid CAIP-2 chain id"),
+		}
+	}
+}
+
+impl fmt::Display for ChainId<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}{}{}", self.namespace, COLON_SEP, self.reference)
+	}
+}
+
+/// A CAIP-10 blockchain account id, e.g. `eip155:1:0xab16a96d359ec26a11e2c2b3d8f8b8942d5bfcdb`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountId<'a> {
+	chain_id: ChainId<'a>,
+	address: &'a str,
+}
+
+impl<'a> AccountId<'a> {
+	pub fn new(chain_id: ChainId<'a>, address: &'a str) -> Self {
+		AccountId { chain_id, address }
+	}
+
+	pub fn chain_id(&self) -> &ChainId<'a> {
+		&self.chain_id
+	}
+
+	pub fn address(&self) -> &'a str {
+		self.address
+	}
+
+	pub fn is_valid(input: &'a str) -> bool {
+		Self::parse(input).is_ok()
+	}
+
+	pub fn parse(input: &'a str) -> Result<Self, &'static str> {
+		match all_consuming(account_id::<(&str, nom::error::ErrorKind)>)(input) {
+			Ok((_, account_id)) => Ok(account_id),
+			Err(_) => Err("invalid CAIP-10 account id"),
+		}
+	}
+}
+
+impl fmt::Display for AccountId<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}{}{}", self.chain_id, COLON_SEP, self.address)
+	}
+}
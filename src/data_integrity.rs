@@ -0,0 +1,477 @@
+use crate::lib::std::fmt;
+use crate::lib::std::str::FromStr;
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+
+use crate::did::Did;
+use crate::did_doc::{DidDocument, VerificationRelationship};
+use crate::did_parser::DID_SCHEME;
+use crate::diddoc_parser::{validate_datetime, CONTEXT_PROP, TYPE_PROP};
+use crate::encoding::{multibase_decode, multibase_encode, BASE58BTC_PREFIX};
+use crate::jcs;
+use crate::jsonld::JsonLdCanonicalizer;
+use crate::jws::Jws;
+use crate::method::DidResolver;
+use crate::verify::{self, KeySelector, SignatureAlgorithm};
+
+use json::JsonValue;
+use sha2::{Digest, Sha256};
+
+// Implement Data Integrity proofs with the EdDSA cryptosuites following the specification at:
+// https://www.w3.org/TR/vc-di-eddsa/
+// Legacy Ed25519Signature2018 proofs are verified following the specification at:
+// https://w3c-ccg.github.io/lds-ed25519-2018/
+
+pub const DATA_INTEGRITY_PROOF_TYPE: &str = "DataIntegrityProof";
+pub const ED25519_SIGNATURE_2018_TYPE: &str = "Ed25519Signature2018";
+const PROOF_PROP: &str = "proof";
+const CRYPTOSUITE_PROP: &str = "cryptosuite";
+const VERIF_METHOD_PROP: &str = "verificationMethod";
+const PROOF_PURPOSE_PROP: &str = "proofPurpose";
+const CREATED_PROP: &str = "created";
+const PROOF_VALUE_PROP: &str = "proofValue";
+const JWS_PROP: &str = "jws";
+const SIGNATURE_VALUE_PROP: &str = "signatureValue";
+const B64_PROP: &str = "b64";
+const EDDSA_JCS_2022: &str = "eddsa-jcs-2022";
+const EDDSA_RDFC_2022: &str = "eddsa-rdfc-2022";
+
+/// Data Integrity cryptosuites producing Ed25519 signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cryptosuite {
+	EddsaJcs2022,
+	EddsaRdfc2022,
+}
+
+impl Cryptosuite {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::EddsaJcs2022 => EDDSA_JCS_2022,
+			Self::EddsaRdfc2022 => EDDSA_RDFC_2022,
+		}
+	}
+}
+
+impl FromStr for Cryptosuite {
+	type Err = ParseCryptosuiteError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			EDDSA_JCS_2022 => Ok(Self::EddsaJcs2022),
+			EDDSA_RDFC_2022 => Ok(Self::EddsaRdfc2022),
+			_ => Err(ParseCryptosuiteError(())),
+		}
+	}
+}
+
+impl fmt::Display for Cryptosuite {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCryptosuiteError(());
+
+impl fmt::Display for ParseCryptosuiteError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.write_str("unsupported cryptosuite")
+	}
+}
+
+/// Canonicalizes a JSON-LD document into RDF N-Quads, as required by eddsa-rdfc-2022.
+/// A `JsonLdCanonicalizer` with the bundled contexts is used when none is provided.
+pub trait RdfCanonicalizer {
+	fn canonicalize(&self, document: &JsonValue) -> Result<String, &'static str>;
+}
+
+impl<F> RdfCanonicalizer for F
+where
+	F: Fn(&JsonValue) -> Result<String, &'static str>,
+{
+	fn canonicalize(&self, document: &JsonValue) -> Result<String, &'static str> {
+		self(document)
+	}
+}
+
+/// Options of a Data Integrity proof to create.
+#[derive(Clone, Copy)]
+pub struct ProofOptions<'o> {
+	cryptosuite: Cryptosuite,
+	verification_method: &'o str,
+	proof_purpose: VerificationRelationship,
+	created: Option<&'o str>,
+	canonicalizer: Option<&'o dyn RdfCanonicalizer>,
+}
+
+impl<'o> ProofOptions<'o> {
+	pub fn new(
+		cryptosuite: Cryptosuite,
+		verification_method: &'o str,
+		proof_purpose: VerificationRelationship,
+	) -> Self {
+		ProofOptions {
+			cryptosuite,
+			verification_method,
+			proof_purpose,
+			created: None,
+			canonicalizer: None,
+		}
+	}
+
+	pub fn created_on(mut self, created: &'o str) -> Self {
+		self.created = Some(created);
+		self
+	}
+
+	pub fn with_canonicalizer(mut self, canonicalizer: &'o dyn RdfCanonicalizer) -> Self {
+		self.canonicalizer = Some(canonicalizer);
+		self
+	}
+}
+
+/// Expectations Data Integrity proofs are verified against.
+#[derive(Clone, Copy, Default)]
+pub struct ProofValidation<'v> {
+	purpose: Option<VerificationRelationship>,
+	canonicalizer: Option<&'v dyn RdfCanonicalizer>,
+}
+
+impl<'v> ProofValidation<'v> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_purpose(mut self, purpose: VerificationRelationship) -> Self {
+		self.purpose = Some(purpose);
+		self
+	}
+
+	pub fn with_canonicalizer(mut self, canonicalizer: &'v dyn RdfCanonicalizer) -> Self {
+		self.canonicalizer = Some(canonicalizer);
+		self
+	}
+}
+
+fn canonicalize(
+	cryptosuite: Cryptosuite,
+	value: &JsonValue,
+	canonicalizer: Option<&dyn RdfCanonicalizer>,
+) -> Result<String, &'static str> {
+	match cryptosuite {
+		Cryptosuite::EddsaJcs2022 => jcs::canonicalize(value),
+		Cryptosuite::EddsaRdfc2022 => rdf_canonicalize(value, canonicalizer),
+	}
+}
+
+fn rdf_canonicalize(
+	value: &JsonValue,
+	canonicalizer: Option<&dyn RdfCanonicalizer>,
+) -> Result<String, &'static str> {
+	canonicalizer
+		.ok_or("missing RDF canonicalizer")?
+		.canonicalize(value)
+}
+
+fn needs_rdf_canonicalization(proof: &JsonValue) -> bool {
+	proof[TYPE_PROP] == ED25519_SIGNATURE_2018_TYPE
+		|| proof[CRYPTOSUITE_PROP] == Cryptosuite::EddsaRdfc2022.as_str()
+}
+
+// The bundled canonicalizer parses its contexts when built, so it is built once per
+// operation, and only when no canonicalizer is provided and RDF is needed.
+fn default_canonicalizer(
+	canonicalizer: Option<&dyn RdfCanonicalizer>,
+	needed: bool,
+) -> Option<JsonLdCanonicalizer> {
+	if canonicalizer.is_none() && needed {
+		Some(JsonLdCanonicalizer::new())
+	} else {
+		None
+	}
+}
+
+fn with_default_canonicalizer<'v>(
+	validation: &ProofValidation<'v>,
+	default: Option<&'v JsonLdCanonicalizer>,
+) -> ProofValidation<'v> {
+	ProofValidation {
+		canonicalizer: validation
+			.canonicalizer
+			.or_else(|| default.map(|c| c as &dyn RdfCanonicalizer)),
+		..*validation
+	}
+}
+
+// The signed data is the hash of the proof configuration followed by the hash of the document.
+fn hash_data(
+	cryptosuite: Cryptosuite,
+	proof: &JsonValue,
+	document: &JsonValue,
+	canonicalizer: Option<&dyn RdfCanonicalizer>,
+) -> Result<Vec<u8>, &'static str> {
+	let mut proof_config = proof.clone();
+	proof_config.remove(PROOF_VALUE_PROP);
+	if cryptosuite == Cryptosuite::EddsaRdfc2022 && !document[CONTEXT_PROP].is_null() {
+		proof_config[CONTEXT_PROP] = document[CONTEXT_PROP].clone();
+	}
+	let mut data =
+		Sha256::digest(canonicalize(cryptosuite, &proof_config, canonicalizer)?.as_bytes())
+			.to_vec();
+	data.extend_from_slice(&Sha256::digest(
+		canonicalize(cryptosuite, document, canonicalizer)?.as_bytes(),
+	));
+	Ok(data)
+}
+
+#[cfg(feature = "ed25519")]
+fn sign_ed25519(secret_key: &[u8], data: &[u8]) -> Result<Vec<u8>, &'static str> {
+	use crate::lib::std::convert::TryFrom;
+	use ed25519_dalek::{Signer, SigningKey};
+
+	let secret_key = <[u8; 32]>::try_from(secret_key).map_err(|_| "invalid Ed25519 secret key")?;
+	Ok(SigningKey::from_bytes(&secret_key)
+		.sign(data)
+		.to_bytes()
+		.to_vec())
+}
+
+#[cfg(not(feature = "ed25519"))]
+fn sign_ed25519(_: &[u8], _: &[u8]) -> Result<Vec<u8>, &'static str> {
+	Err("Ed25519 support is not enabled")
+}
+
+fn context_values(context: &JsonValue) -> Vec<&JsonValue> {
+	match context {
+		JsonValue::Array(values) => values.iter().collect(),
+		value => vec![value],
+	}
+}
+
+fn controller(verification_method: &str) -> Result<String, &'static str> {
+	let did = Did::parse(verification_method)
+		.map_err(|_| "proof verificationMethod must be a DID URL")?;
+	if did.fragment().is_none() {
+		return Err("proof verificationMethod must reference a verification method");
+	}
+	Ok(format!(
+		"{}:{}:{}",
+		DID_SCHEME,
+		did.method_name(),
+		did.method_specific_id()
+	))
+}
+
+// Splits a secured document into the unsecured document and its proof set.
+fn split_proofs(document: &JsonValue) -> Result<(JsonValue, Vec<JsonValue>), &'static str> {
+	let mut unsecured = document.clone();
+	let proofs = match unsecured.remove(PROOF_PROP) {
+		JsonValue::Array(proofs) => proofs,
+		JsonValue::Null => vec![],
+		proof => vec![proof],
+	};
+	if proofs.is_empty() {
+		return Err("missing Data Integrity proof");
+	}
+	Ok((unsecured, proofs))
+}
+
+// The legacy proof signs the hashes of the canonical proof options and document with a
+// detached, unencoded JWS.
+fn verify_ed25519_signature_2018(
+	document: &JsonValue,
+	proof: &JsonValue,
+	did_doc: &DidDocument,
+	selector: KeySelector,
+	canonicalizer: Option<&dyn RdfCanonicalizer>,
+) -> Result<(), &'static str> {
+	let jws = proof[JWS_PROP].as_str().ok_or("invalid proof jws")?;
+	let mut proof_options = proof.clone();
+	proof_options.remove(JWS_PROP);
+	proof_options.remove(SIGNATURE_VALUE_PROP);
+	proof_options.remove(PROOF_VALUE_PROP);
+	if !document[CONTEXT_PROP].is_null() {
+		proof_options[CONTEXT_PROP] = document[CONTEXT_PROP].clone();
+	}
+	let mut data =
+		Sha256::digest(rdf_canonicalize(&proof_options, canonicalizer)?.as_bytes()).to_vec();
+	data.extend_from_slice(&Sha256::digest(
+		rdf_canonicalize(document, canonicalizer)?.as_bytes(),
+	));
+
+	let jws = Jws::parse_detached(jws, &data)?;
+	if jws.alg() != SignatureAlgorithm::EdDsa.as_str() || jws.header()[B64_PROP] != false {
+		return Err("invalid proof jws");
+	}
+	jws.verify_selector(did_doc, selector)
+}
+
+fn verify_single(
+	document: &JsonValue,
+	proof: &JsonValue,
+	did_doc: &DidDocument,
+	validation: &ProofValidation,
+) -> Result<(), &'static str> {
+	let proof_type = proof[TYPE_PROP].as_str();
+	if proof_type != Some(DATA_INTEGRITY_PROOF_TYPE)
+		&& proof_type != Some(ED25519_SIGNATURE_2018_TYPE)
+	{
+		return Err("unsupported proof type");
+	}
+
+	let method = proof[VERIF_METHOD_PROP]
+		.as_str()
+		.ok_or("missing proof verificationMethod")?;
+	if controller(method)? != did_doc.id() {
+		return Err("proof verificationMethod does not match DID document");
+	}
+
+	let purpose = proof[PROOF_PURPOSE_PROP]
+		.as_str()
+		.and_then(|s| VerificationRelationship::from_str(s).ok())
+		.ok_or("invalid proof purpose")?;
+	if validation
+		.purpose
+		.is_some_and(|expected| expected != purpose)
+	{
+		return Err("proof purpose does not match");
+	}
+
+	let created = &proof[CREATED_PROP];
+	if !created.is_null() && !created.as_str().is_some_and(validate_datetime) {
+		return Err("invalid proof created timestamp");
+	}
+
+	if proof_type == Some(ED25519_SIGNATURE_2018_TYPE) {
+		return verify_ed25519_signature_2018(
+			document,
+			proof,
+			did_doc,
+			KeySelector::IdFor(method, purpose),
+			validation.canonicalizer,
+		);
+	}
+	let cryptosuite = proof[CRYPTOSUITE_PROP]
+		.as_str()
+		.and_then(|s| Cryptosuite::from_str(s).ok())
+		.ok_or("unsupported cryptosuite")?;
+
+	// A proof bound to a JSON-LD context only applies to documents using that context
+	let proof_context = &proof[CONTEXT_PROP];
+	if cryptosuite == Cryptosuite::EddsaJcs2022 && !proof_context.is_null() {
+		let expected = context_values(proof_context);
+		let actual = context_values(&document[CONTEXT_PROP]);
+		if !actual.starts_with(&expected) {
+			return Err("proof @context does not match document");
+		}
+	}
+
+	let proof_value = proof[PROOF_VALUE_PROP]
+		.as_str()
+		.filter(|value| value.starts_with(BASE58BTC_PREFIX))
+		.ok_or("invalid proof value")?;
+	let signature = multibase_decode(proof_value)?;
+	let data = hash_data(cryptosuite, proof, document, validation.canonicalizer)?;
+	verify::verify(
+		did_doc,
+		KeySelector::IdFor(method, purpose),
+		Some(SignatureAlgorithm::EdDsa),
+		&data,
+		&signature,
+	)
+}
+
+/// Signs a JSON document with an Ed25519 secret key, returning the document secured
+/// with a Data Integrity proof added to any proofs it already carries.
+pub fn create_proof(
+	document: &JsonValue,
+	options: &ProofOptions,
+	secret_key: &[u8],
+) -> Result<JsonValue, &'static str> {
+	if !document.is_object() {
+		return Err("Data Integrity proofs can only secure JSON objects");
+	}
+	let mut unsecured = document.clone();
+	let proofs = unsecured.remove(PROOF_PROP);
+
+	let mut proof = JsonValue::new_object();
+	proof[TYPE_PROP] = DATA_INTEGRITY_PROOF_TYPE.into();
+	proof[CRYPTOSUITE_PROP] = options.cryptosuite.as_str().into();
+	if let Some(created) = options.created {
+		if !validate_datetime(created) {
+			return Err("invalid proof created timestamp");
+		}
+		proof[CREATED_PROP] = created.into();
+	}
+	controller(options.verification_method)?;
+	proof[VERIF_METHOD_PROP] = options.verification_method.into();
+	proof[PROOF_PURPOSE_PROP] = options.proof_purpose.as_str().into();
+	if options.cryptosuite == Cryptosuite::EddsaJcs2022 && !unsecured[CONTEXT_PROP].is_null() {
+		proof[CONTEXT_PROP] = unsecured[CONTEXT_PROP].clone();
+	}
+
+	let default = default_canonicalizer(
+		options.canonicalizer,
+		options.cryptosuite == Cryptosuite::EddsaRdfc2022,
+	);
+	let canonicalizer = options
+		.canonicalizer
+		.or_else(|| default.as_ref().map(|c| c as &dyn RdfCanonicalizer));
+	let data = hash_data(options.cryptosuite, &proof, &unsecured, canonicalizer)?;
+	proof[PROOF_VALUE_PROP] = multibase_encode(&sign_ed25519(secret_key, &data)?).into();
+
+	let mut secured = unsecured;
+	secured[PROOF_PROP] = match proofs {
+		JsonValue::Null => proof,
+		JsonValue::Array(mut proofs) => {
+			proofs.push(proof);
+			JsonValue::Array(proofs)
+		}
+		existing => JsonValue::Array(vec![existing, proof]),
+	};
+	Ok(secured)
+}
+
+/// Verifies every Data Integrity proof of a secured document with the DID document of
+/// the verification methods, which must be authorized for each proof's purpose.
+/// Legacy `Ed25519Signature2018` proofs are verified as well.
+pub fn verify_proof(
+	document: &JsonValue,
+	did_doc: &DidDocument,
+	validation: &ProofValidation,
+) -> Result<(), &'static str> {
+	let (unsecured, proofs) = split_proofs(document)?;
+	let default = default_canonicalizer(
+		validation.canonicalizer,
+		proofs.iter().any(needs_rdf_canonicalization),
+	);
+	let validation = with_default_canonicalizer(validation, default.as_ref());
+	proofs
+		.iter()
+		.try_for_each(|proof| verify_single(&unsecured, proof, did_doc, &validation))
+}
+
+/// Verifies every Data Integrity proof after resolving the DID document of its verification method.
+pub fn verify_proof_resolved<R: DidResolver>(
+	document: &JsonValue,
+	resolver: &R,
+	validation: &ProofValidation,
+) -> Result<(), &'static str> {
+	let (unsecured, proofs) = split_proofs(document)?;
+	let default = default_canonicalizer(
+		validation.canonicalizer,
+		proofs.iter().any(needs_rdf_canonicalization),
+	);
+	let validation = with_default_canonicalizer(validation, default.as_ref());
+	proofs.iter().try_for_each(|proof| {
+		let method = proof[VERIF_METHOD_PROP]
+			.as_str()
+			.ok_or("missing proof verificationMethod")?;
+		let controller = controller(method)?;
+		let json = resolver.resolve(
+			&Did::parse(&controller).map_err(|_| "proof verificationMethod must be a DID URL")?,
+		)?;
+		let did_doc = DidDocument::parse(&json).map_err(|_| "invalid DID document")?;
+		verify_single(&unsecured, proof, &did_doc, &validation)
+	})
+}
@@ -0,0 +1,314 @@
+use crate::lib::std::cmp::Ordering;
+use crate::lib::std::convert::TryFrom;
+use crate::lib::std::fmt;
+use crate::lib::std::str::FromStr;
+
+use nom::{
+	branch::alt,
+	bytes::complete::{tag, take_while1, take_while_m_n},
+	character::complete::{char, digit1, one_of},
+	combinator::{all_consuming, map, map_res, opt, recognize, verify},
+	error::{ErrorKind, ParseError},
+	sequence::{preceded, tuple},
+	IResult,
+};
+
+// Implement the xsd:dateTime datatype following the specification at:
+// https://www.w3.org/TR/xmlschema11-2/#dateTime
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const MAX_OFFSET_MINUTES: i16 = 14 * 60;
+const NANOS_DIGITS: usize = 9;
+
+/// A point in time with an explicit timezone offset, as used for the `created` and
+/// `updated` properties of DID documents.
+///
+/// Values are ordered by their instant on the timeline; equal instants written with
+/// different offsets are ordered by offset. The `24:00:00` end of day is normalized
+/// to `00:00:00` of the next day, and fractional seconds are kept to the nanosecond.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct XsdDateTime {
+	year: i32,
+	month: u8,
+	day: u8,
+	hour: u8,
+	minute: u8,
+	second: u8,
+	nanosecond: u32,
+	offset: i16,
+}
+
+impl XsdDateTime {
+	pub fn parse(input: &str) -> Result<Self, &'static str> {
+		let (_, ((year, month, day), _, (hour, minute, second, nanosecond), offset)) =
+			all_consuming(tuple((
+				date::<(&str, ErrorKind)>,
+				char('T'),
+				time,
+				timezone,
+			)))(input)
+			.map_err(|_| "invalid xsd:dateTime")?;
+
+		let (year, month, day, hour) = if hour == 24 {
+			let (year, month, day) = civil_from_days(days_from_civil(year, month, day) + 1);
+			let year = i32::try_from(year).map_err(|_| "invalid xsd:dateTime")?;
+			(year, month, day, 0)
+		} else {
+			(year, month, day, hour)
+		};
+		Ok(XsdDateTime {
+			year,
+			month,
+			day,
+			hour,
+			minute,
+			second,
+			nanosecond,
+			offset,
+		})
+	}
+
+	pub fn year(&self) -> i32 {
+		self.year
+	}
+
+	pub fn month(&self) -> u8 {
+		self.month
+	}
+
+	pub fn day(&self) -> u8 {
+		self.day
+	}
+
+	pub fn hour(&self) -> u8 {
+		self.hour
+	}
+
+	pub fn minute(&self) -> u8 {
+		self.minute
+	}
+
+	pub fn second(&self) -> u8 {
+		self.second
+	}
+
+	pub fn nanosecond(&self) -> u32 {
+		self.nanosecond
+	}
+
+	/// Returns the timezone offset from UTC, in minutes.
+	pub fn offset_minutes(&self) -> i16 {
+		self.offset
+	}
+
+	/// Returns the number of whole seconds elapsed since 1970-01-01T00:00:00Z.
+	pub fn unix_timestamp(&self) -> i64 {
+		days_from_civil(self.year, self.month, self.day) * SECONDS_PER_DAY
+			+ i64::from(self.hour) * 3600
+			+ i64::from(self.minute) * 60
+			+ i64::from(self.second)
+			- i64::from(self.offset) * 60
+	}
+}
+
+impl FromStr for XsdDateTime {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		XsdDateTime::parse(s)
+	}
+}
+
+impl Ord for XsdDateTime {
+	fn cmp(&self, other: &Self) -> Ordering {
+		(self.unix_timestamp(), self.nanosecond, self.offset).cmp(&(
+			other.unix_timestamp(),
+			other.nanosecond,
+			other.offset,
+		))
+	}
+}
+
+impl PartialOrd for XsdDateTime {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl fmt::Display for XsdDateTime {
+	/// Formats the date and time in its canonical lexical form.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.year < 0 {
+			f.write_str("-")?;
+		}
+		write!(
+			f,
+			"{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+			self.year.unsigned_abs(),
+			self.month,
+			self.day,
+			self.hour,
+			self.minute,
+			self.second
+		)?;
+		if self.nanosecond > 0 {
+			let (mut fraction, mut width) = (self.nanosecond, NANOS_DIGITS);
+			while fraction % 10 == 0 {
+				fraction /= 10;
+				width -= 1;
+			}
+			write!(f, ".{:0width$}", fraction, width = width)?;
+		}
+		match self.offset {
+			0 => f.write_str("Z"),
+			offset => write!(
+				f,
+				"{}{:02}:{:02}",
+				if offset < 0 { '-' } else { '+' },
+				offset.abs() / 60,
+				offset.abs() % 60
+			),
+		}
+	}
+}
+
+fn is_digit(c: char) -> bool {
+	c.is_ascii_digit()
+}
+
+fn year<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, i32, E> {
+	map_res(
+		verify(recognize(preceded(opt(char('-')), digit1)), |year: &str| {
+			let digits = year.trim_start_matches('-');
+			digits.len() == 4 || (digits.len() > 4 && !digits.starts_with('0'))
+		}),
+		i32::from_str,
+	)(input)
+}
+
+fn date<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (i32, u8, u8), E> {
+	verify(
+		map(
+			tuple((
+				year,
+				char('-'),
+				two_digits(1, 12),
+				char('-'),
+				two_digits(1, 31),
+			)),
+			|(year, _, month, _, day)| (year, month, day),
+		),
+		|(year, month, day)| *day <= days_in_month(*year, *month),
+	)(input)
+}
+
+fn two_digits<'a, E: ParseError<&'a str>>(
+	min: u8,
+	max: u8,
+) -> impl Fn(&'a str) -> IResult<&'a str, u8, E> {
+	verify(
+		map_res(take_while_m_n(2, 2, is_digit), u8::from_str),
+		move |value: &u8| (min..=max).contains(value),
+	)
+}
+
+fn fraction<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, u32, E> {
+	map(preceded(char('.'), digit1), |digits: &str| {
+		digits
+			.bytes()
+			.chain(crate::lib::std::iter::repeat(b'0'))
+			.take(NANOS_DIGITS)
+			.fold(0, |nanos, digit| nanos * 10 + u32::from(digit - b'0'))
+	})(input)
+}
+
+fn time<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, (u8, u8, u8, u32), E> {
+	alt((
+		map(
+			tuple((
+				two_digits(0, 23),
+				char(':'),
+				two_digits(0, 59),
+				char(':'),
+				two_digits(0, 59),
+				opt(fraction),
+			)),
+			|(hour, _, minute, _, second, nanosecond)| {
+				(hour, minute, second, nanosecond.unwrap_or_default())
+			},
+		),
+		map(
+			preceded(
+				tag("24:00:00"),
+				opt(preceded(char('.'), take_while1(|c| c == '0'))),
+			),
+			|_| (24, 0, 0, 0),
+		),
+	))(input)
+}
+
+fn timezone<'a, E: ParseError<&'a str>>(input: &'a str) -> IResult<&'a str, i16, E> {
+	alt((
+		map(char('Z'), |_| 0),
+		verify(
+			map(
+				tuple((
+					one_of("+-"),
+					two_digits(0, 14),
+					char(':'),
+					two_digits(0, 59),
+				)),
+				|(sign, hours, _, minutes)| {
+					let offset = i16::from(hours) * 60 + i16::from(minutes);
+					if sign == '-' {
+						-offset
+					} else {
+						offset
+					}
+				},
+			),
+			|offset: &i16| offset.abs() <= MAX_OFFSET_MINUTES,
+		),
+	))(input)
+}
+
+fn is_leap_year(year: i32) -> bool {
+	year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+	match month {
+		2 if is_leap_year(year) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar, see:
+// https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+	let (month, day) = (i64::from(month), i64::from(day));
+	let year = i64::from(year) - if month <= 2 { 1 } else { 0 };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146_097 + day_of_era - 719_468
+}
+
+// Inverse of days_from_civil, see:
+// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+	let days = days + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days - era * 146_097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = (month_index + 2) % 12 + 1;
+	let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+	(year, month as u8, day as u8)
+}
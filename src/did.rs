@@ -1,18 +1,51 @@
+use crate::lib::std::borrow::Cow;
 use crate::lib::std::fmt;
 use crate::lib::std::string::{String, ToString};
 use crate::lib::std::vec::Vec;
 
+use crate::datetime::XsdDateTime;
 use crate::did_parser;
+use crate::encoding::percent_decode;
+use crate::hashlink::{Hashlink, HL_PARAM};
 
-#[derive(Debug, PartialEq)]
+const DID_URL_PREFIX: &str = "did:";
+const PARAM_SEP: char = ';';
+const FRAGMENT_SEP: char = '#';
+
+// Standard DID parameters, see: https://www.w3.org/TR/did-core/#did-parameters
+pub const SERVICE_PARAM: &str = "service";
+pub const RELATIVE_REF_PARAM: &str = "relativeRef";
+pub const VERSION_ID_PARAM: &str = "versionId";
+pub const VERSION_TIME_PARAM: &str = "versionTime";
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Did<'a> {
 	method_name: &'a str,
 	method_specific_id: &'a str,
-	params: Option<Vec<DidParam<'a>>>,
+	params: Option<Cow<'a, [DidParam<'a>]>>,
 	fragment: Option<&'a str>,
 }
 
 impl<'a> Did<'a> {
+	// Used by the did! macro to expand DID literals into constants
+	#[doc(hidden)]
+	pub const fn from_parts(
+		method_name: &'a str,
+		method_specific_id: &'a str,
+		params: Option<&'a [DidParam<'a>]>,
+		fragment: Option<&'a str>,
+	) -> Self {
+		Did {
+			method_name,
+			method_specific_id,
+			params: match params {
+				Some(params) => Some(Cow::Borrowed(params)),
+				None => None,
+			},
+			fragment,
+		}
+	}
+
 	pub fn method_name(&self) -> &'a str {
 		self.method_name
 	}
@@ -32,15 +65,148 @@ impl<'a> Did<'a> {
 		self.fragment
 	}
 
+	/// Returns the value of the first parameter with the given name, if present.
+	pub fn param(&self, name: &str) -> Option<Option<&'a str>> {
+		self.params
+			.as_ref()?
+			.iter()
+			.find(|param| param.name == name)
+			.map(|param| param.value)
+	}
+
+	/// Returns the parameters in the namespace of a DID method, named relative to it,
+	/// e.g. `foo:bar` for `example:foo:bar` in the `example` namespace.
+	pub fn method_params(&self, method_name: &str) -> Vec<DidParam<'a>> {
+		self.params
+			.as_deref()
+			.into_iter()
+			.flatten()
+			.filter_map(|param| {
+				let name = param.name.strip_prefix(method_name)?.strip_prefix(':')?;
+				Some(DidParam::new(name, param.value))
+			})
+			.collect()
+	}
+
+	fn param_value(&self, name: &str, err: &'static str) -> Result<Option<&'a str>, &'static str> {
+		self.param(name)
+			.map(|value| value.filter(|value| !value.is_empty()).ok_or(err))
+			.transpose()
+	}
+
+	/// Returns the `service` parameter, which identifies a service of the DID document.
+	pub fn service(&self) -> Result<Option<&'a str>, &'static str> {
+		self.param_value(SERVICE_PARAM, "missing service parameter value")
+	}
+
+	/// Returns the decoded `relativeRef` parameter, a relative URI reference to a
+	/// resource at a service endpoint.
+	pub fn relative_ref(&self) -> Result<Option<String>, &'static str> {
+		self.param_value(RELATIVE_REF_PARAM, "missing relativeRef parameter value")?
+			.map(|value| {
+				percent_decode(value)
+					.ok()
+					.filter(|relative_ref| is_relative_ref(relative_ref))
+					.ok_or("invalid relativeRef parameter value")
+			})
+			.transpose()
+	}
+
+	/// Returns the `versionId` parameter, which identifies a version of the DID document.
+	pub fn version_id(&self) -> Result<Option<&'a str>, &'static str> {
+		self.param_value(VERSION_ID_PARAM, "missing versionId parameter value")
+	}
+
+	/// Parses the `versionTime` parameter, a UTC timestamp without sub-second precision
+	/// which identifies the version of the DID document valid at that time.
+	pub fn version_time(&self) -> Result<Option<XsdDateTime>, &'static str> {
+		self.param_value(VERSION_TIME_PARAM, "missing versionTime parameter value")?
+			.map(|value| {
+				// The DID Core spec requires UTC written as a literal `Z`, without
+				// fractional seconds, even zero ones
+				XsdDateTime::parse(value)
+					.ok()
+					.filter(|_| value.ends_with('Z') && !value.contains('.'))
+					.ok_or("invalid versionTime parameter value")
+			})
+			.transpose()
+	}
+
+	/// Parses the hashlink of the `hl` parameter, if present, which pins the resource
+	/// the DID URL dereferences to.
+	pub fn hashlink(&self) -> Result<Option<Hashlink>, &'static str> {
+		self.param_value(HL_PARAM, "missing hl parameter value")?
+			.map(Hashlink::parse)
+			.transpose()
+	}
+
+	/// Returns the canonical form of the DID URL: lowercase method name, upper-case
+	/// hex digits in percent-encodings, and no parameters or fragment without a value.
+	pub fn normalize(&self) -> String {
+		let mut normalized = format!(
+			"{}:{}:",
+			did_parser::DID_SCHEME,
+			self.method_name.to_ascii_lowercase()
+		);
+		normalized.extend(normalize_pct(self.method_specific_id));
+		for param in self.params().into_iter().flatten() {
+			if let Some(value) = param.value.filter(|value| !value.is_empty()) {
+				normalized.push(';');
+				normalized.extend(normalize_pct(param.name));
+				normalized.push('=');
+				normalized.extend(normalize_pct(value));
+			}
+		}
+		if let Some(fragment) = self.fragment.filter(|fragment| !fragment.is_empty()) {
+			normalized.push('#');
+			normalized.extend(normalize_pct(fragment));
+		}
+		normalized
+	}
+
+	/// Checks whether both DID URLs have the same normalized form.
+	pub fn is_equivalent(&self, other: &Did) -> bool {
+		self.normalize() == other.normalize()
+	}
+
+	/// Checks whether both DID URLs identify the same DID subject, ignoring their
+	/// parameters and fragment.
+	pub fn same_subject(&self, other: &Did) -> bool {
+		self.method_name.eq_ignore_ascii_case(other.method_name)
+			&& normalize_pct(self.method_specific_id).eq(normalize_pct(other.method_specific_id))
+	}
+
+	/// Resolves a relative DID URL, such as `#keys-1` or `;service=agent`, against this
+	/// DID URL following RFC 3986 reference resolution. Absolute DID URLs are returned as is.
+	pub fn join(&self, reference: &str) -> Result<String, &'static str> {
+		let joined = match reference.chars().next() {
+			_ if reference.starts_with(DID_URL_PREFIX) => reference.to_string(),
+			None | Some(FRAGMENT_SEP) => {
+				let base = self.to_string();
+				let len = base.len() - self.fragment.map_or(0, |fragment| fragment.len() + 1);
+				format!("{}{}", &base[..len], reference)
+			}
+			Some(PARAM_SEP) => format!(
+				"{}:{}:{}{}",
+				did_parser::DID_SCHEME,
+				self.method_name,
+				self.method_specific_id,
+				reference
+			),
+			_ => return Err("invalid relative DID URL"),
+		};
+		match did_parser::parse_did(&joined) {
+			Ok(_) => Ok(joined),
+			Err(_) => Err("invalid relative DID URL"),
+		}
+	}
+
 	pub fn is_valid(did_string: &'a str) -> bool {
 		did_parser::validate_did(did_string)
 	}
 
 	pub fn parse(did_string: &'a str) -> Result<Self, &'a str> {
-		match did_parser::parse_did(did_string) {
-			Ok((_, did)) => Ok(did),
-			Err(_) => Err("Failed to parse did."),
-		}
+		did_parser::parse_did(did_string).map_err(|_| "Failed to parse did.")
 	}
 }
 
@@ -63,7 +229,7 @@ impl fmt::Display for Did<'_> {
 						// .join(";")
 						.fold(&mut String::new(), |acc, p| {
 							if !acc.is_empty() {
-								acc.push_str(";");
+								acc.push(';');
 							}
 							acc.push_str(&p);
 							acc
@@ -96,15 +262,46 @@ impl<'a> DidBuilder<'a> {
 		}
 	}
 
-	pub fn with_params<T: 'a, I: 'a>(mut self, params: T) -> Self
+	pub fn with_params<T, I>(mut self, params: T) -> Self
 	where
-		T: IntoIterator<Item = I>,
-		I: Into<DidParam<'a>>,
+		T: IntoIterator<Item = I> + 'a,
+		I: Into<DidParam<'a>> + 'a,
 	{
 		self.params = Some(params.into_iter().map(|p| p.into()).collect());
 		self
 	}
 
+	/// Sets a parameter, replacing any parameter with the same name.
+	pub fn with_param(mut self, name: &'a str, value: &'a str) -> Self {
+		let params = self.params.get_or_insert_with(Vec::new);
+		match params.iter_mut().find(|param| param.name == name) {
+			Some(param) => param.value = Some(value),
+			None => params.push(DidParam::new(name, Some(value))),
+		}
+		self
+	}
+
+	pub fn with_service(self, service: &'a str) -> Self {
+		self.with_param(SERVICE_PARAM, service)
+	}
+
+	/// Sets the `relativeRef` parameter from a percent-encoded relative URI reference.
+	pub fn with_relative_ref(self, relative_ref: &'a str) -> Self {
+		self.with_param(RELATIVE_REF_PARAM, relative_ref)
+	}
+
+	pub fn with_version_id(self, version_id: &'a str) -> Self {
+		self.with_param(VERSION_ID_PARAM, version_id)
+	}
+
+	pub fn with_version_time(self, version_time: &'a str) -> Self {
+		self.with_param(VERSION_TIME_PARAM, version_time)
+	}
+
+	pub fn with_hashlink(self, hashlink: &'a str) -> Self {
+		self.with_param(HL_PARAM, hashlink)
+	}
+
 	pub fn with_fragment(mut self, fragment: &'a str) -> Self {
 		self.fragment = Some(fragment);
 		self
@@ -114,13 +311,94 @@ impl<'a> DidBuilder<'a> {
 		Did {
 			method_name: self.method_name,
 			method_specific_id: self.method_specific_id,
-			params: self.params,
+			params: self.params.map(Cow::Owned),
 			fragment: self.fragment,
 		}
 	}
+
+	/// Builds the DID, checking its parts against the generic DID syntax.
+	pub fn try_build(self) -> Result<Did<'a>, DidBuildError> {
+		if !did_parser::is_method_name(self.method_name) {
+			return Err(DidBuildError::InvalidMethodName);
+		}
+		if !did_parser::is_method_specific_id(self.method_specific_id) {
+			return Err(DidBuildError::InvalidMethodSpecificId);
+		}
+		let params = self.params.as_deref().unwrap_or_default();
+		for (i, param) in params.iter().enumerate() {
+			if !did_parser::is_param_name(param.name) {
+				return Err(DidBuildError::InvalidParamName);
+			}
+			if !param.value.is_none_or(did_parser::is_param_value) {
+				return Err(DidBuildError::InvalidParamValue);
+			}
+			if !did_parser::is_method_param(self.method_name, param.name) {
+				return Err(DidBuildError::ForeignParam);
+			}
+			if params[..i].iter().any(|other| other.name == param.name) {
+				return Err(DidBuildError::DuplicateParam);
+			}
+		}
+		if !self.fragment.is_none_or(did_parser::is_fragment) {
+			return Err(DidBuildError::InvalidFragment);
+		}
+		Ok(self.build())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DidBuildError {
+	InvalidMethodName,
+	InvalidMethodSpecificId,
+	InvalidParamName,
+	InvalidParamValue,
+	ForeignParam,
+	DuplicateParam,
+	InvalidFragment,
+}
+
+impl fmt::Display for DidBuildError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.write_str(match self {
+			Self::InvalidMethodName => "invalid DID method name",
+			Self::InvalidMethodSpecificId => "invalid DID method-specific id",
+			Self::InvalidParamName => "invalid DID parameter name",
+			Self::InvalidParamValue => "invalid DID parameter value",
+			Self::ForeignParam => "DID parameter not namespaced by the DID method",
+			Self::DuplicateParam => "duplicate DID parameter",
+			Self::InvalidFragment => "invalid DID fragment",
+		})
+	}
+}
+
+// Upper-cases the hex digits of percent-encoded octets
+fn normalize_pct(value: &str) -> impl Iterator<Item = char> + '_ {
+	let mut hex_digits = 0;
+	value.chars().map(move |c| {
+		if hex_digits > 0 {
+			hex_digits -= 1;
+			c.to_ascii_uppercase()
+		} else {
+			if c == '%' {
+				hex_digits = 2;
+			}
+			c
+		}
+	})
+}
+
+// A relative-ref has no scheme, so its first segment cannot contain a colon, see:
+// https://www.rfc-editor.org/rfc/rfc3986#section-4.2
+fn is_relative_ref(value: &str) -> bool {
+	let first_segment = value.split(&['/', '?', '#'][..]).next().unwrap_or_default();
+	!value.is_empty()
+		&& !first_segment.contains(':')
+		&& value
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || "-._~!$&'()*+,;=:@/?#%".contains(c))
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct DidParam<'a> {
 	name: &'a str,
 	value: Option<&'a str>,
@@ -135,7 +413,7 @@ impl<'a> DidParam<'a> {
 		self.value
 	}
 
-	pub fn new(name: &'a str, value: Option<&'a str>) -> DidParam<'a> {
+	pub const fn new(name: &'a str, value: Option<&'a str>) -> DidParam<'a> {
 		DidParam { name, value }
 	}
 }
@@ -187,7 +465,7 @@ impl fmt::Display for DidParam<'_> {
 
 #[cfg(test)]
 mod tests {
-	use super::{Did, DidBuilder, DidParam};
+	use super::{Did, DidBuildError, DidBuilder, DidParam};
 
 	#[test]
 	fn did_property_accessors() {
@@ -195,10 +473,13 @@ mod tests {
 			method_name: "example",
 			method_specific_id: "1234",
 			fragment: Some("keys-1"),
-			params: Some(vec![DidParam {
-				name: "example",
-				value: None,
-			}]),
+			params: Some(
+				vec![DidParam {
+					name: "example",
+					value: None,
+				}]
+				.into(),
+			),
 		};
 		assert_eq!(did.method_name(), "example");
 		assert_eq!(did.method_specific_id(), "1234");
@@ -231,22 +512,25 @@ mod tests {
 	fn did_builder_for_did_with_params() {
 		assert_eq!(
 			DidBuilder::new("example", "1234")
-				.with_params(&[("service", None), ("example:foo:bar", Some("baz"))])
+				.with_params([("service", None), ("example:foo:bar", Some("baz"))])
 				.build(),
 			Did {
 				method_name: "example",
 				method_specific_id: "1234",
 				fragment: None,
-				params: Some(vec![
-					DidParam {
-						name: "service",
-						value: None
-					},
-					DidParam {
-						name: "example:foo:bar",
-						value: Some("baz")
-					}
-				])
+				params: Some(
+					vec![
+						DidParam {
+							name: "service",
+							value: None
+						},
+						DidParam {
+							name: "example:foo:bar",
+							value: Some("baz")
+						}
+					]
+					.into()
+				)
 			}
 		)
 	}
@@ -255,23 +539,55 @@ mod tests {
 	fn did_builder_for_complex_did() {
 		assert_eq!(
 			DidBuilder::new("example", "1234")
-				.with_params(&[("service", None), ("example:foo:bar", Some("baz"))])
+				.with_params([("service", None), ("example:foo:bar", Some("baz"))])
 				.with_fragment("keys-1")
 				.build(),
 			Did {
 				method_name: "example",
 				method_specific_id: "1234",
 				fragment: Some("keys-1"),
-				params: Some(vec![
-					DidParam {
-						name: "service",
-						value: None
-					},
-					DidParam {
-						name: "example:foo:bar",
-						value: Some("baz")
-					}
-				])
+				params: Some(
+					vec![
+						DidParam {
+							name: "service",
+							value: None
+						},
+						DidParam {
+							name: "example:foo:bar",
+							value: Some("baz")
+						}
+					]
+					.into()
+				)
+			}
+		)
+	}
+
+	#[test]
+	fn did_builder_with_param_replaces_existing_param() {
+		assert_eq!(
+			DidBuilder::new("example", "1234")
+				.with_param("service", "agent")
+				.with_version_id("1")
+				.with_param("service", "files")
+				.build(),
+			Did {
+				method_name: "example",
+				method_specific_id: "1234",
+				fragment: None,
+				params: Some(
+					vec![
+						DidParam {
+							name: "service",
+							value: Some("files")
+						},
+						DidParam {
+							name: "versionId",
+							value: Some("1")
+						}
+					]
+					.into()
+				)
 			}
 		)
 	}
@@ -324,16 +640,19 @@ mod tests {
 					method_name: "example",
 					method_specific_id: "1234",
 					fragment: None,
-					params: Some(vec![
-						DidParam {
-							name: "service",
-							value: Some("agent")
-						},
-						DidParam {
-							name: "example:foo:bar",
-							value: Some("baz")
-						}
-					])
+					params: Some(
+						vec![
+							DidParam {
+								name: "service",
+								value: Some("agent")
+							},
+							DidParam {
+								name: "example:foo:bar",
+								value: Some("baz")
+							}
+						]
+						.into()
+					)
 				}
 			),
 			"did:example:1234;service=agent;example:foo:bar=baz"
@@ -346,16 +665,19 @@ mod tests {
 					method_name: "example",
 					method_specific_id: "1234",
 					fragment: Some("keys-1"),
-					params: Some(vec![
-						DidParam {
-							name: "service",
-							value: Some("agent")
-						},
-						DidParam {
-							name: "example:foo:bar",
-							value: Some("baz")
-						}
-					])
+					params: Some(
+						vec![
+							DidParam {
+								name: "service",
+								value: Some("agent")
+							},
+							DidParam {
+								name: "example:foo:bar",
+								value: Some("baz")
+							}
+						]
+						.into()
+					)
 				}
 			),
 			"did:example:1234;service=agent;example:foo:bar=baz#keys-1"
@@ -436,4 +758,61 @@ mod tests {
 			"service=agent"
 		);
 	}
+
+	#[test]
+	fn did_builder_try_build_with_valid_parts() {
+		assert_eq!(
+			DidBuilder::new("example", "1234:abcd%2F")
+				.with_params([("service", Some("agent")), ("example:foo", Some("bar"))])
+				.with_fragment("keys-1")
+				.try_build(),
+			Ok(
+				Did::parse("did:example:1234:abcd%2F;service=agent;example:foo=bar#keys-1")
+					.unwrap()
+			)
+		);
+		assert!(DidBuilder::new("example", "").try_build().is_ok());
+	}
+
+	#[test]
+	fn did_builder_try_build_with_invalid_parts() {
+		assert_eq!(
+			DidBuilder::new("EXAMPLE!", "1234").try_build(),
+			Err(DidBuildError::InvalidMethodName)
+		);
+		assert_eq!(
+			DidBuilder::new("example", "12 34").try_build(),
+			Err(DidBuildError::InvalidMethodSpecificId)
+		);
+		assert_eq!(
+			DidBuilder::new("example", "1234")
+				.with_param("serv ice", "agent")
+				.try_build(),
+			Err(DidBuildError::InvalidParamName)
+		);
+		assert_eq!(
+			DidBuilder::new("example", "1234")
+				.with_service("a#gent")
+				.try_build(),
+			Err(DidBuildError::InvalidParamValue)
+		);
+		assert_eq!(
+			DidBuilder::new("example", "1234")
+				.with_param("other:foo", "bar")
+				.try_build(),
+			Err(DidBuildError::ForeignParam)
+		);
+		assert_eq!(
+			DidBuilder::new("example", "1234")
+				.with_params([("service", "agent"), ("service", "files")])
+				.try_build(),
+			Err(DidBuildError::DuplicateParam)
+		);
+		assert_eq!(
+			DidBuilder::new("example", "1234")
+				.with_fragment("keys 1")
+				.try_build(),
+			Err(DidBuildError::InvalidFragment)
+		);
+	}
 }
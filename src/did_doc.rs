@@ -1,8 +1,17 @@
+use crate::lib::std::borrow::Cow;
 use crate::lib::std::fmt;
 use crate::lib::std::str::FromStr;
+use crate::lib::std::string::{String, ToString};
 use crate::lib::std::vec::Vec;
 
-use crate::diddoc_parser;
+use crate::datetime::XsdDateTime;
+use crate::did::Did;
+use crate::diddoc_parser::{
+	self, CONTEXT_PROP, CREATED_PROP, CTRL_PROP, ID_PROP, PUBKEYS_PROP, SERVICE_PROP, SUBJECT_PROP,
+	SVCENDP_PROP, TYPE_PROP, UPDATED_PROP,
+};
+use crate::jcs;
+use crate::verify::{self, KeySelector, SignatureAlgorithm};
 
 use json::JsonValue;
 
@@ -11,21 +20,55 @@ pub enum PublicKeyType {
 	Rsa,
 	Ed25519,
 	EcdsaSecp256k1,
+	X25519,
+	JsonWebKey,
+	EcdsaSecp256k1Recovery,
+	Blockchain,
+	Multikey,
 }
 
+const RSA_KEY_TYPE: &str = "RsaVerificationKey2018";
+const ED25519_KEY_TYPE: &str = "Ed25519VerificationKey2018";
+const SECP256K1_KEY_TYPE: &str = "Secp256k1VerificationKey2018";
+const X25519_KEY_TYPE: &str = "X25519KeyAgreementKey2019";
+const JWK_KEY_TYPE: &str = "JsonWebKey2020";
+const SECP256K1_RECOVERY_KEY_TYPE: &str = "EcdsaSecp256k1RecoveryMethod2020";
+const BLOCKCHAIN_KEY_TYPE: &str = "BlockchainVerificationMethod2021";
+const MULTIKEY_KEY_TYPE: &str = "Multikey";
+
 impl FromStr for PublicKeyType {
 	type Err = ParsePublicKeyTypeError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		match s {
-			"RsaVerificationKey2018" => Ok(Self::Rsa),
-			"Ed25519VerificationKey2018" => Ok(Self::Ed25519),
-			"Secp256k1VerificationKey2018" => Ok(Self::EcdsaSecp256k1),
+			RSA_KEY_TYPE => Ok(Self::Rsa),
+			ED25519_KEY_TYPE => Ok(Self::Ed25519),
+			SECP256K1_KEY_TYPE => Ok(Self::EcdsaSecp256k1),
+			X25519_KEY_TYPE => Ok(Self::X25519),
+			JWK_KEY_TYPE => Ok(Self::JsonWebKey),
+			SECP256K1_RECOVERY_KEY_TYPE => Ok(Self::EcdsaSecp256k1Recovery),
+			BLOCKCHAIN_KEY_TYPE => Ok(Self::Blockchain),
+			MULTIKEY_KEY_TYPE => Ok(Self::Multikey),
 			_ => Result::Err(ParsePublicKeyTypeError(())),
 		}
 	}
 }
 
+impl fmt::Display for PublicKeyType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Rsa => RSA_KEY_TYPE,
+			Self::Ed25519 => ED25519_KEY_TYPE,
+			Self::EcdsaSecp256k1 => SECP256K1_KEY_TYPE,
+			Self::X25519 => X25519_KEY_TYPE,
+			Self::JsonWebKey => JWK_KEY_TYPE,
+			Self::EcdsaSecp256k1Recovery => SECP256K1_RECOVERY_KEY_TYPE,
+			Self::Blockchain => BLOCKCHAIN_KEY_TYPE,
+			Self::Multikey => MULTIKEY_KEY_TYPE,
+		})
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsePublicKeyTypeError(());
 
@@ -40,22 +83,25 @@ pub enum PublicKeyEncoded<'a> {
 	None,
 	Pem(&'a str),
 	Jwk(&'a str),
+	JwkObject(&'a JsonValue),
 	Hex(&'a str),
 	Base64(&'a str),
 	Base58(&'a str),
 	Multibase(&'a str),
 	EthrAddress(&'a str),
+	BlockchainAccountId(&'a str),
 	Unsupported,
 }
 
 const KEYPEM_PROP: &str = "publicKeyPem";
-const KEYJWK_PROP: &str = "publicKeyJwk";
+pub(crate) const KEYJWK_PROP: &str = "publicKeyJwk";
 const KEYHEX_PROP: &str = "publicKeyHex";
-const KEYB58_PROP: &str = "publicKeyBase58";
+pub(crate) const KEYB58_PROP: &str = "publicKeyBase58";
 const KEYB64_PROP: &str = "publicKeyBase64";
-const KEYMUL_PROP: &str = "publicKeyMultibase";
+pub(crate) const KEYMUL_PROP: &str = "publicKeyMultibase";
 const KEYETH_PROP: &str = "ethereumAddress";
-pub const KEY_FORMATS: [&str; 7] = [
+pub(crate) const KEYACC_PROP: &str = "blockchainAccountId";
+pub const KEY_FORMATS: [&str; 8] = [
 	KEYPEM_PROP,
 	KEYJWK_PROP,
 	KEYHEX_PROP,
@@ -63,8 +109,27 @@ pub const KEY_FORMATS: [&str; 7] = [
 	KEYB64_PROP,
 	KEYMUL_PROP,
 	KEYETH_PROP,
+	KEYACC_PROP,
 ];
 
+impl<'a> PublicKeyEncoded<'a> {
+	// The DID document property holding the encoded key, along with its value.
+	fn to_json(&self) -> Option<(&'static str, JsonValue)> {
+		match self {
+			PublicKeyEncoded::None | PublicKeyEncoded::Unsupported => None,
+			PublicKeyEncoded::Pem(key) => Some((KEYPEM_PROP, (*key).into())),
+			PublicKeyEncoded::Jwk(key) => Some((KEYJWK_PROP, (*key).into())),
+			PublicKeyEncoded::JwkObject(key) => Some((KEYJWK_PROP, (*key).clone())),
+			PublicKeyEncoded::Hex(key) => Some((KEYHEX_PROP, (*key).into())),
+			PublicKeyEncoded::Base64(key) => Some((KEYB64_PROP, (*key).into())),
+			PublicKeyEncoded::Base58(key) => Some((KEYB58_PROP, (*key).into())),
+			PublicKeyEncoded::Multibase(key) => Some((KEYMUL_PROP, (*key).into())),
+			PublicKeyEncoded::EthrAddress(key) => Some((KEYETH_PROP, (*key).into())),
+			PublicKeyEncoded::BlockchainAccountId(key) => Some((KEYACC_PROP, (*key).into())),
+		}
+	}
+}
+
 impl<'a> From<(&'a str, &'a str)> for PublicKeyEncoded<'a> {
 	fn from(s: (&'a str, &'a str)) -> Self {
 		match s.0 {
@@ -75,6 +140,7 @@ impl<'a> From<(&'a str, &'a str)> for PublicKeyEncoded<'a> {
 			KEYB64_PROP => PublicKeyEncoded::Base64(s.1),
 			KEYMUL_PROP => PublicKeyEncoded::Multibase(s.1),
 			KEYETH_PROP => PublicKeyEncoded::EthrAddress(s.1),
+			KEYACC_PROP => PublicKeyEncoded::BlockchainAccountId(s.1),
 			_ => PublicKeyEncoded::Unsupported,
 		}
 	}
@@ -82,15 +148,16 @@ impl<'a> From<(&'a str, &'a str)> for PublicKeyEncoded<'a> {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct PublicKey<'a> {
-	id: &'a str,
+	id: Cow<'a, str>,
 	key_type: PublicKeyType,
 	controller: &'a str,
 	encoded_key: PublicKeyEncoded<'a>,
+	blockchain_account_id: Option<&'a str>,
 }
 
 impl<'a> PublicKey<'a> {
-	pub fn id(&self) -> &'a str {
-		self.id
+	pub fn id(&self) -> &str {
+		&self.id
 	}
 
 	pub fn key_type(&self) -> &PublicKeyType {
@@ -101,26 +168,51 @@ impl<'a> PublicKey<'a> {
 		self.controller
 	}
 
-	pub fn encoded_key(&self) -> &PublicKeyEncoded {
+	pub fn encoded_key(&self) -> &PublicKeyEncoded<'a> {
 		&self.encoded_key
 	}
+
+	/// The CAIP-10 account id of the key, either as its encoded key or listed
+	/// alongside it (e.g. did:pkh Solana keys also hold `publicKeyBase58`).
+	pub fn blockchain_account_id(&self) -> Option<&'a str> {
+		match self.encoded_key {
+			PublicKeyEncoded::BlockchainAccountId(account_id) => Some(account_id),
+			_ => self.blockchain_account_id,
+		}
+	}
+
+	pub fn to_json(&self) -> JsonValue {
+		let mut json = JsonValue::new_object();
+		json[ID_PROP] = self.id().into();
+		json[TYPE_PROP] = self.key_type.to_string().into();
+		json[CTRL_PROP] = self.controller.into();
+		if let Some((prop, key)) = self.encoded_key.to_json() {
+			json[prop] = key;
+		}
+		if let Some(account_id) = self.blockchain_account_id {
+			json[KEYACC_PROP] = account_id.into();
+		}
+		json
+	}
 }
 
 #[derive(Debug, PartialEq)]
 pub struct PublicKeyBuilder<'a> {
-	id: &'a str,
+	id: Cow<'a, str>,
 	key_type: PublicKeyType,
 	controller: &'a str,
 	encoded_key: PublicKeyEncoded<'a>,
+	blockchain_account_id: Option<&'a str>,
 }
 
 impl<'a> PublicKeyBuilder<'a> {
-	pub fn new(id: &'a str, key_type: PublicKeyType, controller: &'a str) -> Self {
+	pub fn new<I: Into<Cow<'a, str>>>(id: I, key_type: PublicKeyType, controller: &'a str) -> Self {
 		PublicKeyBuilder {
-			id,
+			id: id.into(),
 			key_type,
 			controller,
 			encoded_key: PublicKeyEncoded::None,
+			blockchain_account_id: None,
 		}
 	}
 
@@ -129,22 +221,90 @@ impl<'a> PublicKeyBuilder<'a> {
 		self
 	}
 
+	/// Lists a `blockchainAccountId` alongside another encoded key.
+	pub fn with_blockchain_account_id(mut self, account_id: &'a str) -> Self {
+		self.blockchain_account_id = Some(account_id);
+		self
+	}
+
 	pub fn build(self) -> PublicKey<'a> {
 		PublicKey {
 			id: self.id,
 			key_type: self.key_type,
 			controller: self.controller,
 			encoded_key: self.encoded_key,
+			blockchain_account_id: self.blockchain_account_id,
 		}
 	}
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum VerificationMethod<'a> {
-	Reference(&'a str),
+	Reference(Cow<'a, str>),
 	Embedded(PublicKey<'a>),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationRelationship {
+	Authentication,
+	AssertionMethod,
+	KeyAgreement,
+	CapabilityInvocation,
+	CapabilityDelegation,
+}
+
+const AUTHN_PROP: &str = "authentication";
+const ASSERT_PROP: &str = "assertionMethod";
+const KEYAGR_PROP: &str = "keyAgreement";
+const CAPINV_PROP: &str = "capabilityInvocation";
+const CAPDEL_PROP: &str = "capabilityDelegation";
+pub const VERIF_RELATIONSHIPS: [VerificationRelationship; 5] = [
+	VerificationRelationship::Authentication,
+	VerificationRelationship::AssertionMethod,
+	VerificationRelationship::KeyAgreement,
+	VerificationRelationship::CapabilityInvocation,
+	VerificationRelationship::CapabilityDelegation,
+];
+
+impl VerificationRelationship {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Authentication => AUTHN_PROP,
+			Self::AssertionMethod => ASSERT_PROP,
+			Self::KeyAgreement => KEYAGR_PROP,
+			Self::CapabilityInvocation => CAPINV_PROP,
+			Self::CapabilityDelegation => CAPDEL_PROP,
+		}
+	}
+}
+
+impl FromStr for VerificationRelationship {
+	type Err = ParseVerificationRelationshipError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		VERIF_RELATIONSHIPS
+			.iter()
+			.find(|rel| rel.as_str() == s)
+			.copied()
+			.ok_or(ParseVerificationRelationshipError(()))
+	}
+}
+
+impl fmt::Display for VerificationRelationship {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVerificationRelationshipError(());
+
+impl fmt::Display for ParseVerificationRelationshipError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.write_str("invalid DID verification relationship")
+	}
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ServiceEndpoint<'a> {
 	Uri(&'a str),
@@ -153,40 +313,61 @@ pub enum ServiceEndpoint<'a> {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Service<'a> {
-	id: &'a str,
+	id: Cow<'a, str>,
 	svc_type: &'a str,
 	endpoint: ServiceEndpoint<'a>,
 }
 
 impl<'a> Service<'a> {
-	pub fn new(id: &'a str, svc_type: &'a str, endpoint: ServiceEndpoint<'a>) -> Self {
+	pub fn new<I: Into<Cow<'a, str>>>(
+		id: I,
+		svc_type: &'a str,
+		endpoint: ServiceEndpoint<'a>,
+	) -> Self {
 		Service {
-			id,
+			id: id.into(),
 			svc_type,
 			endpoint,
 		}
 	}
 
-	pub fn id(&self) -> &'a str {
-		self.id
+	pub fn id(&self) -> &str {
+		&self.id
 	}
 
 	pub fn svc_type(&self) -> &'a str {
 		self.svc_type
 	}
 
-	pub fn endpoint(&self) -> &ServiceEndpoint {
+	pub fn endpoint(&self) -> &ServiceEndpoint<'a> {
 		&self.endpoint
 	}
+
+	pub fn to_json(&self) -> JsonValue {
+		let mut json = JsonValue::new_object();
+		json[ID_PROP] = self.id().into();
+		json[TYPE_PROP] = self.svc_type.into();
+		json[SVCENDP_PROP] = match self.endpoint {
+			ServiceEndpoint::Uri(uri) => uri.into(),
+			ServiceEndpoint::Object(object) => {
+				json::parse(object).unwrap_or_else(|_| object.into())
+			}
+		};
+		json
+	}
 }
 
 #[derive(Debug, Default, PartialEq)]
 pub struct DidDocument<'a> {
 	context: &'a str,
 	id: &'a str,
-	created: Option<&'a str>,
-	updated: Option<&'a str>,
+	created: Option<XsdDateTime>,
+	updated: Option<XsdDateTime>,
 	authentication: Vec<VerificationMethod<'a>>,
+	assertion_method: Vec<VerificationMethod<'a>>,
+	key_agreement: Vec<VerificationMethod<'a>>,
+	capability_invocation: Vec<VerificationMethod<'a>>,
+	capability_delegation: Vec<VerificationMethod<'a>>,
 	pub_keys: Vec<PublicKey<'a>>,
 	service: Vec<Service<'a>>,
 }
@@ -200,11 +381,11 @@ impl<'a> DidDocument<'a> {
 		self.id
 	}
 
-	pub fn created(&self) -> Option<&'a str> {
+	pub fn created(&self) -> Option<XsdDateTime> {
 		self.created
 	}
 
-	pub fn updated(&self) -> Option<&'a str> {
+	pub fn updated(&self) -> Option<XsdDateTime> {
 		self.updated
 	}
 
@@ -212,6 +393,35 @@ impl<'a> DidDocument<'a> {
 		&self.authentication[..]
 	}
 
+	pub fn assertion_method(&self) -> &[VerificationMethod<'a>] {
+		&self.assertion_method[..]
+	}
+
+	pub fn key_agreement(&self) -> &[VerificationMethod<'a>] {
+		&self.key_agreement[..]
+	}
+
+	pub fn capability_invocation(&self) -> &[VerificationMethod<'a>] {
+		&self.capability_invocation[..]
+	}
+
+	pub fn capability_delegation(&self) -> &[VerificationMethod<'a>] {
+		&self.capability_delegation[..]
+	}
+
+	pub fn verification_methods(
+		&self,
+		relationship: VerificationRelationship,
+	) -> &[VerificationMethod<'a>] {
+		match relationship {
+			VerificationRelationship::Authentication => self.authentication(),
+			VerificationRelationship::AssertionMethod => self.assertion_method(),
+			VerificationRelationship::KeyAgreement => self.key_agreement(),
+			VerificationRelationship::CapabilityInvocation => self.capability_invocation(),
+			VerificationRelationship::CapabilityDelegation => self.capability_delegation(),
+		}
+	}
+
 	pub fn pub_keys(&self) -> &[PublicKey<'a>] {
 		&self.pub_keys[..]
 	}
@@ -220,18 +430,172 @@ impl<'a> DidDocument<'a> {
 		&self.service[..]
 	}
 
+	/// Iterates over every public key of the document: the keys listed under
+	/// `publicKey`, then the keys embedded in verification relationships.
+	pub fn keys(&self) -> impl Iterator<Item = &PublicKey<'a>> + '_ {
+		self.pub_keys.iter().chain(
+			VERIF_RELATIONSHIPS
+				.iter()
+				.flat_map(move |rel| self.verification_methods(*rel))
+				.filter_map(|method| match method {
+					VerificationMethod::Embedded(key) => Some(key),
+					VerificationMethod::Reference(_) => None,
+				}),
+		)
+	}
+
+	/// Finds a public key by id, given as an absolute DID URL or relative to the
+	/// document subject (e.g. `#keys-1`).
+	pub fn find_key(&self, id: &str) -> Option<&PublicKey<'a>> {
+		self.keys().find(|key| self.matches_id(key.id(), id))
+	}
+
+	/// Iterates over the public keys of a given type.
+	pub fn keys_by_type(
+		&self,
+		key_type: PublicKeyType,
+	) -> impl Iterator<Item = &PublicKey<'a>> + '_ {
+		self.keys().filter(move |key| key.key_type == key_type)
+	}
+
+	/// Iterates over the public keys controlled by a given DID.
+	pub fn keys_by_controller<'s>(
+		&'s self,
+		controller: &'s str,
+	) -> impl Iterator<Item = &'s PublicKey<'a>> + 's {
+		self.keys().filter(move |key| key.controller == controller)
+	}
+
+	/// Iterates over the public keys of a verification relationship, with
+	/// references resolved to the keys they point to.
+	pub fn keys_for(
+		&self,
+		relationship: VerificationRelationship,
+	) -> impl Iterator<Item = &PublicKey<'a>> + '_ {
+		self.verification_methods(relationship)
+			.iter()
+			.filter_map(move |method| self.resolve(method))
+	}
+
+	/// Resolves a verification method to its public key, looking up references
+	/// among every key of the document like `find_key`.
+	pub fn resolve<'s>(&'s self, method: &'s VerificationMethod<'a>) -> Option<&'s PublicKey<'a>> {
+		match method {
+			VerificationMethod::Reference(id) => self.find_key(id),
+			VerificationMethod::Embedded(key) => Some(key),
+		}
+	}
+
+	/// Finds a service by id, given as an absolute DID URL or relative to the
+	/// document subject (e.g. `#openid` or `;service=openid`).
+	pub fn find_service(&self, id: &str) -> Option<&Service<'a>> {
+		self.service
+			.iter()
+			.find(|svc| self.matches_id(svc.id(), id))
+	}
+
+	/// Iterates over the services of a given type.
+	pub fn services_by_type<'s>(
+		&'s self,
+		svc_type: &'s str,
+	) -> impl Iterator<Item = &'s Service<'a>> + 's {
+		self.service
+			.iter()
+			.filter(move |svc| svc.svc_type == svc_type)
+	}
+
+	pub(crate) fn matches_id(&self, id: &str, query: &str) -> bool {
+		id == query || (query.starts_with(['#', ';']) && id.strip_prefix(self.id) == Some(query))
+	}
+
+	/// Serializes the DID document back into JSON.
+	pub fn to_json(&self) -> JsonValue {
+		let mut json = JsonValue::new_object();
+		json[CONTEXT_PROP] = self.context.into();
+		json[SUBJECT_PROP] = self.id.into();
+		if let Some(created) = self.created {
+			json[CREATED_PROP] = created.to_string().into();
+		}
+		if let Some(updated) = self.updated {
+			json[UPDATED_PROP] = updated.to_string().into();
+		}
+		if !self.pub_keys.is_empty() {
+			json[PUBKEYS_PROP] = self
+				.pub_keys
+				.iter()
+				.map(PublicKey::to_json)
+				.collect::<Vec<_>>()
+				.into();
+		}
+		for rel in VERIF_RELATIONSHIPS.iter() {
+			let methods = self.verification_methods(*rel);
+			if !methods.is_empty() {
+				json[rel.as_str()] = methods
+					.iter()
+					.map(|vm| match vm {
+						VerificationMethod::Reference(id) => id.as_ref().into(),
+						VerificationMethod::Embedded(key) => key.to_json(),
+					})
+					.collect::<Vec<_>>()
+					.into();
+			}
+		}
+		if !self.service.is_empty() {
+			json[SERVICE_PROP] = self
+				.service
+				.iter()
+				.map(Service::to_json)
+				.collect::<Vec<_>>()
+				.into();
+		}
+		json
+	}
+
+	/// Serializes the DID document into its canonical JSON form (RFC 8785), e.g. to be
+	/// hashed or signed.
+	pub fn to_canonical_json(&self) -> Result<String, &'static str> {
+		jcs::canonicalize(&self.to_json())
+	}
+
 	pub fn parse(json: &'a JsonValue) -> Result<Self, &'a str> {
 		diddoc_parser::parse_did_doc(json)
 	}
+
+	/// Verifies a signature over a message with the verification method selected by id,
+	/// by verification relationship, or by id restricted to a verification relationship.
+	pub fn verify<'s, S: Into<KeySelector<'s>>>(
+		&self,
+		selector: S,
+		message: &[u8],
+		signature: &[u8],
+	) -> Result<(), &'static str> {
+		verify::verify(self, selector.into(), None, message, signature)
+	}
+
+	/// Verifies a signature like `verify`, with an explicit signature algorithm that
+	/// must be supported by the selected verification method, e.g. PS256 for RSA keys.
+	pub fn verify_with<'s, S: Into<KeySelector<'s>>>(
+		&self,
+		selector: S,
+		algorithm: SignatureAlgorithm,
+		message: &[u8],
+		signature: &[u8],
+	) -> Result<(), &'static str> {
+		verify::verify(self, selector.into(), Some(algorithm), message, signature)
+	}
 }
 
 #[derive(Debug, Default, PartialEq)]
 pub struct DidDocumentBuilder<'a> {
 	context: &'a str,
 	id: &'a str,
-	created: Option<&'a str>,
-	updated: Option<&'a str>,
+	created: Option<XsdDateTime>,
+	updated: Option<XsdDateTime>,
 	authentication: Vec<VerificationMethod<'a>>,
+	assertion_method: Vec<VerificationMethod<'a>>,
+	key_agreement: Vec<VerificationMethod<'a>>,
+	capability_invocation: Vec<VerificationMethod<'a>>,
+	capability_delegation: Vec<VerificationMethod<'a>>,
 	pub_keys: Vec<PublicKey<'a>>,
 	service: Vec<Service<'a>>,
 }
@@ -245,12 +609,12 @@ impl<'a> DidDocumentBuilder<'a> {
 		}
 	}
 
-	pub fn created_on(mut self, created: &'a str) -> Self {
+	pub fn created_on(mut self, created: XsdDateTime) -> Self {
 		self.created = Some(created);
 		self
 	}
 
-	pub fn updated_on(mut self, updated: &'a str) -> Self {
+	pub fn updated_on(mut self, updated: XsdDateTime) -> Self {
 		self.updated = Some(updated);
 		self
 	}
@@ -260,6 +624,50 @@ impl<'a> DidDocumentBuilder<'a> {
 		self
 	}
 
+	pub fn with_assertion_method(mut self, assertion_method: Vec<VerificationMethod<'a>>) -> Self {
+		self.assertion_method = assertion_method;
+		self
+	}
+
+	pub fn with_key_agreement(mut self, key_agreement: Vec<VerificationMethod<'a>>) -> Self {
+		self.key_agreement = key_agreement;
+		self
+	}
+
+	pub fn with_capability_invocation(
+		mut self,
+		capability_invocation: Vec<VerificationMethod<'a>>,
+	) -> Self {
+		self.capability_invocation = capability_invocation;
+		self
+	}
+
+	pub fn with_capability_delegation(
+		mut self,
+		capability_delegation: Vec<VerificationMethod<'a>>,
+	) -> Self {
+		self.capability_delegation = capability_delegation;
+		self
+	}
+
+	pub fn with_verification_methods(
+		self,
+		relationship: VerificationRelationship,
+		methods: Vec<VerificationMethod<'a>>,
+	) -> Self {
+		match relationship {
+			VerificationRelationship::Authentication => self.with_authentication(methods),
+			VerificationRelationship::AssertionMethod => self.with_assertion_method(methods),
+			VerificationRelationship::KeyAgreement => self.with_key_agreement(methods),
+			VerificationRelationship::CapabilityInvocation => {
+				self.with_capability_invocation(methods)
+			}
+			VerificationRelationship::CapabilityDelegation => {
+				self.with_capability_delegation(methods)
+			}
+		}
+	}
+
 	pub fn with_pubkeys(mut self, pub_keys: Vec<PublicKey<'a>>) -> Self {
 		self.pub_keys = pub_keys;
 		self
@@ -277,10 +685,97 @@ impl<'a> DidDocumentBuilder<'a> {
 			created: self.created,
 			updated: self.updated,
 			authentication: self.authentication,
+			assertion_method: self.assertion_method,
+			key_agreement: self.key_agreement,
+			capability_invocation: self.capability_invocation,
+			capability_delegation: self.capability_delegation,
 			pub_keys: self.pub_keys,
 			service: self.service,
 		}
 	}
+
+	/// Builds the DID document, running the same checks as `DidDocument::parse`:
+	/// ids must be absolute DID URLs, public key ids must be unique and reference
+	/// verification methods must point to one of the public keys.
+	pub fn try_build(self) -> Result<DidDocument<'a>, DidDocumentBuildError> {
+		if !Did::is_valid(self.id) {
+			return Err(DidDocumentBuildError::InvalidSubject);
+		}
+		for (i, key) in self.pub_keys.iter().enumerate() {
+			if Did::parse(key.id()).is_err() {
+				return Err(DidDocumentBuildError::InvalidPublicKeyId);
+			}
+			if self.pub_keys[..i].iter().any(|k| k.id() == key.id()) {
+				return Err(DidDocumentBuildError::DuplicatePublicKeyId);
+			}
+		}
+		// Embedded keys must not reuse the id of a public key or of another embedded key
+		let mut embedded_ids: Vec<&str> = Vec::new();
+		for rel in VERIF_RELATIONSHIPS.iter() {
+			for method in self.verification_methods(*rel) {
+				match method {
+					VerificationMethod::Reference(id) => {
+						if !self.pub_keys.iter().any(|k| k.id() == id) {
+							return Err(DidDocumentBuildError::UnknownReference);
+						}
+					}
+					VerificationMethod::Embedded(key) => {
+						if Did::parse(key.id()).is_err() {
+							return Err(DidDocumentBuildError::InvalidPublicKeyId);
+						}
+						if self.pub_keys.iter().any(|k| k.id() == key.id())
+							|| embedded_ids.contains(&key.id())
+						{
+							return Err(DidDocumentBuildError::DuplicateEmbeddedKeyId);
+						}
+						embedded_ids.push(key.id());
+					}
+				}
+			}
+		}
+		if self.service.iter().any(|svc| Did::parse(svc.id()).is_err()) {
+			return Err(DidDocumentBuildError::InvalidServiceId);
+		}
+		Ok(self.build())
+	}
+
+	fn verification_methods(
+		&self,
+		relationship: VerificationRelationship,
+	) -> &[VerificationMethod<'a>] {
+		match relationship {
+			VerificationRelationship::Authentication => &self.authentication,
+			VerificationRelationship::AssertionMethod => &self.assertion_method,
+			VerificationRelationship::KeyAgreement => &self.key_agreement,
+			VerificationRelationship::CapabilityInvocation => &self.capability_invocation,
+			VerificationRelationship::CapabilityDelegation => &self.capability_delegation,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DidDocumentBuildError {
+	InvalidSubject,
+	InvalidPublicKeyId,
+	DuplicatePublicKeyId,
+	DuplicateEmbeddedKeyId,
+	UnknownReference,
+	InvalidServiceId,
+}
+
+impl fmt::Display for DidDocumentBuildError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.write_str(match self {
+			Self::InvalidSubject => "invalid DID subject",
+			Self::InvalidPublicKeyId => "invalid DID public key id",
+			Self::DuplicatePublicKeyId => "duplicate DID public key id",
+			Self::DuplicateEmbeddedKeyId => {
+				"duplicate public key id from embedded verification method"
+			}
+			Self::UnknownReference => "unknown reference verification method",
+			Self::InvalidServiceId => "invalid service endpoint id",
+		})
+	}
 }
 
 #[cfg(test)]
@@ -288,8 +783,9 @@ mod tests {
 	use super::diddoc_parser::GENERIC_DID_CTX;
 	use super::FromStr;
 	use super::{
-		DidDocument, DidDocumentBuilder, ParsePublicKeyTypeError, PublicKey, PublicKeyBuilder,
-		PublicKeyEncoded, PublicKeyType, Service, ServiceEndpoint, VerificationMethod,
+		DidDocument, DidDocumentBuildError, DidDocumentBuilder, ParsePublicKeyTypeError,
+		ParseVerificationRelationshipError, PublicKey, PublicKeyBuilder, PublicKeyEncoded,
+		PublicKeyType, Service, ServiceEndpoint, VerificationMethod, VerificationRelationship,
 	};
 
 	const TEST_ENCODED_KEY: &str = "0x1234567890";
@@ -342,6 +838,93 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn public_key_type_fromstr_trait_for_x25519() {
+		assert_eq!(
+			PublicKeyType::from_str("X25519KeyAgreementKey2019"),
+			Ok(PublicKeyType::X25519)
+		);
+	}
+
+	#[test]
+	fn public_key_type_fromstr_trait_for_jwk() {
+		assert_eq!(
+			PublicKeyType::from_str("JsonWebKey2020"),
+			Ok(PublicKeyType::JsonWebKey)
+		);
+	}
+
+	#[test]
+	fn public_key_type_fromstr_trait_for_secp256k1_recovery() {
+		assert_eq!(
+			PublicKeyType::from_str("EcdsaSecp256k1RecoveryMethod2020"),
+			Ok(PublicKeyType::EcdsaSecp256k1Recovery)
+		);
+	}
+
+	#[test]
+	fn public_key_type_fromstr_trait_for_blockchain() {
+		assert_eq!(
+			PublicKeyType::from_str("BlockchainVerificationMethod2021"),
+			Ok(PublicKeyType::Blockchain)
+		);
+	}
+
+	#[test]
+	fn public_key_type_fromstr_trait_for_multikey() {
+		assert_eq!(
+			PublicKeyType::from_str("Multikey"),
+			Ok(PublicKeyType::Multikey)
+		);
+	}
+
+	#[test]
+	fn public_key_type_display_trait() {
+		assert_eq!(format!("{}", PublicKeyType::Rsa), "RsaVerificationKey2018");
+		assert_eq!(
+			format!("{}", PublicKeyType::Ed25519),
+			"Ed25519VerificationKey2018"
+		);
+		assert_eq!(
+			format!("{}", PublicKeyType::EcdsaSecp256k1),
+			"Secp256k1VerificationKey2018"
+		);
+		assert_eq!(
+			format!("{}", PublicKeyType::X25519),
+			"X25519KeyAgreementKey2019"
+		);
+		assert_eq!(format!("{}", PublicKeyType::JsonWebKey), "JsonWebKey2020");
+		assert_eq!(
+			format!("{}", PublicKeyType::EcdsaSecp256k1Recovery),
+			"EcdsaSecp256k1RecoveryMethod2020"
+		);
+		assert_eq!(
+			format!("{}", PublicKeyType::Blockchain),
+			"BlockchainVerificationMethod2021"
+		);
+		assert_eq!(format!("{}", PublicKeyType::Multikey), "Multikey");
+	}
+
+	#[test]
+	fn verification_relationship_fromstr_trait() {
+		assert_eq!(
+			VerificationRelationship::from_str("keyAgreement"),
+			Ok(VerificationRelationship::KeyAgreement)
+		);
+		assert_eq!(
+			VerificationRelationship::from_str("KeyAgreement"),
+			Err(ParseVerificationRelationshipError(()))
+		);
+	}
+
+	#[test]
+	fn verification_relationship_display_trait() {
+		assert_eq!(
+			format!("{}", VerificationRelationship::CapabilityDelegation),
+			"capabilityDelegation"
+		);
+	}
+
 	#[test]
 	fn public_key_type_error_display_trait() {
 		assert_eq!(
@@ -406,6 +989,14 @@ mod tests {
 		)
 	}
 
+	#[test]
+	fn public_key_encoded_from_trait_for_blockchain_account_id() {
+		assert_eq!(
+			PublicKeyEncoded::from((super::KEYACC_PROP, TEST_ENCODED_KEY)),
+			PublicKeyEncoded::BlockchainAccountId(TEST_ENCODED_KEY)
+		)
+	}
+
 	#[test]
 	fn public_key_encoded_from_trait_for_unsupported() {
 		assert_eq!(
@@ -417,10 +1008,11 @@ mod tests {
 	#[test]
 	fn public_key_property_accessors() {
 		let pubkey = PublicKey {
-			id: "did:example:123456789abcdefghi#keys-1",
+			id: "did:example:123456789abcdefghi#keys-1".into(),
 			key_type: PublicKeyType::Ed25519,
 			controller: "did:example:123456789abcdefghi",
 			encoded_key: PublicKeyEncoded::Base58("H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"),
+			blockchain_account_id: None,
 		};
 		assert_eq!(pubkey.id(), "did:example:123456789abcdefghi#keys-1");
 		assert_eq!(pubkey.key_type(), &PublicKeyType::Ed25519);
@@ -444,12 +1036,13 @@ mod tests {
 			))
 			.build(),
 			PublicKey {
-				id: "did:example:123456789abcdefghi#keys-1",
+				id: "did:example:123456789abcdefghi#keys-1".into(),
 				key_type: PublicKeyType::Rsa,
 				controller: "did:example:123456789abcdefghi",
 				encoded_key: PublicKeyEncoded::Pem(
 					"-----BEGIN PUBLIC KEY...END PUBLIC KEY-----\r\n"
 				),
+				blockchain_account_id: None,
 			}
 		)
 	}
@@ -467,12 +1060,13 @@ mod tests {
 			))
 			.build(),
 			PublicKey {
-				id: "did:example:123456789abcdefghi#keys-2",
+				id: "did:example:123456789abcdefghi#keys-2".into(),
 				key_type: PublicKeyType::Ed25519,
 				controller: "did:example:pqrstuvwxyz0987654321",
 				encoded_key: PublicKeyEncoded::Base58(
 					"H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
 				),
+				blockchain_account_id: None,
 			}
 		)
 	}
@@ -490,12 +1084,13 @@ mod tests {
 			))
 			.build(),
 			PublicKey {
-				id: "did:example:123456789abcdefghi#keys-3",
+				id: "did:example:123456789abcdefghi#keys-3".into(),
 				key_type: PublicKeyType::EcdsaSecp256k1,
 				controller: "did:example:123456789abcdefghi",
 				encoded_key: PublicKeyEncoded::Hex(
 					"02b97c30de767f084ce3080168ee293053ba33b235d7116a3263d29f1450936b71"
 				),
+				blockchain_account_id: None,
 			}
 		)
 	}
@@ -518,16 +1113,17 @@ mod tests {
 	#[test]
 	fn did_document_property_accessors() {
 		let pubkey = PublicKey {
-			id: "did:example:123456789abcdefghi#keys-1",
+			id: "did:example:123456789abcdefghi#keys-1".into(),
 			key_type: PublicKeyType::Ed25519,
 			controller: "did:example:123456789abcdefghi",
 			encoded_key: PublicKeyEncoded::Base58("H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"),
+			blockchain_account_id: None,
 		};
 
 		let verif_method = VerificationMethod::Embedded(pubkey.clone());
 
 		let service = Service {
-			id: "did:example:123456789abcdefghi#openid",
+			id: "did:example:123456789abcdefghi#openid".into(),
 			svc_type: "OpenIdConnectVersion1.0Service",
 			endpoint: ServiceEndpoint::Uri("https://openid.example.com/"),
 		};
@@ -535,18 +1131,35 @@ mod tests {
 		let did_doc = DidDocument {
 			context: "https://www.w3.org/2019/did/v1",
 			id: "did:example:123456789abcdefghi",
-			created: Some("2002-10-10T17:00:00Z"),
-			updated: Some("2002-10-10T17:00:00Z"),
+			created: "2002-10-10T17:00:00Z".parse().ok(),
+			updated: "2002-10-10T17:00:00Z".parse().ok(),
 			authentication: vec![verif_method.clone()],
+			assertion_method: vec![VerificationMethod::Reference(pubkey.id().into())],
+			key_agreement: vec![],
+			capability_invocation: vec![VerificationMethod::Reference(pubkey.id().into())],
+			capability_delegation: vec![],
 			pub_keys: vec![pubkey.clone()],
 			service: vec![service.clone()],
 		};
 		assert_eq!(did_doc.context(), "https://www.w3.org/2019/did/v1");
 		assert_eq!(did_doc.id(), "did:example:123456789abcdefghi");
-		assert_eq!(did_doc.created(), Some("2002-10-10T17:00:00Z"));
-		assert_eq!(did_doc.created(), Some("2002-10-10T17:00:00Z"));
+		assert_eq!(did_doc.created(), "2002-10-10T17:00:00Z".parse().ok());
+		assert_eq!(did_doc.updated(), "2002-10-10T17:00:00Z".parse().ok());
 		assert_eq!(did_doc.authentication(), &[verif_method]);
-		assert_eq!(did_doc.pub_keys(), &[pubkey]);
+		assert_eq!(
+			did_doc.assertion_method(),
+			&[VerificationMethod::Reference(pubkey.id().into())]
+		);
+		assert_eq!(did_doc.key_agreement(), &[]);
+		assert_eq!(
+			did_doc.verification_methods(VerificationRelationship::CapabilityInvocation),
+			&[VerificationMethod::Reference(pubkey.id().into())]
+		);
+		assert_eq!(did_doc.capability_delegation(), &[]);
+		assert_eq!(
+			did_doc.pub_keys(),
+			crate::lib::std::slice::from_ref(&pubkey)
+		);
 		assert_eq!(did_doc.service(), &[service]);
 	}
 
@@ -589,28 +1202,31 @@ mod tests {
 				id: "did:example:123456789abcdefghi",
 				pub_keys: vec![
 					PublicKey {
-						id: "did:example:123456789abcdefghi#keys-1",
+						id: "did:example:123456789abcdefghi#keys-1".into(),
 						key_type: PublicKeyType::Rsa,
 						controller: "did:example:123456789abcdefghi",
 						encoded_key: PublicKeyEncoded::Pem(
 							"-----BEGIN PUBLIC KEY...END PUBLIC KEY-----\r\n"
 						),
+						blockchain_account_id: None,
 					},
 					PublicKey {
-						id: "did:example:123456789abcdefghi#keys-2",
+						id: "did:example:123456789abcdefghi#keys-2".into(),
 						key_type: PublicKeyType::Ed25519,
 						controller: "did:example:pqrstuvwxyz0987654321",
 						encoded_key: PublicKeyEncoded::Base58(
 							"H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
 						),
+						blockchain_account_id: None,
 					},
 					PublicKey {
-						id: "did:example:123456789abcdefghi#keys-3",
+						id: "did:example:123456789abcdefghi#keys-3".into(),
 						key_type: PublicKeyType::EcdsaSecp256k1,
 						controller: "did:example:123456789abcdefghi",
 						encoded_key: PublicKeyEncoded::Hex(
 							"02b97c30de767f084ce3080168ee293053ba33b235d7116a3263d29f1450936b71"
 						),
+						blockchain_account_id: None,
 					}
 				],
 				..Default::default()
@@ -623,7 +1239,7 @@ mod tests {
 		assert_eq!(
 			DidDocumentBuilder::new("did:example:123456789abcdefghi")
 				.with_authentication(vec![VerificationMethod::Reference(
-					"did:example:123456789abcdefghi#keys-1"
+					"did:example:123456789abcdefghi#keys-1".into()
 				)])
 				.with_pubkeys(vec![PublicKeyBuilder::new(
 					"did:example:123456789abcdefghi#keys-1",
@@ -639,15 +1255,16 @@ mod tests {
 				context: GENERIC_DID_CTX,
 				id: "did:example:123456789abcdefghi",
 				authentication: vec![VerificationMethod::Reference(
-					"did:example:123456789abcdefghi#keys-1"
+					"did:example:123456789abcdefghi#keys-1".into()
 				)],
 				pub_keys: vec![PublicKey {
-					id: "did:example:123456789abcdefghi#keys-1",
+					id: "did:example:123456789abcdefghi#keys-1".into(),
 					key_type: PublicKeyType::Rsa,
 					controller: "did:example:123456789abcdefghi",
 					encoded_key: PublicKeyEncoded::Pem(
 						"-----BEGIN PUBLIC KEY...END PUBLIC KEY-----\r\n"
 					),
+					blockchain_account_id: None,
 				}],
 				..Default::default()
 			}
@@ -674,18 +1291,47 @@ mod tests {
 				context: GENERIC_DID_CTX,
 				id: "did:example:123456789abcdefghi",
 				authentication: vec![VerificationMethod::Embedded(PublicKey {
-					id: "did:example:123456789abcdefghi#keys-2",
+					id: "did:example:123456789abcdefghi#keys-2".into(),
 					key_type: PublicKeyType::Ed25519,
 					controller: "did:example:123456789abcdefghi",
 					encoded_key: PublicKeyEncoded::Base58(
 						"H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
 					),
+					blockchain_account_id: None,
 				})],
 				..Default::default()
 			}
 		)
 	}
 
+	#[test]
+	fn did_document_builder_with_key_agreement() {
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123456789abcdefghi")
+				.with_key_agreement(vec![VerificationMethod::Reference(
+					"did:example:123456789abcdefghi#keys-1".into()
+				)])
+				.with_verification_methods(
+					VerificationRelationship::AssertionMethod,
+					vec![VerificationMethod::Reference(
+						"did:example:123456789abcdefghi#keys-2".into()
+					)]
+				)
+				.build(),
+			DidDocument {
+				context: GENERIC_DID_CTX,
+				id: "did:example:123456789abcdefghi",
+				key_agreement: vec![VerificationMethod::Reference(
+					"did:example:123456789abcdefghi#keys-1".into()
+				)],
+				assertion_method: vec![VerificationMethod::Reference(
+					"did:example:123456789abcdefghi#keys-2".into()
+				)],
+				..Default::default()
+			}
+		)
+	}
+
 	#[test]
 	fn did_document_builder_with_service_uri_endpoint() {
 		assert_eq!(
@@ -700,7 +1346,7 @@ mod tests {
 				context: GENERIC_DID_CTX,
 				id: "did:example:123456789abcdefghi",
 				service: vec![Service {
-					id: "did:example:123456789abcdefghi#openid",
+					id: "did:example:123456789abcdefghi#openid".into(),
 					svc_type: "OpenIdConnectVersion1.0Service",
 					endpoint: ServiceEndpoint::Uri("https://openid.example.com/")
 				}],
@@ -713,12 +1359,12 @@ mod tests {
 	fn did_document_builder_with_created() {
 		assert_eq!(
 			DidDocumentBuilder::new("did:example:123456789abcdefghi")
-				.created_on("2002-10-10T17:00:00Z")
+				.created_on("2002-10-10T17:00:00Z".parse().unwrap())
 				.build(),
 			DidDocument {
 				context: GENERIC_DID_CTX,
 				id: "did:example:123456789abcdefghi",
-				created: Some("2002-10-10T17:00:00Z"),
+				created: "2002-10-10T17:00:00Z".parse().ok(),
 				..Default::default()
 			}
 		)
@@ -728,14 +1374,150 @@ mod tests {
 	fn did_document_builder_with_updated() {
 		assert_eq!(
 			DidDocumentBuilder::new("did:example:123456789abcdefghi")
-				.updated_on("2002-10-10T17:00:00Z")
+				.updated_on("2002-10-10T17:00:00Z".parse().unwrap())
 				.build(),
 			DidDocument {
 				context: GENERIC_DID_CTX,
 				id: "did:example:123456789abcdefghi",
-				updated: Some("2002-10-10T17:00:00Z"),
+				updated: "2002-10-10T17:00:00Z".parse().ok(),
 				..Default::default()
 			}
 		)
 	}
+
+	#[test]
+	fn did_document_to_json_with_embedded_verification_method() {
+		let did_doc = DidDocumentBuilder::new("did:example:123456789abcdefghi")
+			.created_on("2002-10-10T17:00:00Z".parse().unwrap())
+			.with_authentication(vec![VerificationMethod::Embedded(
+				PublicKeyBuilder::new(
+					"did:example:123456789abcdefghi#keys-2",
+					PublicKeyType::EcdsaSecp256k1,
+					"did:example:123456789abcdefghi",
+				)
+				.with_encoded_key(PublicKeyEncoded::Hex(TEST_ENCODED_KEY))
+				.build(),
+			)])
+			.build();
+		assert_eq!(
+			did_doc.to_canonical_json(),
+			Ok(concat!(
+				r#"{"@context":"https://www.w3.org/2019/did/v1","#,
+				r#""authentication":[{"controller":"did:example:123456789abcdefghi","#,
+				r#""id":"did:example:123456789abcdefghi#keys-2","publicKeyHex":"0x1234567890","#,
+				r#""type":"Secp256k1VerificationKey2018"}],"#,
+				r#""created":"2002-10-10T17:00:00Z","id":"did:example:123456789abcdefghi"}"#
+			)
+			.into())
+		)
+	}
+
+	fn test_pubkey(id: &str) -> PublicKey<'_> {
+		PublicKeyBuilder::new(
+			id,
+			PublicKeyType::EcdsaSecp256k1,
+			"did:example:123456789abcdefghi",
+		)
+		.with_encoded_key(PublicKeyEncoded::Hex(TEST_ENCODED_KEY))
+		.build()
+	}
+
+	#[test]
+	fn did_document_builder_try_build() {
+		let did_doc = DidDocumentBuilder::new("did:example:123456789abcdefghi")
+			.with_pubkeys(vec![test_pubkey("did:example:123456789abcdefghi#keys-1")])
+			.with_authentication(vec![
+				VerificationMethod::Reference("did:example:123456789abcdefghi#keys-1".into()),
+				VerificationMethod::Embedded(test_pubkey("did:example:123456789abcdefghi#keys-2")),
+			])
+			.with_services(vec![Service::new(
+				"did:example:123456789abcdefghi;service=openid",
+				"OpenIdConnectVersion1.0Service",
+				ServiceEndpoint::Uri("https://openid.example.com/"),
+			)])
+			.try_build()
+			.unwrap();
+		assert_eq!(did_doc.authentication().len(), 2);
+	}
+
+	#[test]
+	fn did_document_builder_try_build_with_invalid_ids() {
+		assert_eq!(
+			DidDocumentBuilder::new("did:EXAMPLE:123").try_build(),
+			Err(DidDocumentBuildError::InvalidSubject)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_pubkeys(vec![test_pubkey("#keys-1")])
+				.try_build(),
+			Err(DidDocumentBuildError::InvalidPublicKeyId)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_key_agreement(vec![VerificationMethod::Embedded(test_pubkey("keys-1"))])
+				.try_build(),
+			Err(DidDocumentBuildError::InvalidPublicKeyId)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_services(vec![Service::new(
+					"openid",
+					"OpenIdConnectVersion1.0Service",
+					ServiceEndpoint::Uri("https://openid.example.com/"),
+				)])
+				.try_build(),
+			Err(DidDocumentBuildError::InvalidServiceId)
+		);
+	}
+
+	#[test]
+	fn did_document_builder_try_build_with_inconsistent_keys() {
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_pubkeys(vec![
+					test_pubkey("did:example:123#keys-1"),
+					test_pubkey("did:example:123#keys-1"),
+				])
+				.try_build(),
+			Err(DidDocumentBuildError::DuplicatePublicKeyId)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_pubkeys(vec![test_pubkey("did:example:123#keys-1")])
+				.with_assertion_method(vec![VerificationMethod::Embedded(test_pubkey(
+					"did:example:123#keys-1"
+				))])
+				.try_build(),
+			Err(DidDocumentBuildError::DuplicateEmbeddedKeyId)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_authentication(vec![VerificationMethod::Embedded(test_pubkey(
+					"did:example:123#keys-1"
+				))])
+				.with_assertion_method(vec![VerificationMethod::Embedded(test_pubkey(
+					"did:example:123#keys-1"
+				))])
+				.try_build(),
+			Err(DidDocumentBuildError::DuplicateEmbeddedKeyId)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_key_agreement(vec![
+					VerificationMethod::Embedded(test_pubkey("did:example:123#keys-1")),
+					VerificationMethod::Embedded(test_pubkey("did:example:123#keys-1")),
+				])
+				.try_build(),
+			Err(DidDocumentBuildError::DuplicateEmbeddedKeyId)
+		);
+		assert_eq!(
+			DidDocumentBuilder::new("did:example:123")
+				.with_pubkeys(vec![test_pubkey("did:example:123#keys-1")])
+				.with_authentication(vec![VerificationMethod::Reference(
+					"did:example:123#keys-2".into()
+				)])
+				.try_build(),
+			Err(DidDocumentBuildError::UnknownReference)
+		);
+	}
 }
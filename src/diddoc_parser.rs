@@ -1,46 +1,32 @@
+use crate::lib::std::borrow::Cow;
 use crate::lib::std::str::FromStr;
+use crate::lib::std::string::{String, ToString};
 use crate::lib::std::vec::Vec;
 
 use crate::{
+	datetime::XsdDateTime,
 	did::Did,
 	did_doc::{
 		DidDocument, DidDocumentBuilder, PublicKey, PublicKeyBuilder, PublicKeyEncoded,
-		PublicKeyType, Service, ServiceEndpoint, VerificationMethod, KEY_FORMATS,
+		PublicKeyType, Service, ServiceEndpoint, VerificationMethod, KEYACC_PROP, KEYJWK_PROP,
+		KEY_FORMATS, VERIF_RELATIONSHIPS,
 	},
 };
 
 use json::JsonValue;
-#[cfg(feature = "std")]
-use regex::Regex;
 
 pub const GENERIC_DID_CTX: &str = "https://www.w3.org/2019/did/v1";
-const CONTEXT_PROP: &str = "@context";
-const SUBJECT_PROP: &str = "id";
-const CREATED_PROP: &str = "created";
-const UPDATED_PROP: &str = "updated";
-const PUBKEYS_PROP: &str = "publicKey";
-const AUTHN_PROP: &str = "authentication";
-const SERVICE_PROP: &str = "service";
-const SVCENDP_PROP: &str = "serviceEndpoint";
-
-const ID_PROP: &str = "id";
-const TYPE_PROP: &str = "type";
-const CTRL_PROP: &str = "controller";
-
-#[cfg(feature = "std")]
-lazy_static! {
-	static ref DATETIME_REGEX: Regex = Regex::new(
-		// See https://www.w3.org/TR/xmlschema11-2/#dateTime
-		r"(?x)
-		-?([1-9][0-9]{3,}|0[0-9]{3})
-		-(0[1-9]|1[0-2])
-		-(0[1-9]|[12][0-9]|3[01])
-		T(([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9](\.[0-9]+)?|(24:00:00(\.0+)?))
-		Z
-    "
-	)
-	.unwrap();
-}
+pub const CONTEXT_PROP: &str = "@context";
+pub const SUBJECT_PROP: &str = "id";
+pub const CREATED_PROP: &str = "created";
+pub const UPDATED_PROP: &str = "updated";
+pub const PUBKEYS_PROP: &str = "publicKey";
+pub const SERVICE_PROP: &str = "service";
+pub const SVCENDP_PROP: &str = "serviceEndpoint";
+
+pub const ID_PROP: &str = "id";
+pub const TYPE_PROP: &str = "type";
+pub const CTRL_PROP: &str = "controller";
 
 #[inline]
 fn parse_str<'a>(json: &'a JsonValue, prop: &str, err: &'a str) -> Result<&'a str, &'a str> {
@@ -77,48 +63,50 @@ fn parse_did_subject(json: &JsonValue) -> Result<&str, &str> {
 	})
 }
 
-#[cfg(feature = "std")]
-fn validate_datetime(input: &str) -> bool {
-	DATETIME_REGEX.is_match(input)
-}
-
-#[cfg(not(feature = "std"))]
-fn validate_datetime(_input: &str) -> bool {
-	true
+pub(crate) fn validate_datetime(input: &str) -> bool {
+	XsdDateTime::parse(input).is_ok()
 }
 
-fn parse_did_created(json: &JsonValue) -> Result<Option<&str>, &str> {
+fn parse_did_created(json: &JsonValue) -> Result<Option<XsdDateTime>, &str> {
 	match json[CREATED_PROP].as_str() {
-		Some(created) if validate_datetime(created) => Ok(Some(created)),
-		Some(_) => Err("invalid created timestamp"),
+		Some(created) => XsdDateTime::parse(created)
+			.map(Some)
+			.map_err(|_| "invalid created timestamp"),
 		None => Ok(None),
 	}
 }
 
-fn parse_did_updated(json: &JsonValue) -> Result<Option<&str>, &str> {
+fn parse_did_updated(json: &JsonValue) -> Result<Option<XsdDateTime>, &str> {
 	match json[UPDATED_PROP].as_str() {
-		#[cfg(feature = "std")]
-		Some(created) if validate_datetime(created) => Ok(Some(created)),
-		Some(_) => Err("invalid updated timestamp"),
+		Some(updated) => XsdDateTime::parse(updated)
+			.map(Some)
+			.map_err(|_| "invalid updated timestamp"),
 		None => Ok(None),
 	}
 }
 
-fn parse_did_pubkey_list<'a>(json: &'a JsonValue) -> Result<Vec<PublicKey>, &'a str> {
+fn parse_did_pubkey_list<'a>(
+	json: &'a JsonValue,
+	base: &Did,
+) -> Result<Vec<PublicKey<'a>>, &'a str> {
 	let mut keys: Vec<PublicKey> = vec![];
 	for i in 0..json[PUBKEYS_PROP].len() {
 		let key = &json[PUBKEYS_PROP][i];
 		if key.is_null() {
 			break;
 		}
-		let pubkey = parse_did_pubkey(key, &keys)?;
+		let pubkey = parse_did_pubkey(key, &keys, base)?;
 		keys.push(pubkey);
 	}
 	Ok(keys)
 }
 
-fn parse_did_pubkey<'a>(key: &'a JsonValue, keys: &[PublicKey]) -> Result<PublicKey<'a>, &'a str> {
-	let key_id = parse_did_pubkey_id(key)?;
+fn parse_did_pubkey<'a>(
+	key: &'a JsonValue,
+	keys: &[PublicKey],
+	base: &Did,
+) -> Result<PublicKey<'a>, &'a str> {
+	let key_id = parse_did_pubkey_id(key, base)?;
 	if keys.iter().any(|k| k.id() == key_id) {
 		// return Err(format!("duplicate DID public key id '{}'", key_id).as_str());
 		return Err("duplicate DID public key id");
@@ -129,26 +117,37 @@ fn parse_did_pubkey<'a>(key: &'a JsonValue, keys: &[PublicKey]) -> Result<Public
 	let key_format = parse_did_pubkey_format(key)?;
 	let key_encoded = parse_did_pubkey_encoded(key, key_format)?;
 
-	let key = PublicKeyBuilder::new(key_id, key_type, key_ctrl)
-		.with_encoded_key(key_encoded)
-		.build();
+	let mut builder =
+		PublicKeyBuilder::new(key_id, key_type, key_ctrl).with_encoded_key(key_encoded);
+	// A blockchainAccountId may be listed alongside the encoded key
+	if key_format != KEYACC_PROP && key.has_key(KEYACC_PROP) {
+		builder = builder.with_blockchain_account_id(parse_str(
+			key,
+			KEYACC_PROP,
+			"invalid DID public key blockchainAccountId",
+		)?);
+	}
 
-	Ok(key)
+	Ok(builder.build())
 }
 
-fn parse_did_pubkey_id(key: &JsonValue) -> Result<&str, &str> {
-	parse_str_then(key, ID_PROP, "missing DID public key id", |id| {
-		if Did::is_valid(&id) {
-			Ok(id)
-		} else {
-			Err("invalid DID public key id")
-		}
-	})
+// Relative DID URLs are resolved against the DID subject, and stored in absolute form
+fn resolve_did_url<'a>(base: &Did, id: &'a str, err: &'a str) -> Result<Cow<'a, str>, &'a str> {
+	if Did::is_valid(id) {
+		Ok(Cow::Borrowed(id))
+	} else {
+		base.join(id).map(Cow::Owned).map_err(|_| err)
+	}
+}
+
+fn parse_did_pubkey_id<'a>(key: &'a JsonValue, base: &Did) -> Result<Cow<'a, str>, &'a str> {
+	parse_str(key, ID_PROP, "missing DID public key id")
+		.and_then(|id| resolve_did_url(base, id, "invalid DID public key id"))
 }
 
 fn parse_did_pubkey_type(key: &JsonValue) -> Result<PublicKeyType, &str> {
 	parse_str(key, TYPE_PROP, "missing DID public key type").and_then(|r#type| {
-		match PublicKeyType::from_str(&r#type) {
+		match PublicKeyType::from_str(r#type) {
 			Ok(key_type) => Ok(key_type),
 			Err(_) => Err("invalid DID public key type"),
 		}
@@ -170,6 +169,9 @@ fn parse_did_pubkey_encoded<'a>(
 	key: &'a JsonValue,
 	key_format: &'a str,
 ) -> Result<PublicKeyEncoded<'a>, &'a str> {
+	if key_format == KEYJWK_PROP && key[key_format].is_object() {
+		return Ok(PublicKeyEncoded::JwkObject(&key[key_format]));
+	}
 	parse_str(key, key_format, "missing DID public key controller").and_then(|key_enc| {
 		match PublicKeyEncoded::from((key_format, key_enc)) {
 			PublicKeyEncoded::Unsupported => Err("unknown DID public key format"),
@@ -178,81 +180,85 @@ fn parse_did_pubkey_encoded<'a>(
 	})
 }
 
-fn parse_did_auth_list<'a>(
+fn parse_verif_method_list<'a>(
 	json: &'a JsonValue,
+	prop: &str,
 	pub_keys: &[PublicKey],
+	embedded_ids: &mut Vec<String>,
+	base: &Did,
 ) -> Result<Vec<VerificationMethod<'a>>, &'a str> {
-	json[AUTHN_PROP]
+	json[prop]
 		.members()
-		.map(|vm| parse_auth_verif_method(vm, pub_keys))
+		.map(|vm| parse_auth_verif_method(vm, pub_keys, embedded_ids, base))
 		.collect()
 }
 
+// Embedded keys must not reuse the id of a public key or of another embedded key,
+// in the same verification relationship or in any other.
 fn parse_auth_verif_method<'a>(
 	json: &'a JsonValue,
 	pub_keys: &[PublicKey],
+	embedded_ids: &mut Vec<String>,
+	base: &Did,
 ) -> Result<VerificationMethod<'a>, &'a str> {
 	if json.is_string() {
-		let did = parse_auth_verif_method_ref(json)?;
+		let did = parse_auth_verif_method_ref(json, base)?;
 		if !pub_keys.iter().any(|k| k.id() == did) {
 			return Err("unknown reference verification method");
 		}
 		Ok(VerificationMethod::Reference(did))
 	} else if json.is_object() {
-		let key = parse_did_pubkey(json, Vec::<PublicKey>::new().as_slice())?;
-		if pub_keys.iter().any(|k| k.id() == key.id()) {
+		let key = parse_did_pubkey(json, &[], base)?;
+		if pub_keys.iter().any(|k| k.id() == key.id())
+			|| embedded_ids.iter().any(|id| id == key.id())
+		{
 			return Err("duplicate public key id from embedded verification method");
 		}
+		embedded_ids.push(key.id().to_string());
 		Ok(VerificationMethod::Embedded(key))
 	} else {
 		Err("invalid embedded verification method")
 	}
 }
 
-fn parse_auth_verif_method_ref(json: &JsonValue) -> Result<&str, &str> {
+fn parse_auth_verif_method_ref<'a>(
+	json: &'a JsonValue,
+	base: &Did,
+) -> Result<Cow<'a, str>, &'a str> {
 	json.as_str()
 		.ok_or("invalid reference verification method")
-		.and_then(|did| {
-			if Did::is_valid(did) {
-				Ok(did)
-			} else {
-				Err("invalid reference verification method")
-			}
-		})
+		.and_then(|did| resolve_did_url(base, did, "invalid reference verification method"))
 }
 
-fn parse_did_service_list<'a>(json: &'a JsonValue) -> Result<Vec<Service<'a>>, &'a str> {
+fn parse_did_service_list<'a>(
+	json: &'a JsonValue,
+	base: &Did,
+) -> Result<Vec<Service<'a>>, &'a str> {
 	json[SERVICE_PROP]
 		.members()
-		.map(parse_did_svc_endpoint)
+		.map(|svc| parse_did_svc_endpoint(svc, base))
 		.collect()
 }
 
-fn parse_did_svc_endpoint(json: &JsonValue) -> Result<Service<'_>, &str> {
-	let svc_id = parse_did_svc_endpoint_id(json)?;
+fn parse_did_svc_endpoint<'a>(json: &'a JsonValue, base: &Did) -> Result<Service<'a>, &'a str> {
+	let svc_id = parse_did_svc_endpoint_id(json, base)?;
 	let svc_type = parse_did_svc_endpoint_type(json)?;
 	let svc_endpoint = parse_did_svc_endpoint_value(json)?;
 	Ok(Service::new(svc_id, svc_type, svc_endpoint))
 }
 
-fn parse_did_svc_endpoint_id(key: &JsonValue) -> Result<&str, &str> {
-	parse_str_then(key, ID_PROP, "missing service endpoint id", |id| {
-		if Did::is_valid(&id) {
-			Ok(id)
-		} else {
-			Err("invalid service endpoint id")
-		}
-	})
+fn parse_did_svc_endpoint_id<'a>(key: &'a JsonValue, base: &Did) -> Result<Cow<'a, str>, &'a str> {
+	parse_str(key, ID_PROP, "missing service endpoint id")
+		.and_then(|id| resolve_did_url(base, id, "invalid service endpoint id"))
 }
 
 fn parse_did_svc_endpoint_type(json: &JsonValue) -> Result<&str, &str> {
 	parse_str(json, TYPE_PROP, "missing service endpoint type")
 }
 
-fn parse_did_svc_endpoint_value(json: &JsonValue) -> Result<ServiceEndpoint, &str> {
+fn parse_did_svc_endpoint_value(json: &JsonValue) -> Result<ServiceEndpoint<'_>, &str> {
 	if json[SVCENDP_PROP].is_string() {
-		parse_str(json, SVCENDP_PROP, "invalid service endpoint URI")
-			.and_then(|uri| Ok(ServiceEndpoint::Uri(uri)))
+		parse_str(json, SVCENDP_PROP, "invalid service endpoint URI").map(ServiceEndpoint::Uri)
 	} else if json.is_object() {
 		Err("invalid service endpoint JSON-LD object : unimplemented")
 	} else {
@@ -263,16 +269,20 @@ fn parse_did_svc_endpoint_value(json: &JsonValue) -> Result<ServiceEndpoint, &st
 pub fn parse_did_doc(json: &JsonValue) -> Result<DidDocument<'_>, &str> {
 	let _ctx = parse_did_context(json)?; //TODO: handle additional contexts beyond generic DID context
 	let sub = parse_did_subject(json)?;
+	let base = Did::parse(sub).map_err(|_| "invalid DID subject")?;
 	let created = parse_did_created(json)?;
 	let updated = parse_did_updated(json)?;
-	let keys = parse_did_pubkey_list(json)?;
-	let auth = parse_did_auth_list(json, &keys[..])?;
-	let services = parse_did_service_list(json)?; //TODO: validate URI, handle embedded service object + extra props
-
-	let mut did_doc = DidDocumentBuilder::new(sub)
-		.with_authentication(auth)
-		.with_pubkeys(keys)
-		.with_services(services);
+	let keys = parse_did_pubkey_list(json, &base)?;
+	let mut did_doc = DidDocumentBuilder::new(sub);
+	let mut embedded_ids = Vec::new();
+	for rel in VERIF_RELATIONSHIPS.iter() {
+		let methods =
+			parse_verif_method_list(json, rel.as_str(), &keys[..], &mut embedded_ids, &base)?;
+		did_doc = did_doc.with_verification_methods(*rel, methods);
+	}
+	let services = parse_did_service_list(json, &base)?; //TODO: validate URI, handle embedded service object + extra props
+
+	did_doc = did_doc.with_pubkeys(keys).with_services(services);
 	if let Some(created) = created {
 		did_doc = did_doc.created_on(created);
 	}
@@ -0,0 +1,145 @@
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+// Helpers for the multiformats used by DID methods, see:
+// https://github.com/multiformats/multibase
+// https://github.com/multiformats/multicodec
+// https://github.com/multiformats/multihash
+
+pub const BASE58BTC_PREFIX: char = 'z';
+pub const BASE64URL_PREFIX: char = 'u';
+
+pub const ED25519_PUB_CODEC: u64 = 0xed;
+pub const X25519_PUB_CODEC: u64 = 0xec;
+pub const SECP256K1_PUB_CODEC: u64 = 0xe7;
+pub const P256_PUB_CODEC: u64 = 0x1200;
+pub const JSON_CODEC: u64 = 0x0200;
+pub const SHA2_256_CODEC: u64 = 0x12;
+
+pub fn base58_encode(bytes: &[u8]) -> String {
+	bs58::encode(bytes).into_string()
+}
+
+pub fn base58_decode(input: &str) -> Result<Vec<u8>, &'static str> {
+	bs58::decode(input)
+		.into_vec()
+		.map_err(|_| "invalid base58 encoding")
+}
+
+pub fn base64_decode(input: &str) -> Result<Vec<u8>, &'static str> {
+	STANDARD.decode(input).map_err(|_| "invalid base64 encoding")
+}
+
+pub fn base64url_encode(bytes: &[u8]) -> String {
+	URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn base64url_decode(input: &str) -> Result<Vec<u8>, &'static str> {
+	if input.ends_with('=') {
+		URL_SAFE.decode(input)
+	} else {
+		URL_SAFE_NO_PAD.decode(input)
+	}
+	.map_err(|_| "invalid base64url encoding")
+}
+
+pub fn hex_encode(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn hex_decode(input: &str) -> Result<Vec<u8>, &'static str> {
+	let input = input.strip_prefix("0x").unwrap_or(input);
+	if !input.len().is_multiple_of(2) || !input.bytes().all(|b| b.is_ascii_hexdigit()) {
+		return Err("invalid hex encoding");
+	}
+	(0..input.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&input[i..i + 2], 16).map_err(|_| "invalid hex encoding"))
+		.collect()
+}
+
+pub fn percent_decode(input: &str) -> Result<String, &'static str> {
+	let mut decoded = Vec::with_capacity(input.len());
+	let mut bytes = input.bytes();
+	while let Some(byte) = bytes.next() {
+		if byte != b'%' {
+			decoded.push(byte);
+			continue;
+		}
+		let hex = [bytes.next(), bytes.next()];
+		match hex {
+			[Some(hi), Some(lo)] if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {
+				decoded.push((hex_digit(hi) << 4) | hex_digit(lo))
+			}
+			_ => return Err("invalid percent encoding"),
+		}
+	}
+	String::from_utf8(decoded).map_err(|_| "invalid percent encoding")
+}
+
+fn hex_digit(digit: u8) -> u8 {
+	match digit {
+		b'0'..=b'9' => digit - b'0',
+		b'a'..=b'f' => digit - b'a' + 10,
+		_ => digit - b'A' + 10,
+	}
+}
+
+pub fn multibase_encode(bytes: &[u8]) -> String {
+	let mut encoded = String::new();
+	encoded.push(BASE58BTC_PREFIX);
+	encoded.push_str(&base58_encode(bytes));
+	encoded
+}
+
+pub fn multibase_decode(input: &str) -> Result<Vec<u8>, &'static str> {
+	let mut chars = input.chars();
+	match chars.next() {
+		Some(BASE58BTC_PREFIX) => base58_decode(chars.as_str()),
+		Some(BASE64URL_PREFIX) => base64url_decode(chars.as_str()),
+		_ => Err("unsupported multibase encoding"),
+	}
+}
+
+pub fn varint_encode(mut value: u64, out: &mut Vec<u8>) {
+	while value >= 0x80 {
+		out.push((value as u8) | 0x80);
+		value >>= 7;
+	}
+	out.push(value as u8);
+}
+
+pub fn varint_decode(bytes: &[u8]) -> Result<(u64, &[u8]), &'static str> {
+	let mut value = 0u64;
+	for (i, byte) in bytes.iter().enumerate().take(9) {
+		value |= u64::from(byte & 0x7f) << (7 * i);
+		if byte & 0x80 == 0 {
+			return Ok((value, &bytes[i + 1..]));
+		}
+	}
+	Err("invalid varint encoding")
+}
+
+pub fn multicodec_encode(codec: u64, bytes: &[u8]) -> Vec<u8> {
+	let mut encoded = Vec::with_capacity(bytes.len() + 3);
+	varint_encode(codec, &mut encoded);
+	encoded.extend_from_slice(bytes);
+	encoded
+}
+
+pub fn multicodec_decode(bytes: &[u8]) -> Result<(u64, &[u8]), &'static str> {
+	varint_decode(bytes)
+}
+
+pub fn sha256_multihash(bytes: &[u8]) -> Vec<u8> {
+	let digest = Sha256::digest(bytes);
+	let mut multihash = Vec::with_capacity(digest.len() + 2);
+	varint_encode(SHA2_256_CODEC, &mut multihash);
+	varint_encode(digest.len() as u64, &mut multihash);
+	multihash.extend_from_slice(&digest);
+	multihash
+}
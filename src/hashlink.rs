@@ -0,0 +1,124 @@
+use crate::lib::std::fmt;
+use crate::lib::std::vec::Vec;
+
+use crate::encoding::{multibase_decode, multibase_encode, varint_decode, varint_encode};
+use crate::jcs;
+
+use json::JsonValue;
+use sha2::{Digest, Sha256, Sha512};
+
+// Implement Cryptographic Hyperlinks (hashlinks) following the specification at:
+// https://datatracker.ietf.org/doc/html/draft-sporny-hashlink
+// See also the hl DID parameter: https://www.w3.org/TR/did-core/#did-parameters
+
+pub const HL_PARAM: &str = "hl";
+const SHA2_256_CODE: u64 = 0x12;
+const SHA2_512_CODE: u64 = 0x13;
+
+/// Hash algorithms a hashlink digest may be computed with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+	Sha2_256,
+	Sha2_512,
+}
+
+impl HashAlgorithm {
+	fn code(self) -> u64 {
+		match self {
+			Self::Sha2_256 => SHA2_256_CODE,
+			Self::Sha2_512 => SHA2_512_CODE,
+		}
+	}
+
+	fn from_code(code: u64) -> Option<Self> {
+		match code {
+			SHA2_256_CODE => Some(Self::Sha2_256),
+			SHA2_512_CODE => Some(Self::Sha2_512),
+			_ => None,
+		}
+	}
+
+	fn digest_len(self) -> usize {
+		match self {
+			Self::Sha2_256 => 32,
+			Self::Sha2_512 => 64,
+		}
+	}
+
+	fn digest(self, data: &[u8]) -> Vec<u8> {
+		match self {
+			Self::Sha2_256 => Sha256::digest(data).to_vec(),
+			Self::Sha2_512 => Sha512::digest(data).to_vec(),
+		}
+	}
+}
+
+/// The multibase-encoded multihash of a resource, as carried by the `hl` DID parameter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hashlink {
+	algorithm: HashAlgorithm,
+	digest: Vec<u8>,
+}
+
+impl Hashlink {
+	/// Hashes a resource into a hashlink.
+	pub fn new(algorithm: HashAlgorithm, data: &[u8]) -> Self {
+		Hashlink {
+			algorithm,
+			digest: algorithm.digest(data),
+		}
+	}
+
+	/// Hashes the canonical form (RFC 8785) of a JSON document into a hashlink.
+	pub fn from_json(algorithm: HashAlgorithm, json: &JsonValue) -> Result<Self, &'static str> {
+		Ok(Self::new(algorithm, jcs::canonicalize(json)?.as_bytes()))
+	}
+
+	pub fn parse(value: &str) -> Result<Self, &'static str> {
+		let multihash = multibase_decode(value).map_err(|_| "invalid hashlink encoding")?;
+		let (code, rest) = varint_decode(&multihash).map_err(|_| "invalid hashlink multihash")?;
+		let (length, digest) = varint_decode(rest).map_err(|_| "invalid hashlink multihash")?;
+		let algorithm =
+			HashAlgorithm::from_code(code).ok_or("unsupported hashlink hash algorithm")?;
+		if digest.len() as u64 != length || digest.len() != algorithm.digest_len() {
+			return Err("invalid hashlink digest length");
+		}
+		Ok(Hashlink {
+			algorithm,
+			digest: digest.to_vec(),
+		})
+	}
+
+	pub fn algorithm(&self) -> HashAlgorithm {
+		self.algorithm
+	}
+
+	pub fn digest(&self) -> &[u8] {
+		&self.digest
+	}
+
+	/// Checks that a resource matches the hashlink digest.
+	pub fn verify(&self, data: &[u8]) -> Result<(), &'static str> {
+		if self.algorithm.digest(data) != self.digest {
+			return Err("hashlink digest does not match");
+		}
+		Ok(())
+	}
+
+	/// Checks that the canonical form (RFC 8785) of a JSON document matches the hashlink digest.
+	/// For a DID document, pass the fetched JSON rather than a re-serialized `DidDocument`,
+	/// so that properties it does not model are covered by the hash.
+	pub fn verify_json(&self, json: &JsonValue) -> Result<(), &'static str> {
+		self.verify(jcs::canonicalize(json)?.as_bytes())
+	}
+}
+
+impl fmt::Display for Hashlink {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut multihash = Vec::with_capacity(self.digest.len() + 2);
+		varint_encode(self.algorithm.code(), &mut multihash);
+		varint_encode(self.digest.len() as u64, &mut multihash);
+		multihash.extend_from_slice(&self.digest);
+		f.write_str(&multibase_encode(&multihash))
+	}
+}
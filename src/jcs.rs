@@ -0,0 +1,127 @@
+use crate::lib::std::fmt::Write;
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+
+use json::JsonValue;
+
+// Implement the JSON Canonicalization Scheme (JCS) defined at:
+// https://www.rfc-editor.org/rfc/rfc8785
+
+/// Serializes a JSON value in its canonical form: object properties sorted by their
+/// UTF-16 code units, no whitespace, minimal string escaping and numbers formatted
+/// like ECMAScript. Fails on numbers that are not finite IEEE 754 doubles.
+pub fn canonicalize(value: &JsonValue) -> Result<String, &'static str> {
+	let mut out = String::new();
+	write_value(value, &mut out)?;
+	Ok(out)
+}
+
+/// Serializes a JSON value in its canonical form, as UTF-8 bytes ready to be hashed or signed.
+pub fn canonicalize_to_vec(value: &JsonValue) -> Result<Vec<u8>, &'static str> {
+	canonicalize(value).map(String::into_bytes)
+}
+
+fn write_value(value: &JsonValue, out: &mut String) -> Result<(), &'static str> {
+	match value {
+		JsonValue::Null => out.push_str("null"),
+		JsonValue::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+		JsonValue::Short(s) => write_string(s.as_str(), out),
+		JsonValue::String(s) => write_string(s, out),
+		// The decimal form of the number is exact, and parsing it rounds correctly
+		JsonValue::Number(_) => match value.dump().parse::<f64>() {
+			Ok(number) if number.is_finite() => write_number(number, out),
+			_ => return Err("JSON number is not finite"),
+		},
+		JsonValue::Array(members) => {
+			out.push('[');
+			for (i, member) in members.iter().enumerate() {
+				if i > 0 {
+					out.push(',');
+				}
+				write_value(member, out)?;
+			}
+			out.push(']');
+		}
+		JsonValue::Object(object) => {
+			// Properties are sorted by their UTF-16 code units
+			let mut entries: Vec<(&str, &JsonValue)> = object.iter().collect();
+			entries.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+			out.push('{');
+			for (i, (key, member)) in entries.into_iter().enumerate() {
+				if i > 0 {
+					out.push(',');
+				}
+				write_string(key, out);
+				out.push(':');
+				write_value(member, out)?;
+			}
+			out.push('}');
+		}
+	}
+	Ok(())
+}
+
+// Formats a finite number like ECMAScript's Number.prototype.toString, see:
+// https://tc39.es/ecma262/#sec-numeric-types-number-tostring
+fn write_number(number: f64, out: &mut String) {
+	if number == 0.0 {
+		out.push('0');
+		return;
+	}
+	if number < 0.0 {
+		out.push('-');
+	}
+	let number = if number < 0.0 { -number } else { number };
+	// The shortest digits that round-trip, e.g. 1.2345e-7, choosing the closest ones
+	// rounded half to even when several are as short
+	let mut formatted = format!("{:e}", number);
+	let precision = formatted.find('e').unwrap_or_default().saturating_sub(2);
+	let closest = format!("{:.*e}", precision, number);
+	if closest.parse::<f64>() == Ok(number) {
+		formatted = closest;
+	}
+	let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+	let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+	let k = digits.len() as i32;
+	let n = exponent.parse::<i32>().unwrap_or_default() + 1;
+
+	if k <= n && n <= 21 {
+		out.push_str(&digits);
+		(k..n).for_each(|_| out.push('0'));
+	} else if 0 < n && n <= 21 {
+		out.push_str(&digits[..n as usize]);
+		out.push('.');
+		out.push_str(&digits[n as usize..]);
+	} else if -6 < n && n <= 0 {
+		out.push_str("0.");
+		(n..0).for_each(|_| out.push('0'));
+		out.push_str(&digits);
+	} else {
+		out.push_str(&digits[..1]);
+		if k > 1 {
+			out.push('.');
+			out.push_str(&digits[1..]);
+		}
+		let _ = write!(out, "e{}{}", if n > 0 { '+' } else { '-' }, (n - 1).abs());
+	}
+}
+
+fn write_string(s: &str, out: &mut String) {
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\u{8}' => out.push_str("\\b"),
+			'\u{c}' => out.push_str("\\f"),
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => {
+				let _ = write!(out, "\\u{:04x}", c as u32);
+			}
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+}
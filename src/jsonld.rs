@@ -0,0 +1,98 @@
+use crate::lib::std::string::{String, ToString};
+
+use crate::data_integrity::RdfCanonicalizer;
+use crate::diddoc_parser::GENERIC_DID_CTX;
+use crate::rdf;
+
+use json::JsonValue;
+
+// Implement JSON-LD to RDF canonicalization following the specifications at:
+// https://www.w3.org/TR/json-ld11-api/
+// https://www.w3.org/TR/rdf-canon/
+
+mod context;
+mod expansion;
+mod to_rdf;
+
+pub use context::Documents;
+
+pub const DID_V1_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+pub const SECURITY_V1_CONTEXT: &str = "https://w3id.org/security/v1";
+pub const SECURITY_V2_CONTEXT: &str = "https://w3id.org/security/v2";
+pub const CREDENTIALS_V1_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+pub const ED25519_2018_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2018/v1";
+
+const BUNDLED_CONTEXTS: [(&str, &str); 7] = [
+	(
+		DID_V1_CONTEXT,
+		include_str!("jsonld/contexts/did-v1.jsonld"),
+	),
+	(
+		"https://w3id.org/did/v1",
+		include_str!("jsonld/contexts/did-2019-v1.jsonld"),
+	),
+	(
+		GENERIC_DID_CTX,
+		include_str!("jsonld/contexts/did-2019-v1.jsonld"),
+	),
+	(
+		SECURITY_V1_CONTEXT,
+		include_str!("jsonld/contexts/security-v1.jsonld"),
+	),
+	(
+		SECURITY_V2_CONTEXT,
+		include_str!("jsonld/contexts/security-v2.jsonld"),
+	),
+	(
+		CREDENTIALS_V1_CONTEXT,
+		include_str!("jsonld/contexts/credentials-v1.jsonld"),
+	),
+	(
+		ED25519_2018_CONTEXT,
+		include_str!("jsonld/contexts/ed25519-2018-v1.jsonld"),
+	),
+];
+
+/// Canonicalizes JSON-LD documents with URDNA2015 (RDFC-1.0) into N-Quads.
+/// Remote contexts are never fetched: only the bundled contexts and those added
+/// with `with_context` can be referenced.
+#[derive(Clone, Debug)]
+pub struct JsonLdCanonicalizer {
+	contexts: Documents,
+}
+
+impl JsonLdCanonicalizer {
+	pub fn new() -> Self {
+		let contexts = BUNDLED_CONTEXTS
+			.iter()
+			.map(|(url, context)| {
+				let context = json::parse(context).expect("bundled JSON-LD contexts are valid");
+				(url.to_string(), context)
+			})
+			.collect();
+		JsonLdCanonicalizer { contexts }
+	}
+
+	/// Makes a context document available under a URL.
+	pub fn with_context(mut self, url: &str, context: JsonValue) -> Self {
+		self.contexts.insert(url.to_string(), context);
+		self
+	}
+
+	pub fn contexts(&self) -> &Documents {
+		&self.contexts
+	}
+}
+
+impl Default for JsonLdCanonicalizer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RdfCanonicalizer for JsonLdCanonicalizer {
+	fn canonicalize(&self, document: &JsonValue) -> Result<String, &'static str> {
+		let expanded = expansion::expand(document, &self.contexts)?;
+		rdf::canonicalize(&to_rdf::to_rdf(&expanded))
+	}
+}
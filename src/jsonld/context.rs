@@ -0,0 +1,414 @@
+use crate::lib::std::boxed::Box;
+use crate::lib::std::collections::BTreeMap;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+
+use json::JsonValue;
+
+// Implement JSON-LD context processing following the specification at:
+// https://www.w3.org/TR/json-ld11-api/#context-processing-algorithms
+
+const KEYWORDS: [&str; 24] = [
+	"@base",
+	"@container",
+	"@context",
+	"@default",
+	"@direction",
+	"@graph",
+	"@id",
+	"@import",
+	"@included",
+	"@index",
+	"@json",
+	"@language",
+	"@list",
+	"@nest",
+	"@none",
+	"@prefix",
+	"@propagate",
+	"@protected",
+	"@reverse",
+	"@set",
+	"@type",
+	"@value",
+	"@version",
+	"@vocab",
+];
+const CONTAINERS: [&str; 7] = [
+	"@graph",
+	"@id",
+	"@index",
+	"@language",
+	"@list",
+	"@set",
+	"@type",
+];
+const GEN_DELIMS: [char; 7] = [':', '/', '?', '#', '[', ']', '@'];
+// Bounds the nesting of remote contexts referencing other remote contexts
+const MAX_REMOTE_CONTEXTS: usize = 16;
+
+/// JSON-LD documents holding the remote contexts that may be referenced, by URL.
+pub type Documents = BTreeMap<String, JsonValue>;
+
+pub fn is_keyword(value: &str) -> bool {
+	KEYWORDS.contains(&value)
+}
+
+// Reserved for future keywords and ignored
+fn looks_like_keyword(value: &str) -> bool {
+	value.len() > 1 && value.starts_with('@') && value[1..].chars().all(|c| c.is_ascii_alphabetic())
+}
+
+pub fn is_absolute_iri(value: &str) -> bool {
+	match value.find(':') {
+		Some(pos) if pos > 0 => {
+			let scheme = &value[..pos];
+			scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+				&& scheme
+					.chars()
+					.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+		}
+		_ => false,
+	}
+}
+
+pub fn is_blank_node(value: &str) -> bool {
+	value.starts_with("_:")
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TermDefinition {
+	pub iri: Option<String>,
+	pub prefix: bool,
+	pub reverse: bool,
+	pub type_mapping: Option<String>,
+	pub language: Option<Option<String>>,
+	pub container: Vec<String>,
+	pub context: Option<JsonValue>,
+}
+
+impl TermDefinition {
+	pub fn has_container(&self, container: &str) -> bool {
+		self.container.iter().any(|c| c == container)
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ActiveContext {
+	pub terms: BTreeMap<String, TermDefinition>,
+	pub vocab: Option<String>,
+	pub language: Option<String>,
+	// Context to revert to when entering a node object, set by non-propagated contexts
+	pub previous: Option<Box<ActiveContext>>,
+}
+
+impl ActiveContext {
+	pub fn term(&self, term: &str) -> Option<&TermDefinition> {
+		self.terms.get(term)
+	}
+
+	/// Expands a term, compact IRI or relative IRI into an absolute IRI or keyword.
+	pub fn expand_iri(&self, value: &str, vocab: bool) -> Option<String> {
+		if is_keyword(value) {
+			return Some(value.to_string());
+		}
+		if looks_like_keyword(value) {
+			return None;
+		}
+		if let Some(definition) = self.terms.get(value) {
+			match &definition.iri {
+				Some(iri) if is_keyword(iri) => return Some(iri.clone()),
+				iri if vocab => return iri.clone(),
+				_ => {}
+			}
+		}
+		if let Some(pos) = value.find(':').filter(|pos| *pos > 0) {
+			let (prefix, suffix) = (&value[..pos], &value[pos + 1..]);
+			if prefix == "_" || suffix.starts_with("//") {
+				return Some(value.to_string());
+			}
+			if let Some(TermDefinition {
+				iri: Some(iri),
+				prefix: true,
+				..
+			}) = self.terms.get(prefix)
+			{
+				return Some(format!("{}{}", iri, suffix));
+			}
+			if is_absolute_iri(value) {
+				return Some(value.to_string());
+			}
+		}
+		match &self.vocab {
+			Some(vocab_iri) if vocab => Some(format!("{}{}", vocab_iri, value)),
+			// Without a base IRI relative IRIs are kept as is, and later dropped from RDF
+			_ => Some(value.to_string()),
+		}
+	}
+}
+
+// State of the term definitions being created from a local context.
+struct TermCreation<'c> {
+	local: &'c JsonValue,
+	defined: BTreeMap<String, bool>,
+}
+
+impl TermCreation<'_> {
+	fn define_dependency(
+		&mut self,
+		active: &mut ActiveContext,
+		value: &str,
+	) -> Result<(), &'static str> {
+		if self.local.has_key(value) && self.defined.get(value) != Some(&true) {
+			self.create_term_definition(active, value)?;
+		}
+		Ok(())
+	}
+
+	fn expand_iri(
+		&mut self,
+		active: &mut ActiveContext,
+		value: &str,
+		vocab: bool,
+	) -> Result<Option<String>, &'static str> {
+		self.define_dependency(active, value)?;
+		if let Some(pos) = value.find(':').filter(|pos| *pos > 0) {
+			self.define_dependency(active, &value[..pos])?;
+		}
+		Ok(active.expand_iri(value, vocab))
+	}
+
+	fn create_term_definition(
+		&mut self,
+		active: &mut ActiveContext,
+		term: &str,
+	) -> Result<(), &'static str> {
+		match self.defined.get(term) {
+			Some(true) => return Ok(()),
+			Some(false) => return Err("cyclic JSON-LD IRI mapping"),
+			None => {}
+		}
+		if term.is_empty() {
+			return Err("invalid JSON-LD term definition");
+		}
+		self.defined.insert(term.to_string(), false);
+
+		let value = &self.local[term];
+		// Only the container and protection of @type may be defined, which do not affect RDF
+		if term == "@type" || (!is_keyword(term) && looks_like_keyword(term)) {
+			self.defined.insert(term.to_string(), true);
+			return Ok(());
+		}
+		if is_keyword(term) {
+			return Err("JSON-LD keywords cannot be redefined");
+		}
+		active.terms.remove(term);
+
+		let simple = value.is_string();
+		let mut definition = TermDefinition::default();
+		let id = match value {
+			JsonValue::Null => Some(&JsonValue::Null),
+			JsonValue::Short(_) | JsonValue::String(_) => Some(value),
+			JsonValue::Object(_) if value.has_key("@id") => Some(&value["@id"]),
+			JsonValue::Object(_) => None,
+			_ => return Err("invalid JSON-LD term definition"),
+		};
+
+		if value.has_key("@type") {
+			let type_mapping = value["@type"]
+				.as_str()
+				.ok_or("invalid JSON-LD type mapping")?;
+			let type_mapping = self
+				.expand_iri(active, type_mapping, true)?
+				.filter(|t| {
+					matches!(t.as_str(), "@id" | "@json" | "@none" | "@vocab") || is_absolute_iri(t)
+				})
+				.ok_or("invalid JSON-LD type mapping")?;
+			definition.type_mapping = Some(type_mapping);
+		}
+
+		if value.has_key("@reverse") {
+			let reverse = value["@reverse"]
+				.as_str()
+				.ok_or("invalid JSON-LD reverse property")?;
+			definition.iri = self
+				.expand_iri(active, reverse, true)?
+				.filter(|iri| iri.contains(':'))
+				.map(Some)
+				.ok_or("invalid JSON-LD reverse property")?;
+			definition.reverse = true;
+		} else if let Some(id) = id.filter(|id| id.as_str() != Some(term)) {
+			if !id.is_null() {
+				let id = id.as_str().ok_or("invalid JSON-LD IRI mapping")?;
+				if !is_keyword(id) && looks_like_keyword(id) {
+					self.defined.insert(term.to_string(), true);
+					return Ok(());
+				}
+				let iri = self
+					.expand_iri(active, id, true)?
+					.filter(|iri| (is_keyword(iri) && iri != "@context") || iri.contains(':'))
+					.ok_or("invalid JSON-LD IRI mapping")?;
+				definition.prefix = simple
+					&& !term.contains(':')
+					&& !term.contains('/')
+					&& (is_blank_node(&iri) || iri.ends_with(&GEN_DELIMS[..]));
+				definition.iri = Some(iri);
+			}
+		} else if let Some(pos) = term.find(':').filter(|pos| *pos > 0) {
+			let (prefix, suffix) = (&term[..pos], &term[pos + 1..]);
+			self.define_dependency(active, prefix)?;
+			definition.iri = match active.terms.get(prefix) {
+				Some(TermDefinition { iri: Some(iri), .. }) => Some(format!("{}{}", iri, suffix)),
+				_ => Some(term.to_string()),
+			};
+		} else if term.contains('/') {
+			definition.iri = active
+				.expand_iri(term, true)
+				.filter(|iri| is_absolute_iri(iri));
+			if definition.iri.is_none() {
+				return Err("invalid JSON-LD IRI mapping");
+			}
+		} else {
+			let vocab = active.vocab.as_ref().ok_or("invalid JSON-LD IRI mapping")?;
+			definition.iri = Some(format!("{}{}", vocab, term));
+		}
+
+		if value.has_key("@container") {
+			let container = &value["@container"];
+			let containers: Vec<&JsonValue> = match container {
+				JsonValue::Array(values) => values.iter().collect(),
+				value => vec![value],
+			};
+			for container in containers {
+				match container.as_str() {
+					Some(c) if CONTAINERS.contains(&c) => definition.container.push(c.to_string()),
+					_ => return Err("invalid JSON-LD container mapping"),
+				}
+			}
+		}
+		if value.has_key("@context") {
+			definition.context = Some(value["@context"].clone());
+		}
+		if value.has_key("@language") && !value.has_key("@type") {
+			definition.language = match &value["@language"] {
+				JsonValue::Null => Some(None),
+				language => Some(Some(
+					language
+						.as_str()
+						.ok_or("invalid JSON-LD language mapping")?
+						.to_ascii_lowercase(),
+				)),
+			};
+		}
+		if let Some(prefix) = value["@prefix"].as_bool() {
+			definition.prefix = prefix;
+		}
+		active.terms.insert(term.to_string(), definition);
+		self.defined.insert(term.to_string(), true);
+		Ok(())
+	}
+}
+
+fn process_definition(active: &mut ActiveContext, context: &JsonValue) -> Result<(), &'static str> {
+	if context.has_key("@import") {
+		return Err("JSON-LD @import is not supported");
+	}
+	if !context["@base"].is_null() {
+		return Err("JSON-LD @base is not supported");
+	}
+	// Term protection only guards against redefinitions and is not enforced
+	let mut creation = TermCreation {
+		local: context,
+		defined: BTreeMap::new(),
+	};
+	if context.has_key("@vocab") {
+		active.vocab = match &context["@vocab"] {
+			JsonValue::Null => None,
+			vocab => {
+				let vocab = vocab.as_str().ok_or("invalid JSON-LD vocabulary mapping")?;
+				Some(
+					creation
+						.expand_iri(active, vocab, true)?
+						.ok_or("invalid JSON-LD vocabulary mapping")?,
+				)
+			}
+		};
+	}
+	if context.has_key("@language") {
+		active.language = match &context["@language"] {
+			JsonValue::Null => None,
+			language => Some(
+				language
+					.as_str()
+					.ok_or("invalid JSON-LD default language")?
+					.to_ascii_lowercase(),
+			),
+		};
+	}
+	for (term, _) in context.entries() {
+		match term {
+			"@base" | "@direction" | "@import" | "@language" | "@propagate" | "@protected"
+			| "@version" | "@vocab" => {}
+			term => creation.create_term_definition(active, term)?,
+		}
+	}
+	Ok(())
+}
+
+fn process(
+	active: &ActiveContext,
+	local: &JsonValue,
+	documents: &Documents,
+	remote_contexts: &mut Vec<String>,
+	propagate: bool,
+) -> Result<ActiveContext, &'static str> {
+	let mut result = active.clone();
+	let propagate = local["@propagate"].as_bool().unwrap_or(propagate);
+	if !propagate && result.previous.is_none() {
+		result.previous = Some(Box::new(active.clone()));
+	}
+	let contexts: Vec<&JsonValue> = match local {
+		JsonValue::Array(contexts) => contexts.iter().collect(),
+		context => vec![context],
+	};
+	for context in contexts {
+		match context {
+			JsonValue::Null => {
+				let previous = result;
+				result = ActiveContext::default();
+				if !propagate {
+					result.previous = Some(Box::new(previous));
+				}
+			}
+			JsonValue::Short(_) | JsonValue::String(_) => {
+				let url = context.as_str().unwrap_or_default();
+				if remote_contexts.iter().any(|remote| remote == url) {
+					return Err("recursive JSON-LD context inclusion");
+				}
+				if remote_contexts.len() >= MAX_REMOTE_CONTEXTS {
+					return Err("JSON-LD context overflow");
+				}
+				let remote = &documents.get(url).ok_or("unknown JSON-LD context")?["@context"];
+				if remote.is_null() {
+					return Err("invalid remote JSON-LD context");
+				}
+				remote_contexts.push(url.to_string());
+				result = process(&result, remote, documents, remote_contexts, true)?;
+				remote_contexts.pop();
+			}
+			JsonValue::Object(_) => process_definition(&mut result, context)?,
+			_ => return Err("invalid JSON-LD local context"),
+		}
+	}
+	Ok(result)
+}
+
+/// Updates the active context with a local context, which may reference remote contexts.
+pub fn process_context(
+	active: &ActiveContext,
+	local: &JsonValue,
+	documents: &Documents,
+	propagate: bool,
+) -> Result<ActiveContext, &'static str> {
+	process(active, local, documents, &mut vec![], propagate)
+}
@@ -0,0 +1,417 @@
+use crate::lib::std::vec::Vec;
+
+use json::JsonValue;
+
+use super::context::{is_keyword, process_context, ActiveContext, Documents, TermDefinition};
+
+// Implement JSON-LD expansion following the specification at:
+// https://www.w3.org/TR/json-ld11-api/#expansion-algorithm
+
+fn to_array(value: JsonValue) -> Vec<JsonValue> {
+	match value {
+		JsonValue::Array(values) => values,
+		JsonValue::Null => vec![],
+		value => vec![value],
+	}
+}
+
+fn values(value: &JsonValue) -> Vec<&JsonValue> {
+	match value {
+		JsonValue::Array(values) => values.iter().collect(),
+		value => vec![value],
+	}
+}
+
+fn wrap(keyword: &str, values: Vec<JsonValue>) -> JsonValue {
+	let mut object = JsonValue::new_object();
+	object[keyword] = JsonValue::Array(values);
+	object
+}
+
+fn is_list_object(value: &JsonValue) -> bool {
+	value.is_object() && value.has_key("@list")
+}
+
+fn is_graph_object(value: &JsonValue) -> bool {
+	value.is_object()
+		&& value.has_key("@graph")
+		&& value
+			.entries()
+			.all(|(key, _)| matches!(key, "@graph" | "@id" | "@index"))
+}
+
+fn add_value(object: &mut JsonValue, property: &str, value: JsonValue) {
+	if !object[property].is_array() {
+		object[property] = JsonValue::new_array();
+	}
+	if let JsonValue::Array(values) = &mut object[property] {
+		values.extend(to_array(value));
+	}
+}
+
+struct Expansion<'d> {
+	documents: &'d Documents,
+}
+
+impl Expansion<'_> {
+	fn expand(
+		&self,
+		active: &ActiveContext,
+		active_property: Option<&str>,
+		element: &JsonValue,
+		from_map: bool,
+	) -> Result<JsonValue, &'static str> {
+		let definition = active_property.and_then(|property| active.term(property));
+		match element {
+			JsonValue::Null => Ok(JsonValue::Null),
+			JsonValue::Array(items) => {
+				let is_list = definition.is_some_and(|d| d.has_container("@list"));
+				let mut result = vec![];
+				for item in items {
+					match self.expand(active, active_property, item, from_map)? {
+						JsonValue::Array(values) if is_list => result.push(wrap("@list", values)),
+						JsonValue::Array(values) => result.extend(values),
+						JsonValue::Null => {}
+						value => result.push(value),
+					}
+				}
+				Ok(JsonValue::Array(result))
+			}
+			JsonValue::Object(_) => {
+				let scoped = definition.and_then(|d| d.context.as_ref());
+				self.expand_object(active, active_property, element, from_map, scoped)
+			}
+			scalar => match active_property {
+				// Free-floating scalars are dropped
+				None | Some("@graph") => Ok(JsonValue::Null),
+				Some(property) => match definition.and_then(|d| d.context.as_ref()) {
+					Some(scoped) => {
+						let active = process_context(active, scoped, self.documents, true)?;
+						Ok(expand_value(&active, property, scalar))
+					}
+					None => Ok(expand_value(active, property, scalar)),
+				},
+			},
+		}
+	}
+
+	fn expand_object(
+		&self,
+		active: &ActiveContext,
+		active_property: Option<&str>,
+		element: &JsonValue,
+		from_map: bool,
+		scoped: Option<&JsonValue>,
+	) -> Result<JsonValue, &'static str> {
+		let mut active = active.clone();
+
+		// Non-propagated contexts do not apply to embedded node objects
+		if let Some(previous) = active.previous.take() {
+			let expands_to = |keyword: &str| {
+				element
+					.entries()
+					.any(|(key, _)| active.expand_iri(key, true).as_deref() == Some(keyword))
+			};
+			let keep =
+				from_map || expands_to("@value") || (element.len() == 1 && expands_to("@id"));
+			if keep {
+				active.previous = Some(previous);
+			} else {
+				active = *previous;
+			}
+		}
+		if let Some(scoped) = scoped {
+			active = process_context(&active, scoped, self.documents, true)?;
+		}
+		if element.has_key("@context") {
+			active = process_context(&active, &element["@context"], self.documents, true)?;
+		}
+
+		let mut keys: Vec<&str> = element.entries().map(|(key, _)| key).collect();
+		keys.sort_unstable();
+
+		// Apply the contexts scoped to the types of the node, in lexicographical order
+		let type_scoped = active.clone();
+		let mut input_type = None;
+		for key in &keys {
+			if active.expand_iri(key, true).as_deref() != Some("@type") {
+				continue;
+			}
+			let mut types: Vec<&str> = values(&element[*key])
+				.into_iter()
+				.filter_map(JsonValue::as_str)
+				.collect();
+			types.sort_unstable();
+			for term in &types {
+				if let Some(scoped) = type_scoped.term(term).and_then(|d| d.context.as_ref()) {
+					active = process_context(&active, scoped, self.documents, false)?;
+				}
+			}
+			input_type = types.last().and_then(|t| type_scoped.expand_iri(t, true));
+		}
+
+		let mut result = JsonValue::new_object();
+		for key in keys {
+			if key == "@context" {
+				continue;
+			}
+			let value = &element[key];
+			let property = match active.expand_iri(key, true) {
+				Some(property) if property.contains(':') || is_keyword(&property) => property,
+				_ => continue,
+			};
+
+			if is_keyword(&property) {
+				if result.has_key(&property) {
+					return Err("colliding JSON-LD keywords");
+				}
+				let expanded = match property.as_str() {
+					"@id" => {
+						let id = value.as_str().ok_or("invalid JSON-LD @id value")?;
+						active.expand_iri(id, false).into()
+					}
+					"@type" => {
+						let mut types = vec![];
+						for t in values(value) {
+							let t = t.as_str().ok_or("invalid JSON-LD type value")?;
+							types.extend(type_scoped.expand_iri(t, true).map(JsonValue::from));
+						}
+						match value {
+							JsonValue::Array(_) => JsonValue::Array(types),
+							_ => types.pop().unwrap_or(JsonValue::Null),
+						}
+					}
+					"@graph" => JsonValue::Array(to_array(self.expand(
+						&active,
+						Some("@graph"),
+						value,
+						false,
+					)?)),
+					"@value" => {
+						if input_type.as_deref() != Some("@json")
+							&& (value.is_object() || value.is_array())
+						{
+							return Err("invalid JSON-LD value object value");
+						}
+						result["@value"] = value.clone();
+						continue;
+					}
+					"@language" => value
+						.as_str()
+						.ok_or("invalid JSON-LD language-tagged string")?
+						.to_ascii_lowercase()
+						.into(),
+					"@direction" | "@index" => value
+						.as_str()
+						.ok_or("invalid JSON-LD keyword value")?
+						.into(),
+					"@list" => match active_property {
+						None | Some("@graph") => continue,
+						_ => JsonValue::Array(to_array(self.expand(
+							&active,
+							active_property,
+							value,
+							false,
+						)?)),
+					},
+					"@set" => self.expand(&active, active_property, value, false)?,
+					"@reverse" | "@nest" | "@included" => {
+						return Err("unsupported JSON-LD keyword");
+					}
+					// Framing keywords are ignored
+					_ => continue,
+				};
+				if !expanded.is_null() {
+					result[property.as_str()] = expanded;
+				}
+				continue;
+			}
+
+			let definition = active.term(key);
+			let container = |c: &str| definition.is_some_and(|d| d.has_container(c));
+			let mut expanded =
+				if definition.and_then(|d| d.type_mapping.as_deref()) == Some("@json") {
+					let mut json = JsonValue::new_object();
+					json["@value"] = value.clone();
+					json["@type"] = "@json".into();
+					json
+				} else if container("@language") && value.is_object() {
+					expand_language_map(&active, value)?
+				} else if (container("@index") || container("@id")) && value.is_object() {
+					self.expand_index_map(&active, key, definition, value)?
+				} else if container("@type") && value.is_object() {
+					return Err("unsupported JSON-LD type map");
+				} else {
+					self.expand(&active, Some(key), value, false)?
+				};
+			if expanded.is_null() {
+				continue;
+			}
+			if container("@list") && !is_list_object(&expanded) {
+				expanded = wrap("@list", to_array(expanded));
+			}
+			if container("@graph") && !container("@id") && !container("@index") {
+				expanded = JsonValue::Array(
+					to_array(expanded)
+						.into_iter()
+						.map(|value| wrap("@graph", to_array(value)))
+						.collect(),
+				);
+			}
+			if definition.is_some_and(|d| d.reverse) {
+				return Err("unsupported JSON-LD reverse property");
+			}
+			add_value(&mut result, &property, expanded);
+		}
+
+		post_process(result, active_property)
+	}
+
+	fn expand_index_map(
+		&self,
+		active: &ActiveContext,
+		key: &str,
+		definition: Option<&TermDefinition>,
+		value: &JsonValue,
+	) -> Result<JsonValue, &'static str> {
+		let container = |c: &str| definition.is_some_and(|d| d.has_container(c));
+		let mut entries: Vec<(&str, &JsonValue)> = value.entries().collect();
+		entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+		let mut result = vec![];
+		for (index, index_value) in entries {
+			let expanded_index = active.expand_iri(index, true);
+			for mut item in to_array(self.expand(active, Some(key), index_value, true)?) {
+				if container("@graph") && !is_graph_object(&item) {
+					item = wrap("@graph", vec![item]);
+				}
+				if expanded_index.as_deref() == Some("@none") {
+					// Entries under @none carry no index
+				} else if container("@index") && !item.has_key("@index") {
+					item["@index"] = index.into();
+				} else if container("@id") && !item.has_key("@id") {
+					item["@id"] = active.expand_iri(index, false).into();
+				}
+				result.push(item);
+			}
+		}
+		Ok(JsonValue::Array(result))
+	}
+}
+
+fn expand_language_map(
+	active: &ActiveContext,
+	value: &JsonValue,
+) -> Result<JsonValue, &'static str> {
+	let mut entries: Vec<(&str, &JsonValue)> = value.entries().collect();
+	entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+	let mut result = vec![];
+	for (language, language_value) in entries {
+		for item in values(language_value) {
+			if item.is_null() {
+				continue;
+			}
+			let mut expanded = JsonValue::new_object();
+			expanded["@value"] = item
+				.as_str()
+				.ok_or("invalid JSON-LD language map value")?
+				.into();
+			if active.expand_iri(language, true).as_deref() != Some("@none") {
+				expanded["@language"] = language.to_ascii_lowercase().into();
+			}
+			result.push(expanded);
+		}
+	}
+	Ok(JsonValue::Array(result))
+}
+
+fn expand_value(active: &ActiveContext, property: &str, value: &JsonValue) -> JsonValue {
+	let definition = active.term(property);
+	let type_mapping = definition.and_then(|d| d.type_mapping.as_deref());
+	let mut result = JsonValue::new_object();
+	if let Some(id) = value.as_str() {
+		match type_mapping {
+			Some("@id") => {
+				result["@id"] = active.expand_iri(id, false).into();
+				return result;
+			}
+			Some("@vocab") => {
+				result["@id"] = active.expand_iri(id, true).into();
+				return result;
+			}
+			_ => {}
+		}
+	}
+	result["@value"] = value.clone();
+	match type_mapping {
+		Some(datatype) if !matches!(datatype, "@id" | "@vocab" | "@none") => {
+			result["@type"] = datatype.into();
+		}
+		_ if value.is_string() => {
+			let language = definition
+				.and_then(|d| d.language.clone())
+				.unwrap_or_else(|| active.language.clone());
+			if let Some(language) = language {
+				result["@language"] = language.into();
+			}
+		}
+		_ => {}
+	}
+	result
+}
+
+fn post_process(
+	mut result: JsonValue,
+	active_property: Option<&str>,
+) -> Result<JsonValue, &'static str> {
+	if result.has_key("@value") {
+		if result["@value"].is_null() {
+			return Ok(JsonValue::Null);
+		}
+		let valid = result.entries().all(|(key, _)| {
+			matches!(
+				key,
+				"@value" | "@language" | "@type" | "@index" | "@direction"
+			)
+		}) && !(result.has_key("@language") && result.has_key("@type"))
+			&& (!result.has_key("@language") || result["@value"].is_string());
+		if !valid {
+			return Err("invalid JSON-LD value object");
+		}
+	} else if result.has_key("@type") && !result["@type"].is_array() {
+		let types = result["@type"].take();
+		result["@type"] = JsonValue::Array(vec![types]);
+	} else if result.has_key("@set") || result.has_key("@list") {
+		let valid = result.len() == 1 || (result.len() == 2 && result.has_key("@index"));
+		if !valid {
+			return Err("invalid JSON-LD set or list object");
+		}
+		if result.has_key("@set") {
+			result = result["@set"].take();
+		}
+	}
+	if result.is_object() && result.len() == 1 && result.has_key("@language") {
+		return Ok(JsonValue::Null);
+	}
+
+	// Top-level values, lists and node references are dropped
+	let top_level = matches!(active_property, None | Some("@graph"));
+	let dropped = result.is_object()
+		&& (result.is_empty()
+			|| result.has_key("@value")
+			|| result.has_key("@list")
+			|| (result.len() == 1 && result.has_key("@id")));
+	if top_level && dropped {
+		return Ok(JsonValue::Null);
+	}
+	Ok(result)
+}
+
+/// Expands a JSON-LD document, removing its contexts.
+pub fn expand(document: &JsonValue, documents: &Documents) -> Result<Vec<JsonValue>, &'static str> {
+	let expansion = Expansion { documents };
+	let mut expanded = expansion.expand(&ActiveContext::default(), None, document, false)?;
+	if expanded.is_object() && expanded.len() == 1 && expanded.has_key("@graph") {
+		expanded = expanded["@graph"].take();
+	}
+	Ok(to_array(expanded))
+}
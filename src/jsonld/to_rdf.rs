@@ -0,0 +1,195 @@
+use crate::lib::std::collections::BTreeMap;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+
+use crate::jcs;
+use crate::rdf::{
+	Quad, Term, RDF_FIRST, RDF_JSON, RDF_LANGSTRING, RDF_NIL, RDF_REST, RDF_TYPE, XSD_BOOLEAN,
+	XSD_DOUBLE, XSD_INTEGER, XSD_STRING,
+};
+
+use json::JsonValue;
+
+use super::context::{is_absolute_iri, is_blank_node};
+
+// Implement the conversion of expanded JSON-LD to RDF following the specification at:
+// https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm
+
+// Canonical lexical form of an xsd:double, as produced by JSON-LD processors.
+fn canonical_double(value: f64) -> String {
+	let formatted = format!("{:.15e}", value);
+	let (mantissa, exponent) = formatted.split_at(formatted.find('e').unwrap_or_default());
+	let mantissa = mantissa.trim_end_matches('0');
+	let mantissa = if mantissa.ends_with('.') {
+		format!("{}0", mantissa)
+	} else {
+		mantissa.to_string()
+	};
+	format!("{}E{}", mantissa, &exponent[1..])
+}
+
+struct Conversion {
+	labels: BTreeMap<String, String>,
+	counter: usize,
+	quads: Vec<Quad>,
+}
+
+impl Conversion {
+	// Labels are only compared during canonicalization, so they just have to be unique.
+	fn blank_node(&mut self, existing: Option<&str>) -> Term {
+		if let Some(label) = existing.and_then(|existing| self.labels.get(existing)) {
+			return Term::Blank(label.clone());
+		}
+		let label = format!("b{}", self.counter);
+		self.counter += 1;
+		if let Some(existing) = existing {
+			self.labels.insert(existing.to_string(), label.clone());
+		}
+		Term::Blank(label)
+	}
+
+	fn iri(&mut self, iri: &str) -> Option<Term> {
+		if is_blank_node(iri) {
+			Some(self.blank_node(Some(iri)))
+		} else if is_absolute_iri(iri) {
+			Some(Term::Iri(iri.to_string()))
+		} else {
+			None
+		}
+	}
+
+	fn push(&mut self, subject: &Term, predicate: &str, object: Term, graph: Option<&Term>) {
+		self.quads.push(Quad {
+			subject: subject.clone(),
+			predicate: predicate.to_string(),
+			object,
+			graph: graph.cloned(),
+		});
+	}
+
+	fn node(&mut self, node: &JsonValue, graph: Option<&Term>) -> Option<Term> {
+		let subject = match node["@id"].as_str() {
+			Some(id) => self.iri(id),
+			None => Some(self.blank_node(None)),
+		};
+
+		for (property, values) in node.entries() {
+			match property {
+				"@type" => {
+					for t in values.members().filter_map(JsonValue::as_str) {
+						if let (Some(subject), Some(object)) = (&subject, self.iri(t)) {
+							self.push(subject, RDF_TYPE, object, graph);
+						}
+					}
+				}
+				"@graph" => {
+					// Named graphs with a relative name are dropped
+					if let Some(name) = &subject {
+						for item in values.members() {
+							self.node(item, Some(name));
+						}
+					}
+				}
+				property if property.starts_with('@') => {}
+				// Blank node predicates are not allowed outside of generalized RDF
+				property if is_blank_node(property) || !is_absolute_iri(property) => {}
+				property => {
+					for value in values.members() {
+						let object = self.object(value, graph);
+						if let (Some(subject), Some(object)) = (&subject, object) {
+							self.push(subject, property, object, graph);
+						}
+					}
+				}
+			}
+		}
+		subject
+	}
+
+	fn object(&mut self, value: &JsonValue, graph: Option<&Term>) -> Option<Term> {
+		if value.has_key("@value") {
+			literal(value)
+		} else if value.has_key("@list") {
+			self.list(&value["@list"], graph)
+		} else {
+			self.node(value, graph)
+		}
+	}
+
+	fn list(&mut self, items: &JsonValue, graph: Option<&Term>) -> Option<Term> {
+		if items.is_empty() {
+			return Some(Term::Iri(RDF_NIL.to_string()));
+		}
+		let nodes: Vec<Term> = items.members().map(|_| self.blank_node(None)).collect();
+		for (i, item) in items.members().enumerate() {
+			if let Some(object) = self.object(item, graph) {
+				self.push(&nodes[i], RDF_FIRST, object, graph);
+			}
+			let rest = match nodes.get(i + 1) {
+				Some(next) => next.clone(),
+				None => Term::Iri(RDF_NIL.to_string()),
+			};
+			self.push(&nodes[i], RDF_REST, rest, graph);
+		}
+		nodes.into_iter().next()
+	}
+}
+
+fn literal(value: &JsonValue) -> Option<Term> {
+	let datatype = value["@type"].as_str();
+	if datatype.is_some_and(|datatype| datatype != "@json" && !is_absolute_iri(datatype)) {
+		return None;
+	}
+	let language = value["@language"].as_str().map(ToString::to_string);
+	let literal = &value["@value"];
+	let (lexical, default_datatype) = if datatype == Some("@json") {
+		(jcs::canonicalize(literal).ok()?, RDF_JSON)
+	} else {
+		match literal {
+			JsonValue::Boolean(b) => (b.to_string(), XSD_BOOLEAN),
+			JsonValue::Number(_) => {
+				let number = literal.as_f64().unwrap_or_default();
+				if number % 1.0 != 0.0
+					|| !(-1e21..1e21).contains(&number)
+					|| datatype == Some(XSD_DOUBLE)
+				{
+					(canonical_double(number), XSD_DOUBLE)
+				} else if number == 0.0 {
+					("0".to_string(), XSD_INTEGER)
+				} else {
+					(format!("{}", number), XSD_INTEGER)
+				}
+			}
+			literal => (
+				literal.as_str()?.to_string(),
+				if language.is_some() {
+					RDF_LANGSTRING
+				} else {
+					XSD_STRING
+				},
+			),
+		}
+	};
+	let datatype = match datatype {
+		Some(datatype) if datatype != "@json" && language.is_none() => datatype,
+		_ => default_datatype,
+	};
+	Some(Term::Literal {
+		value: lexical,
+		datatype: datatype.to_string(),
+		language,
+	})
+}
+
+/// Converts expanded JSON-LD nodes into an RDF dataset.
+pub fn to_rdf(expanded: &[JsonValue]) -> Vec<Quad> {
+	let mut conversion = Conversion {
+		labels: BTreeMap::new(),
+		counter: 0,
+		quads: vec![],
+	};
+	for node in expanded {
+		conversion.node(node, None);
+	}
+	conversion.quads
+}
@@ -0,0 +1,210 @@
+use crate::lib::std::str::{self, FromStr};
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+
+use crate::did::Did;
+use crate::did_doc::{DidDocument, VerificationRelationship};
+use crate::did_parser::DID_SCHEME;
+use crate::encoding::{base64url_decode, base64url_encode};
+use crate::method::DidResolver;
+use crate::verify::{KeySelector, SignatureAlgorithm};
+
+use json::JsonValue;
+
+// Verify JSON Web Signatures in compact serialization, see:
+// https://www.rfc-editor.org/rfc/rfc7515
+// https://www.rfc-editor.org/rfc/rfc7797 (unencoded payload option)
+
+const SEGMENT_SEP: char = '.';
+const ALG_PROP: &str = "alg";
+const KID_PROP: &str = "kid";
+const B64_PROP: &str = "b64";
+const CRIT_PROP: &str = "crit";
+
+/// A compact serialized JWS whose `kid` header references a DID verification method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jws {
+	header: JsonValue,
+	payload: Vec<u8>,
+	signing_input: Vec<u8>,
+	signature: Vec<u8>,
+}
+
+fn parse_header(encoded: &str) -> Result<JsonValue, &'static str> {
+	let decoded = base64url_decode(encoded).map_err(|_| "invalid JWS header")?;
+	let header = str::from_utf8(&decoded)
+		.ok()
+		.and_then(|s| json::parse(s).ok())
+		.filter(JsonValue::is_object)
+		.ok_or("invalid JWS header")?;
+	if !header[ALG_PROP].is_string() {
+		return Err("missing JWS alg header");
+	}
+	Ok(header)
+}
+
+// The critical header parameters must be a non-empty list of extensions that are
+// understood and present in the header; only the unencoded payload option is.
+fn validate_crit(header: &JsonValue) -> Result<(), &'static str> {
+	if !header.has_key(CRIT_PROP) {
+		return Ok(());
+	}
+	let crit = &header[CRIT_PROP];
+	if !crit.is_array() || crit.is_empty() {
+		return Err("invalid JWS crit header");
+	}
+	for name in crit.members() {
+		match name.as_str() {
+			Some(B64_PROP) if header.has_key(B64_PROP) => (),
+			Some(B64_PROP) => return Err("missing JWS critical header"),
+			Some(_) => return Err("unsupported JWS critical header"),
+			None => return Err("invalid JWS crit header"),
+		}
+	}
+	Ok(())
+}
+
+// The unencoded payload option must be listed as a critical header parameter.
+fn is_payload_encoded(header: &JsonValue) -> Result<bool, &'static str> {
+	validate_crit(header)?;
+	match header[B64_PROP].as_bool() {
+		None if header.has_key(B64_PROP) => Err("invalid JWS b64 header"),
+		None | Some(true) => Ok(true),
+		Some(false) if header[CRIT_PROP].contains(B64_PROP) => Ok(false),
+		Some(false) => Err("JWS b64 header must be critical"),
+	}
+}
+
+impl Jws {
+	pub fn parse(compact: &str) -> Result<Self, &'static str> {
+		Self::parse_with_payload(compact, None)
+	}
+
+	/// Parses a JWS with a detached payload, i.e. `<header>..<signature>`.
+	pub fn parse_detached(compact: &str, payload: &[u8]) -> Result<Self, &'static str> {
+		Self::parse_with_payload(compact, Some(payload))
+	}
+
+	fn parse_with_payload(compact: &str, detached: Option<&[u8]>) -> Result<Self, &'static str> {
+		// An unencoded payload may itself contain periods
+		let (encoded_header, encoded_payload, encoded_signature) = compact
+			.split_once(SEGMENT_SEP)
+			.and_then(|(header, rest)| {
+				rest.rsplit_once(SEGMENT_SEP)
+					.map(|(payload, signature)| (header, payload, signature))
+			})
+			.ok_or("invalid JWS")?;
+
+		let header = parse_header(encoded_header)?;
+		let encoded = is_payload_encoded(&header)?;
+		let payload = match (detached, encoded) {
+			(Some(_), _) if !encoded_payload.is_empty() => {
+				return Err("detached JWS must have an empty payload")
+			}
+			(Some(payload), _) => payload.to_vec(),
+			(None, true) => base64url_decode(encoded_payload).map_err(|_| "invalid JWS payload")?,
+			(None, false) => encoded_payload.as_bytes().to_vec(),
+		};
+
+		let mut signing_input = Vec::from(encoded_header.as_bytes());
+		signing_input.push(SEGMENT_SEP as u8);
+		// Attached payloads are signed as they appear in the JWS, detached ones
+		// are encoded the way the signer did
+		match detached {
+			None => signing_input.extend_from_slice(encoded_payload.as_bytes()),
+			Some(_) if encoded => {
+				signing_input.extend_from_slice(base64url_encode(&payload).as_bytes())
+			}
+			Some(_) => signing_input.extend_from_slice(&payload),
+		}
+
+		Ok(Jws {
+			header,
+			payload,
+			signing_input,
+			signature: base64url_decode(encoded_signature).map_err(|_| "invalid JWS signature")?,
+		})
+	}
+
+	pub fn header(&self) -> &JsonValue {
+		&self.header
+	}
+
+	pub fn alg(&self) -> &str {
+		self.header[ALG_PROP].as_str().unwrap_or_default()
+	}
+
+	pub fn kid(&self) -> Option<&str> {
+		self.header[KID_PROP].as_str()
+	}
+
+	pub fn payload(&self) -> &[u8] {
+		&self.payload
+	}
+
+	/// Returns the DID referenced by the `kid` header, without its fragment.
+	pub fn signer(&self) -> Result<String, &'static str> {
+		let kid = self.kid().ok_or("missing JWS kid header")?;
+		let did = Did::parse(kid).map_err(|_| "JWS kid must be a DID URL")?;
+		if did.fragment().is_none() {
+			return Err("JWS kid must reference a verification method");
+		}
+		Ok(format!(
+			"{}:{}:{}",
+			DID_SCHEME,
+			did.method_name(),
+			did.method_specific_id()
+		))
+	}
+
+	fn verify_selected(
+		&self,
+		did_doc: &DidDocument,
+		relationship: Option<VerificationRelationship>,
+	) -> Result<(), &'static str> {
+		if self.signer()? != did_doc.id() {
+			return Err("JWS kid does not match DID document");
+		}
+		let kid = self.kid().unwrap_or_default();
+		let selector = match relationship {
+			Some(rel) => KeySelector::IdFor(kid, rel),
+			None => KeySelector::Id(kid),
+		};
+		self.verify_selector(did_doc, selector)
+	}
+
+	// Verifies the signature with the selected keys, regardless of the `kid` header.
+	pub(crate) fn verify_selector(
+		&self,
+		did_doc: &DidDocument,
+		selector: KeySelector,
+	) -> Result<(), &'static str> {
+		let algorithm =
+			SignatureAlgorithm::from_str(self.alg()).map_err(|_| "unsupported JWS algorithm")?;
+		did_doc.verify_with(selector, algorithm, &self.signing_input, &self.signature)
+	}
+
+	/// Verifies the signature with the verification method referenced by the `kid` header.
+	pub fn verify(&self, did_doc: &DidDocument) -> Result<(), &'static str> {
+		self.verify_selected(did_doc, None)
+	}
+
+	/// Verifies the signature, requiring the `kid` verification method to be listed
+	/// under the given verification relationship.
+	pub fn verify_for(
+		&self,
+		did_doc: &DidDocument,
+		relationship: VerificationRelationship,
+	) -> Result<(), &'static str> {
+		self.verify_selected(did_doc, Some(relationship))
+	}
+
+	/// Verifies the signature after resolving the DID document of the `kid` DID.
+	pub fn verify_resolved<R: DidResolver>(&self, resolver: &R) -> Result<(), &'static str> {
+		let signer = self.signer()?;
+		let json =
+			resolver.resolve(&Did::parse(&signer).map_err(|_| "JWS kid must be a DID URL")?)?;
+		let did_doc = DidDocument::parse(&json).map_err(|_| "invalid DID document")?;
+		self.verify(&did_doc)
+	}
+}
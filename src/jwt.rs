@@ -0,0 +1,161 @@
+use crate::lib::std::str;
+
+use crate::did::Did;
+use crate::did_doc::{DidDocument, VerificationRelationship};
+use crate::jws::Jws;
+use crate::method::DidResolver;
+
+use json::JsonValue;
+
+// Verify JSON Web Tokens issued by a DID, see:
+// https://www.rfc-editor.org/rfc/rfc7519
+
+const ISS_CLAIM: &str = "iss";
+const SUB_CLAIM: &str = "sub";
+const AUD_CLAIM: &str = "aud";
+const EXP_CLAIM: &str = "exp";
+const NBF_CLAIM: &str = "nbf";
+
+/// Expectations a JWT is validated against. The current time is supplied by the
+/// caller as seconds since the Unix epoch so that validation also works in no_std.
+/// The signing key must be an assertion method unless another relationship is given.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JwtValidation<'v> {
+	now: i64,
+	leeway: i64,
+	audience: Option<&'v str>,
+	relationship: VerificationRelationship,
+}
+
+impl<'v> JwtValidation<'v> {
+	pub fn new(now: i64) -> Self {
+		JwtValidation {
+			now,
+			leeway: 0,
+			audience: None,
+			relationship: VerificationRelationship::AssertionMethod,
+		}
+	}
+
+	pub fn with_leeway(mut self, leeway: i64) -> Self {
+		self.leeway = leeway;
+		self
+	}
+
+	pub fn with_audience(mut self, audience: &'v str) -> Self {
+		self.audience = Some(audience);
+		self
+	}
+
+	pub fn with_relationship(mut self, relationship: VerificationRelationship) -> Self {
+		self.relationship = relationship;
+		self
+	}
+}
+
+/// A JWT in JWS compact serialization whose issuer is a DID.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jwt {
+	jws: Jws,
+	claims: JsonValue,
+}
+
+fn numeric_date(claims: &JsonValue, claim: &str) -> Result<Option<i64>, &'static str> {
+	match &claims[claim] {
+		JsonValue::Null => Ok(None),
+		JsonValue::Number(_) => Ok(claims[claim].as_f64().map(|date| date as i64)),
+		_ => Err("invalid JWT numeric date claim"),
+	}
+}
+
+impl Jwt {
+	pub fn parse(compact: &str) -> Result<Self, &'static str> {
+		let jws = Jws::parse(compact)?;
+		let claims = str::from_utf8(jws.payload())
+			.ok()
+			.and_then(|s| json::parse(s).ok())
+			.filter(JsonValue::is_object)
+			.ok_or("invalid JWT claims")?;
+		Ok(Jwt { jws, claims })
+	}
+
+	pub fn header(&self) -> &JsonValue {
+		self.jws.header()
+	}
+
+	pub fn claims(&self) -> &JsonValue {
+		&self.claims
+	}
+
+	pub fn issuer(&self) -> Option<&str> {
+		self.claims[ISS_CLAIM].as_str()
+	}
+
+	pub fn subject(&self) -> Option<&str> {
+		self.claims[SUB_CLAIM].as_str()
+	}
+
+	fn check_issuer(&self) -> Result<(), &'static str> {
+		let issuer = self.issuer().ok_or("missing JWT iss claim")?;
+		if Did::parse(issuer).is_err() {
+			return Err("JWT iss must be a DID");
+		}
+		if self.jws.signer()? != issuer {
+			return Err("JWT iss does not match kid DID");
+		}
+		Ok(())
+	}
+
+	fn check_claims(&self, validation: &JwtValidation) -> Result<(), &'static str> {
+		if let Some(exp) = numeric_date(&self.claims, EXP_CLAIM)? {
+			if validation.now >= exp.saturating_add(validation.leeway) {
+				return Err("JWT has expired");
+			}
+		}
+		if let Some(nbf) = numeric_date(&self.claims, NBF_CLAIM)? {
+			if validation.now.saturating_add(validation.leeway) < nbf {
+				return Err("JWT is not yet valid");
+			}
+		}
+		// A JWT intended for specific audiences is rejected by any other recipient
+		let aud = &self.claims[AUD_CLAIM];
+		let valid_aud = match (aud, validation.audience) {
+			(JsonValue::Null, _) => true,
+			(_, None) => false,
+			(JsonValue::Array(audiences), Some(audience)) => {
+				audiences.iter().any(|aud| aud.as_str() == Some(audience))
+			}
+			(aud, Some(audience)) => aud.as_str() == Some(audience),
+		};
+		if valid_aud {
+			Ok(())
+		} else {
+			Err("JWT audience does not match")
+		}
+	}
+
+	/// Verifies the JWT signature with the issuer's DID document and validates its claims.
+	pub fn verify_with_document(
+		&self,
+		did_doc: &DidDocument,
+		validation: &JwtValidation,
+	) -> Result<(), &'static str> {
+		self.check_issuer()?;
+		self.jws.verify_for(did_doc, validation.relationship)?;
+		self.check_claims(validation)
+	}
+
+	/// Resolves the issuer's DID document, then verifies the JWT like `verify_with_document`.
+	pub fn verify<R: DidResolver>(
+		&self,
+		resolver: &R,
+		validation: &JwtValidation,
+	) -> Result<(), &'static str> {
+		self.check_issuer()?;
+		let issuer =
+			Did::parse(self.issuer().unwrap_or_default()).map_err(|_| "JWT iss must be a DID")?;
+		let json = resolver.resolve(&issuer)?;
+		let did_doc = DidDocument::parse(&json).map_err(|_| "invalid DID document")?;
+		self.verify_with_document(&did_doc, validation)
+	}
+}
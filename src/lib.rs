@@ -4,13 +4,7 @@
 extern crate json;
 extern crate nom;
 
-#[cfg(feature = "std")]
-#[macro_use]
-extern crate lazy_static;
-#[cfg(feature = "std")]
-extern crate regex;
-
-#[cfg(all(not(feature = "std")))]
+#[cfg(not(feature = "std"))]
 #[macro_use]
 extern crate alloc;
 
@@ -18,6 +12,7 @@ extern crate alloc;
 /// crates. This avoids elaborate import wrangling having to happen in every
 /// module.
 pub mod lib {
+	#[allow(unused_imports)]
 	mod core {
 		#[cfg(not(feature = "std"))]
 		pub use core::*;
@@ -28,8 +23,8 @@ pub mod lib {
 	#[cfg(not(feature = "std"))]
 	/// internal std exports for no_std compatibility
 	pub mod std {
-		pub use alloc::{boxed, string, vec};
-		pub use core::{borrow, cmp, convert, fmt, iter, mem, ops, option, result, slice, str};
+		pub use alloc::{borrow, boxed, collections, string, vec};
+		pub use core::{cell, cmp, convert, fmt, iter, mem, ops, option, result, slice, str};
 		/// internal reproduction of std prelude
 		pub mod prelude {
 			pub use core::prelude as v1;
@@ -40,7 +35,7 @@ pub mod lib {
 	/// internal std exports for no_std compatibility
 	pub mod std {
 		pub use std::{
-			alloc, borrow, boxed, cmp, collections, convert, fmt, hash, iter, mem, ops, option,
+			alloc, borrow, boxed, cell, cmp, collections, convert, fmt, hash, iter, mem, ops, option,
 			result, slice, str, string, vec,
 		};
 		/// internal reproduction of std prelude
@@ -52,9 +47,21 @@ pub mod lib {
 
 mod did_parser;
 mod diddoc_parser;
+mod encoding;
+mod rdf;
 
+pub mod caip;
+pub mod data_integrity;
+pub mod datetime;
 pub mod did;
 pub mod did_doc;
+pub mod hashlink;
+pub mod jcs;
+pub mod jsonld;
+pub mod jws;
+pub mod jwt;
 pub mod macros;
+pub mod method;
+pub mod verify;
 
 pub use json::parse as json_parse;
@@ -1,7 +1,19 @@
+// The did! macro is implemented by the did_common_macros crate, as procedural
+// macros have to live in a crate of their own. This wrapper passes it the path
+// of this crate, so that expansions resolve even when the crate is renamed.
+
+#[doc(hidden)]
+pub use did_common_macros::expand_did;
+
+/// Parses a DID literal at compile time and expands to a constant `Did<'static>`,
+/// e.g. `did!("did:example:1234#keys-1")`. Invalid DIDs are compile errors.
+///
+/// DIDs can also be built from runtime parts, returning a `Result<Did, DidBuildError>`:
+/// `did!(method = "example", id = id; service = "agent" # "keys-1")`.
+/// Runtime strings are parsed with `Did::parse` instead.
 #[macro_export]
 macro_rules! did {
-	($did: expr) => {
-		//TODO: If cannot parse, should generate error w/ parsing error
-		Did::parse($did).unwrap()
+	($($input:tt)*) => {
+		$crate::macros::expand_did!($crate; $($input)*)
 	};
 }
@@ -0,0 +1,42 @@
+use crate::did::Did;
+use crate::diddoc_parser::{CONTEXT_PROP, GENERIC_DID_CTX, SUBJECT_PROP};
+
+use json::JsonValue;
+
+// Drivers for specific DID methods, see the DID method registry at:
+// https://w3c.github.io/did-spec-registries/#did-methods
+
+pub mod ion;
+pub mod jwk;
+pub mod peer;
+pub mod pkh;
+pub mod web;
+
+/// Resolves a DID into its DID document, e.g. with one of the method drivers,
+/// for APIs that need to look up the verification methods of arbitrary DIDs.
+pub trait DidResolver {
+	fn resolve(&self, did: &Did) -> Result<JsonValue, &'static str>;
+}
+
+impl<F> DidResolver for F
+where
+	F: Fn(&Did) -> Result<JsonValue, &'static str>,
+{
+	fn resolve(&self, did: &Did) -> Result<JsonValue, &'static str> {
+		self(did)
+	}
+}
+
+fn new_document(did: &str) -> JsonValue {
+	let mut doc = JsonValue::new_object();
+	doc[CONTEXT_PROP] = GENERIC_DID_CTX.into();
+	doc[SUBJECT_PROP] = did.into();
+	doc
+}
+
+fn push_member(doc: &mut JsonValue, prop: &str, value: JsonValue) -> Result<(), &'static str> {
+	if doc[prop].is_null() {
+		doc[prop] = JsonValue::new_array();
+	}
+	doc[prop].push(value).map_err(|_| "invalid DID document")
+}
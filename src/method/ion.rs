@@ -0,0 +1,190 @@
+use crate::lib::std::str::{self, FromStr};
+use crate::lib::std::string::{String, ToString};
+
+use crate::did::Did;
+use crate::did_doc::{DidDocument, PublicKeyType, VerificationRelationship, KEYJWK_PROP};
+use crate::did_parser::DID_SCHEME;
+use crate::diddoc_parser::{
+	CTRL_PROP, ID_PROP, PUBKEYS_PROP, SERVICE_PROP, SVCENDP_PROP, TYPE_PROP,
+};
+use crate::encoding::{base64url_decode, base64url_encode, sha256_multihash};
+use crate::jcs;
+
+use json::JsonValue;
+
+use super::{new_document, push_member};
+
+// Implement resolution of long-form did:ion DIDs following the Sidetree specification at:
+// https://identity.foundation/sidetree/spec/#long-form-did-uris
+
+pub const ION_METHOD: &str = "ion";
+const SEGMENT_SEP: char = ':';
+const SUFFIX_DATA_PROP: &str = "suffixData";
+const DELTA_PROP: &str = "delta";
+const DELTA_HASH_PROP: &str = "deltaHash";
+const RECOVERY_COMMITMENT_PROP: &str = "recoveryCommitment";
+const UPDATE_COMMITMENT_PROP: &str = "updateCommitment";
+const PATCHES_PROP: &str = "patches";
+const ACTION_PROP: &str = "action";
+const DOCUMENT_PROP: &str = "document";
+const PUBLIC_KEYS_PROP: &str = "publicKeys";
+const SERVICES_PROP: &str = "services";
+const PURPOSES_PROP: &str = "purposes";
+const REPLACE_ACTION: &str = "replace";
+const SECP256K1_2019_KEY_TYPE: &str = "EcdsaSecp256k1VerificationKey2019";
+
+/// Sidetree method metadata of a did:ion DID document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IonDocumentMetadata {
+	published: bool,
+	equivalent_id: String,
+	recovery_commitment: String,
+	update_commitment: String,
+}
+
+impl IonDocumentMetadata {
+	pub fn published(&self) -> bool {
+		self.published
+	}
+
+	pub fn equivalent_id(&self) -> &str {
+		&self.equivalent_id
+	}
+
+	pub fn recovery_commitment(&self) -> &str {
+		&self.recovery_commitment
+	}
+
+	pub fn update_commitment(&self) -> &str {
+		&self.update_commitment
+	}
+}
+
+/// Result of resolving a did:ion DID: its DID document along with its metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IonResolution {
+	document: JsonValue,
+	metadata: IonDocumentMetadata,
+}
+
+impl IonResolution {
+	pub fn document(&self) -> &JsonValue {
+		&self.document
+	}
+
+	pub fn did_document(&self) -> Result<DidDocument<'_>, &str> {
+		DidDocument::parse(&self.document)
+	}
+
+	pub fn metadata(&self) -> &IonDocumentMetadata {
+		&self.metadata
+	}
+}
+
+fn hash(value: &JsonValue) -> Result<String, &'static str> {
+	Ok(base64url_encode(&sha256_multihash(
+		jcs::canonicalize(value)?.as_bytes(),
+	)))
+}
+
+fn parse_str<'a>(value: &'a JsonValue, prop: &str) -> Result<&'a str, &'static str> {
+	value[prop].as_str().ok_or("invalid did:ion initial state")
+}
+
+fn key_type(value: &JsonValue) -> Result<PublicKeyType, &'static str> {
+	match value[TYPE_PROP].as_str() {
+		Some(SECP256K1_2019_KEY_TYPE) => Ok(PublicKeyType::EcdsaSecp256k1),
+		Some(key_type) => {
+			PublicKeyType::from_str(key_type).map_err(|_| "unsupported did:ion public key type")
+		}
+		None => Err("invalid did:ion public key"),
+	}
+}
+
+fn apply_replace(did: &str, document: &JsonValue) -> Result<JsonValue, &'static str> {
+	let mut doc = new_document(did);
+	for key in document[PUBLIC_KEYS_PROP].members() {
+		let id = format!("{}#{}", did, parse_str(key, ID_PROP)?);
+		if !key[KEYJWK_PROP].is_object() {
+			return Err("invalid did:ion public key");
+		}
+		let mut pub_key = JsonValue::new_object();
+		pub_key[ID_PROP] = id.as_str().into();
+		pub_key[TYPE_PROP] = key_type(key)?.to_string().into();
+		pub_key[CTRL_PROP] = did.into();
+		pub_key[KEYJWK_PROP] = key[KEYJWK_PROP].clone();
+		push_member(&mut doc, PUBKEYS_PROP, pub_key)?;
+		for purpose in key[PURPOSES_PROP].members() {
+			let rel = purpose
+				.as_str()
+				.and_then(|p| VerificationRelationship::from_str(p).ok())
+				.ok_or("invalid did:ion public key purpose")?;
+			push_member(&mut doc, rel.as_str(), id.as_str().into())?;
+		}
+	}
+	for service in document[SERVICES_PROP].members() {
+		let mut svc = JsonValue::new_object();
+		svc[ID_PROP] = format!("{}#{}", did, parse_str(service, ID_PROP)?).into();
+		svc[TYPE_PROP] = parse_str(service, TYPE_PROP)?.into();
+		svc[SVCENDP_PROP] = service[SVCENDP_PROP].clone();
+		push_member(&mut doc, SERVICE_PROP, svc)?;
+	}
+	Ok(doc)
+}
+
+fn decode_initial_state(encoded: &str) -> Option<JsonValue> {
+	let decoded = base64url_decode(encoded).ok()?;
+	json::parse(str::from_utf8(&decoded).ok()?).ok()
+}
+
+/// Resolves a long-form did:ion DID offline from its embedded initial state.
+pub fn resolve(did: &Did) -> Result<IonResolution, &'static str> {
+	if did.method_name() != ION_METHOD {
+		return Err("not a did:ion DID");
+	}
+
+	// Short forms may be prefixed with a network (did:ion:test:EiA...), so only a
+	// last segment holding an encoded initial state makes a long form
+	let method_id = did.method_specific_id();
+	let (short_id, initial_state) = method_id
+		.rfind(SEGMENT_SEP)
+		.and_then(|pos| {
+			decode_initial_state(&method_id[pos + 1..]).map(|state| (&method_id[..pos], state))
+		})
+		.ok_or("cannot resolve did:ion short form without an ION node")?;
+	if !initial_state.is_object() {
+		return Err("invalid did:ion initial state");
+	}
+	let suffix = short_id.rsplit(SEGMENT_SEP).next().unwrap_or_default();
+
+	let suffix_data = &initial_state[SUFFIX_DATA_PROP];
+	let delta = &initial_state[DELTA_PROP];
+	if !suffix_data.is_object() || !delta.is_object() {
+		return Err("invalid did:ion initial state");
+	}
+	if hash(suffix_data)? != suffix {
+		return Err("did:ion suffix does not match create operation");
+	}
+	if hash(delta)? != parse_str(suffix_data, DELTA_HASH_PROP)? {
+		return Err("did:ion delta hash does not match create operation");
+	}
+
+	let subject = format!("{}:{}:{}", DID_SCHEME, ION_METHOD, method_id);
+	let mut document = new_document(&subject);
+	for patch in delta[PATCHES_PROP].members() {
+		document = match patch[ACTION_PROP].as_str() {
+			Some(REPLACE_ACTION) => apply_replace(&subject, &patch[DOCUMENT_PROP])?,
+			_ => return Err("unsupported did:ion patch action"),
+		};
+	}
+
+	Ok(IonResolution {
+		document,
+		metadata: IonDocumentMetadata {
+			published: false,
+			equivalent_id: format!("{}:{}:{}", DID_SCHEME, ION_METHOD, short_id),
+			recovery_commitment: parse_str(suffix_data, RECOVERY_COMMITMENT_PROP)?.to_string(),
+			update_commitment: parse_str(delta, UPDATE_COMMITMENT_PROP)?.to_string(),
+		},
+	})
+}
@@ -0,0 +1,92 @@
+use crate::lib::std::str;
+use crate::lib::std::string::{String, ToString};
+
+use crate::did::Did;
+use crate::did_doc::{PublicKeyType, VerificationRelationship, KEYJWK_PROP, VERIF_RELATIONSHIPS};
+use crate::did_parser::DID_SCHEME;
+use crate::diddoc_parser::{CTRL_PROP, ID_PROP, PUBKEYS_PROP, TYPE_PROP};
+use crate::encoding::{base64url_decode, base64url_encode};
+
+use json::JsonValue;
+
+use super::{new_document, push_member};
+
+// Implement the did:jwk method following the specification at:
+// https://github.com/quartzjer/did-jwk/blob/main/spec.md
+
+pub const JWK_METHOD: &str = "jwk";
+const KEY_ID: &str = "0";
+const KTY_PROP: &str = "kty";
+const USE_PROP: &str = "use";
+const SIG_USE: &str = "sig";
+const ENC_USE: &str = "enc";
+const SYMMETRIC_KTY: &str = "oct";
+const PRIVATE_KEY_PROPS: [&str; 7] = ["d", "p", "q", "dp", "dq", "qi", "oth"];
+
+fn validate_jwk(jwk: &JsonValue) -> Result<(), &'static str> {
+	match jwk[KTY_PROP].as_str() {
+		Some(SYMMETRIC_KTY) => Err("did:jwk key must not be a symmetric key"),
+		Some(_) if PRIVATE_KEY_PROPS.iter().any(|prop| jwk.has_key(prop)) => {
+			Err("did:jwk key must not contain private key material")
+		}
+		Some(_) => Ok(()),
+		None => Err("invalid did:jwk key"),
+	}
+}
+
+fn key_relationships(jwk: &JsonValue) -> Result<&'static [VerificationRelationship], &'static str> {
+	match jwk[USE_PROP].as_str() {
+		Some(SIG_USE) => Ok(&[
+			VerificationRelationship::Authentication,
+			VerificationRelationship::AssertionMethod,
+			VerificationRelationship::CapabilityInvocation,
+			VerificationRelationship::CapabilityDelegation,
+		]),
+		Some(ENC_USE) => Ok(&[VerificationRelationship::KeyAgreement]),
+		Some(_) => Err("invalid did:jwk key use"),
+		None if jwk.has_key(USE_PROP) => Err("invalid did:jwk key use"),
+		None => Ok(&VERIF_RELATIONSHIPS),
+	}
+}
+
+/// Generates a did:jwk DID from a public JWK.
+pub fn generate(jwk: &JsonValue) -> Result<String, &'static str> {
+	validate_jwk(jwk)?;
+	Ok(format!(
+		"{}:{}:{}",
+		DID_SCHEME,
+		JWK_METHOD,
+		base64url_encode(jwk.dump().as_bytes())
+	))
+}
+
+/// Resolves a did:jwk DID offline by expanding its encoded JWK into a DID document.
+pub fn resolve(did: &Did) -> Result<JsonValue, &'static str> {
+	if did.method_name() != JWK_METHOD {
+		return Err("not a did:jwk DID");
+	}
+
+	let decoded = base64url_decode(did.method_specific_id())?;
+	let jwk = str::from_utf8(&decoded)
+		.ok()
+		.and_then(|s| json::parse(s).ok())
+		.filter(JsonValue::is_object)
+		.ok_or("invalid did:jwk key")?;
+	validate_jwk(&jwk)?;
+	let relationships = key_relationships(&jwk)?;
+
+	let subject = format!("{}:{}:{}", DID_SCHEME, JWK_METHOD, did.method_specific_id());
+	let key_id = format!("{}#{}", subject, KEY_ID);
+	let mut key = JsonValue::new_object();
+	key[ID_PROP] = key_id.as_str().into();
+	key[TYPE_PROP] = PublicKeyType::JsonWebKey.to_string().into();
+	key[CTRL_PROP] = subject.as_str().into();
+	key[KEYJWK_PROP] = jwk;
+
+	let mut doc = new_document(&subject);
+	push_member(&mut doc, PUBKEYS_PROP, key)?;
+	for rel in relationships {
+		push_member(&mut doc, rel.as_str(), key_id.as_str().into())?;
+	}
+	Ok(doc)
+}
@@ -0,0 +1,398 @@
+use crate::lib::std::str;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+
+use crate::did::Did;
+use crate::did_doc::{
+	PublicKeyType, VerificationRelationship, KEYB58_PROP, KEYMUL_PROP, VERIF_RELATIONSHIPS,
+};
+use crate::did_parser::DID_SCHEME;
+use crate::diddoc_parser::{
+	CTRL_PROP, ID_PROP, PUBKEYS_PROP, SERVICE_PROP, SUBJECT_PROP, SVCENDP_PROP, TYPE_PROP,
+};
+use crate::encoding::{
+	base58_encode, base64url_decode, base64url_encode, multibase_decode, multibase_encode,
+	multicodec_decode, multicodec_encode, sha256_multihash, ED25519_PUB_CODEC, JSON_CODEC,
+	X25519_PUB_CODEC,
+};
+
+use json::JsonValue;
+
+use super::{new_document, push_member};
+
+// Implement the did:peer method (numalgo 0, 2 and 4) following the specification at:
+// https://identity.foundation/peer-did-method-spec/
+
+pub const PEER_METHOD: &str = "peer";
+const NUMALGO_0: char = '0';
+const NUMALGO_2: char = '2';
+const NUMALGO_4: char = '4';
+const ELEMENT_SEP: char = '.';
+const HASH_SEP: char = ':';
+const SERVICE_PURPOSE: char = 'S';
+
+const KEY_ID_PREFIX: &str = "key-";
+const SERVICE_ID: &str = "service";
+const VERIF_METHOD_PROP: &str = "verificationMethod";
+const ALSO_KNOWN_AS_PROP: &str = "alsoKnownAs";
+const ROUTING_KEYS_PROP: &str = "routingKeys";
+const ACCEPT_PROP: &str = "accept";
+const URI_PROP: &str = "uri";
+
+const DIDCOMM_SERVICE_TYPE: &str = "DIDCommMessaging";
+const DIDCOMM_SERVICE_ABBR: &str = "dm";
+const SERVICE_ABBREVIATIONS: [(&str, &str); 4] = [
+	(TYPE_PROP, "t"),
+	(SVCENDP_PROP, "s"),
+	(ROUTING_KEYS_PROP, "r"),
+	(ACCEPT_PROP, "a"),
+];
+const MULTIKEY_TYPES: [&str; 3] = [
+	"Multikey",
+	"Ed25519VerificationKey2020",
+	"X25519KeyAgreementKey2020",
+];
+
+/// A key of a numalgo 2 DID: its purpose, type and raw public key bytes.
+pub type PeerKeyTuple<'a> = (VerificationRelationship, PublicKeyType, &'a [u8]);
+
+fn purpose_code(relationship: VerificationRelationship) -> char {
+	match relationship {
+		VerificationRelationship::AssertionMethod => 'A',
+		VerificationRelationship::KeyAgreement => 'E',
+		VerificationRelationship::Authentication => 'V',
+		VerificationRelationship::CapabilityInvocation => 'I',
+		VerificationRelationship::CapabilityDelegation => 'D',
+	}
+}
+
+fn purpose_from_code(code: char) -> Result<VerificationRelationship, &'static str> {
+	VERIF_RELATIONSHIPS
+		.iter()
+		.find(|rel| purpose_code(**rel) == code)
+		.copied()
+		.ok_or("invalid did:peer purpose code")
+}
+
+fn encode_key(key_type: &PublicKeyType, public_key: &[u8]) -> Result<String, &'static str> {
+	let codec = match key_type {
+		PublicKeyType::Ed25519 => ED25519_PUB_CODEC,
+		PublicKeyType::X25519 => X25519_PUB_CODEC,
+		_ => return Err("unsupported did:peer key type"),
+	};
+	if public_key.len() != 32 {
+		return Err("invalid did:peer public key length");
+	}
+	Ok(multibase_encode(&multicodec_encode(codec, public_key)))
+}
+
+fn decode_key(encoded: &str) -> Result<(PublicKeyType, Vec<u8>), &'static str> {
+	let bytes = multibase_decode(encoded)?;
+	let (codec, public_key) = multicodec_decode(&bytes)?;
+	let key_type = match codec {
+		ED25519_PUB_CODEC => PublicKeyType::Ed25519,
+		X25519_PUB_CODEC => PublicKeyType::X25519,
+		_ => return Err("unsupported did:peer key type"),
+	};
+	if public_key.len() != 32 {
+		return Err("invalid did:peer public key length");
+	}
+	Ok((key_type, public_key.to_vec()))
+}
+
+fn abbreviate_service(service: &JsonValue) -> JsonValue {
+	match service {
+		JsonValue::Object(obj) => {
+			let mut abbreviated = JsonValue::new_object();
+			for (key, value) in obj.iter() {
+				let abbr = SERVICE_ABBREVIATIONS
+					.iter()
+					.find(|(prop, _)| *prop == key)
+					.map_or(key, |(_, abbr)| abbr);
+				abbreviated[abbr] = match value.as_str() {
+					Some(DIDCOMM_SERVICE_TYPE) if key == TYPE_PROP => DIDCOMM_SERVICE_ABBR.into(),
+					_ => abbreviate_service(value),
+				};
+			}
+			abbreviated
+		}
+		other => other.clone(),
+	}
+}
+
+fn expand_service(service: &JsonValue) -> JsonValue {
+	match service {
+		JsonValue::Object(obj) => {
+			let mut expanded = JsonValue::new_object();
+			for (key, value) in obj.iter() {
+				let prop = SERVICE_ABBREVIATIONS
+					.iter()
+					.find(|(_, abbr)| *abbr == key)
+					.map_or(key, |(prop, _)| prop);
+				expanded[prop] = match value.as_str() {
+					Some(DIDCOMM_SERVICE_ABBR) if prop == TYPE_PROP => DIDCOMM_SERVICE_TYPE.into(),
+					_ => expand_service(value),
+				};
+			}
+			expanded
+		}
+		other => other.clone(),
+	}
+}
+
+fn absolute_id(did: &str, id: &str) -> String {
+	if id.starts_with('#') || id.starts_with(';') {
+		format!("{}{}", did, id)
+	} else {
+		id.to_string()
+	}
+}
+
+fn build_key(did: &str, id: &str, key_type: &PublicKeyType, public_key: &[u8]) -> JsonValue {
+	let mut key = JsonValue::new_object();
+	key[ID_PROP] = absolute_id(did, id).into();
+	key[TYPE_PROP] = key_type.to_string().into();
+	key[CTRL_PROP] = did.into();
+	key[KEYB58_PROP] = base58_encode(public_key).into();
+	key
+}
+
+// Services with a DIDComm v2 endpoint object are flattened to a URI endpoint,
+// with the routing keys and accepted media types as extra service properties.
+fn contextualize_service(did: &str, index: usize, mut service: JsonValue) -> JsonValue {
+	let id = match service[ID_PROP].as_str() {
+		Some(id) => absolute_id(did, id),
+		None if index == 0 => format!("{}#{}", did, SERVICE_ID),
+		None => format!("{}#{}-{}", did, SERVICE_ID, index),
+	};
+	service[ID_PROP] = id.into();
+	if service[SVCENDP_PROP].is_object() {
+		let mut endpoint = service.remove(SVCENDP_PROP);
+		service[SVCENDP_PROP] = endpoint.remove(URI_PROP);
+		for prop in [ROUTING_KEYS_PROP, ACCEPT_PROP].iter() {
+			if endpoint.has_key(prop) {
+				service[*prop] = endpoint.remove(prop);
+			}
+		}
+	}
+	service
+}
+
+fn contextualize_key(did: &str, mut key: JsonValue) -> Result<JsonValue, &'static str> {
+	let id = key[ID_PROP]
+		.as_str()
+		.map(|id| absolute_id(did, id))
+		.ok_or("missing did:peer verification method id")?;
+	key[ID_PROP] = id.into();
+	if !key.has_key(CTRL_PROP) {
+		key[CTRL_PROP] = did.into();
+	}
+
+	let is_multikey = key[TYPE_PROP]
+		.as_str()
+		.is_some_and(|t| MULTIKEY_TYPES.contains(&t));
+	if is_multikey {
+		let (key_type, public_key) = key[KEYMUL_PROP]
+			.as_str()
+			.ok_or("missing did:peer public key")
+			.and_then(decode_key)?;
+		key.remove(KEYMUL_PROP);
+		key[TYPE_PROP] = key_type.to_string().into();
+		key[KEYB58_PROP] = base58_encode(&public_key).into();
+	}
+	Ok(key)
+}
+
+/// Generates a numalgo 0 DID from a single Ed25519 or X25519 inception key.
+pub fn generate_numalgo0(
+	key_type: PublicKeyType,
+	public_key: &[u8],
+) -> Result<String, &'static str> {
+	Ok(format!(
+		"{}:{}:{}{}",
+		DID_SCHEME,
+		PEER_METHOD,
+		NUMALGO_0,
+		encode_key(&key_type, public_key)?
+	))
+}
+
+/// Generates a numalgo 2 DID from a list of purposed keys and services,
+/// services being abbreviated before being encoded.
+pub fn generate_numalgo2(
+	keys: &[PeerKeyTuple],
+	services: &[JsonValue],
+) -> Result<String, &'static str> {
+	let mut did = format!("{}:{}:{}", DID_SCHEME, PEER_METHOD, NUMALGO_2);
+	for (purpose, key_type, public_key) in keys {
+		did.push(ELEMENT_SEP);
+		did.push(purpose_code(*purpose));
+		did.push_str(&encode_key(key_type, public_key)?);
+	}
+	for service in services {
+		if !service.is_object() {
+			return Err("invalid did:peer service");
+		}
+		did.push(ELEMENT_SEP);
+		did.push(SERVICE_PURPOSE);
+		did.push_str(&base64url_encode(
+			abbreviate_service(service).dump().as_bytes(),
+		));
+	}
+	Ok(did)
+}
+
+/// Generates the long form of a numalgo 4 DID from an input document,
+/// which must not contain an `id`.
+pub fn generate_numalgo4(input_doc: &JsonValue) -> Result<String, &'static str> {
+	if !input_doc.is_object() || input_doc.has_key(SUBJECT_PROP) {
+		return Err("invalid did:peer input document");
+	}
+	let encoded = multibase_encode(&multicodec_encode(JSON_CODEC, input_doc.dump().as_bytes()));
+	let hash = multibase_encode(&sha256_multihash(encoded.as_bytes()));
+	Ok(format!(
+		"{}:{}:{}{}{}{}",
+		DID_SCHEME, PEER_METHOD, NUMALGO_4, hash, HASH_SEP, encoded
+	))
+}
+
+/// Returns the short form of a numalgo 4 DID.
+pub fn short_form(did: &Did) -> Result<String, &'static str> {
+	match did.method_specific_id().split(HASH_SEP).next() {
+		Some(hash) if did.method_name() == PEER_METHOD && hash.starts_with(NUMALGO_4) => {
+			Ok(format!("{}:{}:{}", DID_SCHEME, PEER_METHOD, hash))
+		}
+		_ => Err("not a did:peer numalgo 4 DID"),
+	}
+}
+
+fn resolve_numalgo0(did: &str, encoded_key: &str) -> Result<JsonValue, &'static str> {
+	let (key_type, public_key) = decode_key(encoded_key)?;
+	let relationships: &[VerificationRelationship] = match key_type {
+		PublicKeyType::X25519 => &[VerificationRelationship::KeyAgreement],
+		_ => &[
+			VerificationRelationship::Authentication,
+			VerificationRelationship::AssertionMethod,
+			VerificationRelationship::CapabilityInvocation,
+			VerificationRelationship::CapabilityDelegation,
+		],
+	};
+
+	let key_id = format!("{}#{}", did, encoded_key);
+	let mut doc = new_document(did);
+	push_member(
+		&mut doc,
+		PUBKEYS_PROP,
+		build_key(did, &key_id, &key_type, &public_key),
+	)?;
+	for rel in relationships {
+		push_member(&mut doc, rel.as_str(), key_id.as_str().into())?;
+	}
+	Ok(doc)
+}
+
+fn resolve_numalgo2(did: &str, elements: &str) -> Result<JsonValue, &'static str> {
+	let mut doc = new_document(did);
+	let mut elements = elements.split(ELEMENT_SEP);
+	if elements.next() != Some("") {
+		return Err("invalid did:peer numalgo 2 DID");
+	}
+
+	let (mut key_count, mut service_count) = (0, 0);
+	for element in elements {
+		let mut chars = element.chars();
+		match chars.next() {
+			Some(SERVICE_PURPOSE) => {
+				let decoded = base64url_decode(chars.as_str())?;
+				let json = str::from_utf8(&decoded)
+					.ok()
+					.and_then(|s| json::parse(s).ok())
+					.ok_or("invalid did:peer service")?;
+				let services = if json.is_array() {
+					json.members().cloned().collect::<Vec<_>>()
+				} else {
+					vec![json]
+				};
+				for service in services.iter().map(expand_service) {
+					let service = contextualize_service(did, service_count, service);
+					push_member(&mut doc, SERVICE_PROP, service)?;
+					service_count += 1;
+				}
+			}
+			Some(code) => {
+				let purpose = purpose_from_code(code)?;
+				let (key_type, public_key) = decode_key(chars.as_str())?;
+				key_count += 1;
+				let key_id = format!("#{}{}", KEY_ID_PREFIX, key_count);
+				let key = build_key(did, &key_id, &key_type, &public_key);
+				push_member(&mut doc, purpose.as_str(), key[ID_PROP].clone())?;
+				push_member(&mut doc, PUBKEYS_PROP, key)?;
+			}
+			None => return Err("invalid did:peer numalgo 2 DID"),
+		}
+	}
+	Ok(doc)
+}
+
+fn resolve_numalgo4(did: &str, suffix: &str) -> Result<JsonValue, &'static str> {
+	let mut parts = suffix.splitn(2, HASH_SEP);
+	let hash = parts.next().unwrap_or_default();
+	let encoded = parts
+		.next()
+		.ok_or("cannot resolve did:peer numalgo 4 short form without its long form")?;
+	if multibase_encode(&sha256_multihash(encoded.as_bytes())) != hash {
+		return Err("did:peer numalgo 4 hash does not match input document");
+	}
+
+	let decoded = multibase_decode(encoded)?;
+	let mut input_doc = match multicodec_decode(&decoded)? {
+		(JSON_CODEC, bytes) => str::from_utf8(bytes)
+			.ok()
+			.and_then(|s| json::parse(s).ok())
+			.filter(JsonValue::is_object)
+			.ok_or("invalid did:peer input document")?,
+		_ => return Err("invalid did:peer input document"),
+	};
+
+	let mut doc = new_document(did);
+	doc[ALSO_KNOWN_AS_PROP] = json::array![format!(
+		"{}:{}:{}{}",
+		DID_SCHEME, PEER_METHOD, NUMALGO_4, hash
+	)];
+	for prop in [VERIF_METHOD_PROP, PUBKEYS_PROP].iter() {
+		for key in input_doc.remove(prop).members() {
+			push_member(&mut doc, PUBKEYS_PROP, contextualize_key(did, key.clone())?)?;
+		}
+	}
+	for rel in VERIF_RELATIONSHIPS.iter() {
+		for method in input_doc.remove(rel.as_str()).members() {
+			let method = match method.as_str() {
+				Some(id) => absolute_id(did, id).into(),
+				None => contextualize_key(did, method.clone())?,
+			};
+			push_member(&mut doc, rel.as_str(), method)?;
+		}
+	}
+	for (index, service) in input_doc.remove(SERVICE_PROP).members().enumerate() {
+		let service = contextualize_service(did, index, service.clone());
+		push_member(&mut doc, SERVICE_PROP, service)?;
+	}
+	Ok(doc)
+}
+
+/// Resolves a did:peer DID offline into its DID document.
+pub fn resolve(did: &Did) -> Result<JsonValue, &'static str> {
+	if did.method_name() != PEER_METHOD {
+		return Err("not a did:peer DID");
+	}
+
+	let method_id = did.method_specific_id();
+	let subject = format!("{}:{}:{}", DID_SCHEME, PEER_METHOD, method_id);
+	let mut chars = method_id.chars();
+	match chars.next() {
+		Some(NUMALGO_0) => resolve_numalgo0(&subject, chars.as_str()),
+		Some(NUMALGO_2) => resolve_numalgo2(&subject, chars.as_str()),
+		Some(NUMALGO_4) => resolve_numalgo4(&subject, chars.as_str()),
+		_ => Err("unsupported did:peer numalgo"),
+	}
+}
@@ -0,0 +1,96 @@
+use crate::lib::std::string::{String, ToString};
+
+use crate::caip::AccountId;
+use crate::did::Did;
+use crate::did_doc::{PublicKeyType, VerificationRelationship, KEYACC_PROP, KEYB58_PROP};
+use crate::did_parser::DID_SCHEME;
+use crate::diddoc_parser::{CTRL_PROP, ID_PROP, PUBKEYS_PROP, TYPE_PROP};
+use crate::encoding::base58_decode;
+
+use json::JsonValue;
+
+use super::{new_document, push_member};
+
+// Implement the did:pkh method following the specification at:
+// https://github.com/w3c-ccg/did-pkh/blob/main/did-pkh-method-draft.md
+
+pub const PKH_METHOD: &str = "pkh";
+const EIP155_NAMESPACE: &str = "eip155";
+const SOLANA_NAMESPACE: &str = "solana";
+const ACCOUNT_KEY_ID: &str = "blockchainAccountId";
+const CONTROLLER_KEY_ID: &str = "controller";
+const HEX_PREFIX: &str = "0x";
+const KEY_RELATIONSHIPS: [VerificationRelationship; 2] = [
+	VerificationRelationship::Authentication,
+	VerificationRelationship::AssertionMethod,
+];
+
+fn validate_address(account: &AccountId) -> Result<(), &'static str> {
+	let address = account.address();
+	match account.chain_id().namespace() {
+		EIP155_NAMESPACE => {
+			let valid = address.len() == 42
+				&& address.starts_with(HEX_PREFIX)
+				&& address[2..].chars().all(|c| c.is_ascii_hexdigit());
+			if valid {
+				Ok(())
+			} else {
+				Err("invalid did:pkh eip155 address")
+			}
+		}
+		SOLANA_NAMESPACE => match base58_decode(address) {
+			Ok(ref key) if key.len() == 32 => Ok(()),
+			_ => Err("invalid did:pkh solana address"),
+		},
+		_ => Ok(()),
+	}
+}
+
+/// Generates a did:pkh DID from a CAIP-10 blockchain account id.
+pub fn generate(account: &AccountId) -> Result<String, &'static str> {
+	validate_address(account)?;
+	Ok(format!("{}:{}:{}", DID_SCHEME, PKH_METHOD, account))
+}
+
+/// Extracts the CAIP-10 blockchain account id of a did:pkh DID.
+pub fn account_id<'a>(did: &Did<'a>) -> Result<AccountId<'a>, &'static str> {
+	if did.method_name() != PKH_METHOD {
+		return Err("not a did:pkh DID");
+	}
+	let account = AccountId::parse(did.method_specific_id())?;
+	validate_address(&account)?;
+	Ok(account)
+}
+
+/// Resolves a did:pkh DID offline into a DID document controlled by its blockchain account.
+pub fn resolve(did: &Did) -> Result<JsonValue, &'static str> {
+	let account = account_id(did)?;
+	let subject = format!("{}:{}:{}", DID_SCHEME, PKH_METHOD, account);
+
+	let mut key = JsonValue::new_object();
+	let key_id = match account.chain_id().namespace() {
+		EIP155_NAMESPACE => {
+			key[TYPE_PROP] = PublicKeyType::EcdsaSecp256k1Recovery.to_string().into();
+			format!("{}#{}", subject, ACCOUNT_KEY_ID)
+		}
+		SOLANA_NAMESPACE => {
+			key[TYPE_PROP] = PublicKeyType::Ed25519.to_string().into();
+			key[KEYB58_PROP] = account.address().into();
+			format!("{}#{}", subject, CONTROLLER_KEY_ID)
+		}
+		_ => {
+			key[TYPE_PROP] = PublicKeyType::Blockchain.to_string().into();
+			format!("{}#{}", subject, ACCOUNT_KEY_ID)
+		}
+	};
+	key[ID_PROP] = key_id.as_str().into();
+	key[CTRL_PROP] = subject.as_str().into();
+	key[KEYACC_PROP] = account.to_string().into();
+
+	let mut doc = new_document(&subject);
+	push_member(&mut doc, PUBKEYS_PROP, key)?;
+	for rel in KEY_RELATIONSHIPS.iter() {
+		push_member(&mut doc, rel.as_str(), key_id.as_str().into())?;
+	}
+	Ok(doc)
+}
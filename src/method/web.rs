@@ -0,0 +1,178 @@
+use crate::lib::std::string::String;
+use crate::lib::std::vec::Vec;
+
+use crate::did::Did;
+use crate::did_parser::DID_SCHEME;
+use crate::diddoc_parser::SUBJECT_PROP;
+
+use json::JsonValue;
+
+// Implement the did:web method following the specification at:
+// https://w3c-ccg.github.io/did-method-web/
+
+pub const WEB_METHOD: &str = "web";
+const HTTPS_SCHEME: &str = "https://";
+const WELL_KNOWN_PATH: &str = "/.well-known";
+const DID_DOC_PATH: &str = "/did.json";
+const ENCODED_PORT_SEP: &str = "%3A";
+
+/// Retrieves the body of an HTTPS resource, used to resolve did:web DIDs
+/// without tying the crate to a specific HTTP client.
+pub trait HttpFetch {
+	fn fetch(&self, url: &str) -> Result<String, &'static str>;
+}
+
+impl<F> HttpFetch for F
+where
+	F: Fn(&str) -> Result<String, &'static str>,
+{
+	fn fetch(&self, url: &str) -> Result<String, &'static str> {
+		self(url)
+	}
+}
+
+fn is_label_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '-'
+}
+
+fn is_segment_char(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_'
+}
+
+fn validate_domain(domain: &str) -> Result<(), &'static str> {
+	let valid = !domain.is_empty()
+		&& domain.len() <= 253
+		&& domain.split('.').all(|label| {
+			!label.is_empty()
+				&& label.len() <= 63
+				&& !label.starts_with('-')
+				&& !label.ends_with('-')
+				&& label.chars().all(is_label_char)
+		});
+	if valid {
+		Ok(())
+	} else {
+		Err("invalid did:web domain name")
+	}
+}
+
+fn validate_port(port: &str) -> Result<(), &'static str> {
+	match port.parse::<u16>() {
+		Ok(p) if p > 0 && port.chars().all(|c| c.is_ascii_digit()) => Ok(()),
+		_ => Err("invalid did:web port"),
+	}
+}
+
+fn validate_path_segment(segment: &str) -> Result<(), &'static str> {
+	if segment.is_empty() || segment == "." || segment == ".." {
+		return Err("invalid did:web path segment");
+	}
+	let bytes = segment.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] == b'%' {
+			match bytes.get(i + 1..i + 3) {
+				Some(hex) if hex.iter().all(u8::is_ascii_hexdigit) => i += 3,
+				_ => return Err("invalid did:web path segment"),
+			}
+		} else if is_segment_char(bytes[i] as char) {
+			i += 1;
+		} else {
+			return Err("invalid did:web path segment");
+		}
+	}
+	Ok(())
+}
+
+fn split_host(host: &str) -> (&str, Option<&str>) {
+	match host.to_ascii_uppercase().find(ENCODED_PORT_SEP) {
+		Some(i) => (&host[..i], Some(&host[i + ENCODED_PORT_SEP.len()..])),
+		None => (host, None),
+	}
+}
+
+/// Transforms a did:web DID into the HTTPS URL of its DID document.
+pub fn did_to_url(did: &Did) -> Result<String, &'static str> {
+	if did.method_name() != WEB_METHOD {
+		return Err("not a did:web DID");
+	}
+
+	let mut segments = did.method_specific_id().split(':');
+	let (domain, port) = split_host(segments.next().unwrap_or_default());
+	validate_domain(domain)?;
+	if let Some(port) = port {
+		validate_port(port)?;
+	}
+	let path = segments.collect::<Vec<_>>();
+	for segment in &path {
+		validate_path_segment(segment)?;
+	}
+
+	let mut url = String::from(HTTPS_SCHEME);
+	url.push_str(domain);
+	if let Some(port) = port {
+		url.push(':');
+		url.push_str(port);
+	}
+	if path.is_empty() {
+		url.push_str(WELL_KNOWN_PATH);
+	}
+	for segment in path {
+		url.push('/');
+		url.push_str(segment);
+	}
+	url.push_str(DID_DOC_PATH);
+	Ok(url)
+}
+
+/// Transforms the HTTPS URL of a DID document into the corresponding did:web DID.
+pub fn url_to_did(url: &str) -> Result<String, &'static str> {
+	let location = url
+		.strip_prefix(HTTPS_SCHEME)
+		.ok_or("did:web URL must use the https scheme")?;
+	let (authority, path) = location.split_at(location.find('/').unwrap_or(location.len()));
+	let path = path
+		.strip_suffix(DID_DOC_PATH)
+		.ok_or("did:web URL must point to a did.json document")?;
+
+	let (domain, port) = match authority.rfind(':') {
+		Some(i) => (&authority[..i], Some(&authority[i + 1..])),
+		None => (authority, None),
+	};
+	validate_domain(domain)?;
+	if let Some(port) = port {
+		validate_port(port)?;
+	}
+
+	let mut did = format!("{}:{}:{}", DID_SCHEME, WEB_METHOD, domain);
+	if let Some(port) = port {
+		did.push_str(ENCODED_PORT_SEP);
+		did.push_str(port);
+	}
+	if path != WELL_KNOWN_PATH {
+		if path.is_empty() {
+			return Err("invalid did:web URL path");
+		}
+		for segment in path[1..].split('/') {
+			validate_path_segment(segment)?;
+			did.push(':');
+			did.push_str(segment);
+		}
+	}
+	Ok(did)
+}
+
+/// Retrieves the DID document of a did:web DID and checks that it describes
+/// the requested DID subject.
+pub fn resolve<F: HttpFetch>(did: &Did, fetcher: &F) -> Result<JsonValue, &'static str> {
+	let url = did_to_url(did)?;
+	let body = fetcher.fetch(&url)?;
+	let json = json::parse(&body).map_err(|_| "invalid did:web DID document")?;
+
+	let subject = format!("{}:{}:{}", DID_SCHEME, WEB_METHOD, did.method_specific_id());
+	match json[SUBJECT_PROP].as_str() {
+		Some(id) if id == subject => Ok(json),
+		Some(_) => Err("did:web DID document id does not match DID"),
+		None => Err("missing did:web DID document id"),
+	}
+}
@@ -0,0 +1,385 @@
+use crate::lib::std::cell::Cell;
+use crate::lib::std::collections::BTreeMap;
+use crate::lib::std::fmt::Write;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+
+use crate::encoding::hex_encode;
+
+use sha2::{Digest, Sha256};
+
+// Implement RDF dataset canonicalization (RDFC-1.0, formerly URDNA2015) following the specification at:
+// https://www.w3.org/TR/rdf-canon/
+
+pub const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+pub const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+pub const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+pub const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+pub const RDF_LANGSTRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+pub const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+pub const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+pub const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+pub const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+
+const CANONICAL_PREFIX: &str = "_:c14n";
+const TEMPORARY_PREFIX: &str = "_:b";
+// Bounds the work spent on datasets crafted to make canonicalization explode
+const MAX_CANONICALIZATION_STEPS: usize = 100_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Term {
+	Iri(String),
+	Blank(String),
+	Literal {
+		value: String,
+		datatype: String,
+		language: Option<String>,
+	},
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Quad {
+	pub subject: Term,
+	pub predicate: String,
+	pub object: Term,
+	pub graph: Option<Term>,
+}
+
+fn write_literal(value: &str, out: &mut String) {
+	out.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\u{8}' => out.push_str("\\b"),
+			'\t' => out.push_str("\\t"),
+			'\n' => out.push_str("\\n"),
+			'\u{c}' => out.push_str("\\f"),
+			'\r' => out.push_str("\\r"),
+			c if (c as u32) < 0x20 || c == '\u{7f}' => {
+				let _ = write!(out, "\\u{:04X}", c as u32);
+			}
+			c => out.push(c),
+		}
+	}
+	out.push('"');
+}
+
+fn write_term(term: &Term, label: &dyn Fn(&str) -> String, out: &mut String) {
+	match term {
+		Term::Iri(iri) => {
+			out.push('<');
+			out.push_str(iri);
+			out.push('>');
+		}
+		Term::Blank(id) => out.push_str(&label(id)),
+		Term::Literal {
+			value,
+			datatype,
+			language,
+		} => {
+			write_literal(value, out);
+			match language {
+				Some(language) => {
+					out.push('@');
+					out.push_str(language);
+				}
+				None if datatype != XSD_STRING => {
+					out.push_str("^^<");
+					out.push_str(datatype);
+					out.push('>');
+				}
+				None => {}
+			}
+		}
+	}
+}
+
+// Serializes a quad in canonical N-Quads form, relabeling its blank nodes.
+fn nquad(quad: &Quad, label: &dyn Fn(&str) -> String) -> String {
+	let mut out = String::new();
+	write_term(&quad.subject, label, &mut out);
+	out.push_str(" <");
+	out.push_str(&quad.predicate);
+	out.push_str("> ");
+	write_term(&quad.object, label, &mut out);
+	if let Some(graph) = &quad.graph {
+		out.push(' ');
+		write_term(graph, label, &mut out);
+	}
+	out.push_str(" .\n");
+	out
+}
+
+fn sha256_hex(input: &str) -> String {
+	hex_encode(&Sha256::digest(input.as_bytes()))
+}
+
+// Issues identifiers for blank nodes in the order they are first requested.
+#[derive(Clone, Debug)]
+struct IdentifierIssuer {
+	prefix: &'static str,
+	issued: Vec<(String, String)>,
+}
+
+impl IdentifierIssuer {
+	fn new(prefix: &'static str) -> Self {
+		IdentifierIssuer {
+			prefix,
+			issued: vec![],
+		}
+	}
+
+	fn get(&self, existing: &str) -> Option<&str> {
+		self.issued
+			.iter()
+			.find(|(id, _)| id == existing)
+			.map(|(_, issued)| issued.as_str())
+	}
+
+	fn issue(&mut self, existing: &str) -> String {
+		if let Some(issued) = self.get(existing) {
+			return issued.to_string();
+		}
+		let issued = format!("{}{}", self.prefix, self.issued.len());
+		self.issued.push((existing.to_string(), issued.clone()));
+		issued
+	}
+}
+
+struct Canonicalizer<'q> {
+	quads: &'q [Quad],
+	blank_quads: BTreeMap<&'q str, Vec<&'q Quad>>,
+	first_degree: BTreeMap<&'q str, String>,
+	steps: Cell<usize>,
+}
+
+// Blank nodes of a quad along with their position: subject, object or graph name.
+fn blank_nodes(quad: &Quad) -> Vec<(char, &str)> {
+	let terms = vec![
+		('s', Some(&quad.subject)),
+		('o', Some(&quad.object)),
+		('g', quad.graph.as_ref()),
+	];
+	terms
+		.into_iter()
+		.filter_map(|(position, term)| match term {
+			Some(Term::Blank(id)) => Some((position, id.as_str())),
+			_ => None,
+		})
+		.collect()
+}
+
+fn permutations<'a>(items: &[&'a str]) -> Vec<Vec<&'a str>> {
+	if items.len() <= 1 {
+		return vec![items.to_vec()];
+	}
+	let mut result = vec![];
+	for (i, item) in items.iter().enumerate() {
+		let mut rest = items.to_vec();
+		rest.remove(i);
+		for mut permutation in permutations(&rest) {
+			permutation.insert(0, item);
+			result.push(permutation);
+		}
+	}
+	result
+}
+
+impl<'q> Canonicalizer<'q> {
+	fn new(quads: &'q [Quad]) -> Self {
+		let mut blank_quads: BTreeMap<&str, Vec<&Quad>> = BTreeMap::new();
+		for quad in quads {
+			let mut ids: Vec<&str> = blank_nodes(quad).into_iter().map(|(_, id)| id).collect();
+			ids.sort_unstable();
+			ids.dedup();
+			for id in ids {
+				blank_quads.entry(id).or_default().push(quad);
+			}
+		}
+		let mut canonicalizer = Canonicalizer {
+			quads,
+			blank_quads,
+			first_degree: BTreeMap::new(),
+			steps: Cell::new(0),
+		};
+		let first_degree = canonicalizer
+			.blank_quads
+			.keys()
+			.map(|id| (*id, canonicalizer.hash_first_degree(id)))
+			.collect();
+		canonicalizer.first_degree = first_degree;
+		canonicalizer
+	}
+
+	fn step(&self) -> Result<(), &'static str> {
+		let steps = self.steps.get() + 1;
+		if steps > MAX_CANONICALIZATION_STEPS {
+			return Err("RDF dataset is too complex to canonicalize");
+		}
+		self.steps.set(steps);
+		Ok(())
+	}
+
+	fn hash_first_degree(&self, reference: &str) -> String {
+		let label = |id: &str| {
+			if id == reference {
+				"_:a".to_string()
+			} else {
+				"_:z".to_string()
+			}
+		};
+		let mut nquads: Vec<String> = self.blank_quads[reference]
+			.iter()
+			.map(|quad| nquad(quad, &label))
+			.collect();
+		nquads.sort();
+		sha256_hex(&nquads.concat())
+	}
+
+	fn hash_related(
+		&self,
+		related: &str,
+		quad: &Quad,
+		canonical: &IdentifierIssuer,
+		issuer: &IdentifierIssuer,
+		position: char,
+	) -> String {
+		let id = canonical
+			.get(related)
+			.or_else(|| issuer.get(related))
+			.unwrap_or_else(|| self.first_degree[related].as_str());
+		let mut input = String::new();
+		input.push(position);
+		if position != 'g' {
+			input.push('<');
+			input.push_str(&quad.predicate);
+			input.push('>');
+		}
+		input.push_str(id);
+		sha256_hex(&input)
+	}
+
+	fn hash_n_degree(
+		&self,
+		reference: &'q str,
+		canonical: &IdentifierIssuer,
+		mut issuer: IdentifierIssuer,
+	) -> Result<(String, IdentifierIssuer), &'static str> {
+		self.step()?;
+		let mut hash_to_related: BTreeMap<String, Vec<&'q str>> = BTreeMap::new();
+		for quad in &self.blank_quads[reference] {
+			for (position, related) in blank_nodes(quad) {
+				if related != reference {
+					let hash = self.hash_related(related, quad, canonical, &issuer, position);
+					hash_to_related.entry(hash).or_default().push(related);
+				}
+			}
+		}
+
+		let mut data = String::new();
+		for (related_hash, related) in hash_to_related {
+			data.push_str(&related_hash);
+			let mut chosen_path = String::new();
+			let mut chosen_issuer = None;
+			'permutations: for permutation in permutations(&related) {
+				self.step()?;
+				let mut issuer_copy = issuer.clone();
+				let mut path = String::new();
+				let mut recursion = vec![];
+				for id in permutation {
+					match canonical.get(id) {
+						Some(canonical_id) => path.push_str(canonical_id),
+						None => {
+							if issuer_copy.get(id).is_none() {
+								recursion.push(id);
+							}
+							path.push_str(&issuer_copy.issue(id));
+						}
+					}
+					if !chosen_path.is_empty()
+						&& path.len() >= chosen_path.len()
+						&& path > chosen_path
+					{
+						continue 'permutations;
+					}
+				}
+				for id in recursion {
+					let (hash, result_issuer) =
+						self.hash_n_degree(id, canonical, issuer_copy.clone())?;
+					path.push_str(&issuer_copy.issue(id));
+					path.push('<');
+					path.push_str(&hash);
+					path.push('>');
+					issuer_copy = result_issuer;
+					if !chosen_path.is_empty()
+						&& path.len() >= chosen_path.len()
+						&& path > chosen_path
+					{
+						continue 'permutations;
+					}
+				}
+				if chosen_path.is_empty() || path < chosen_path {
+					chosen_path = path;
+					chosen_issuer = Some(issuer_copy);
+				}
+			}
+			data.push_str(&chosen_path);
+			if let Some(chosen_issuer) = chosen_issuer {
+				issuer = chosen_issuer;
+			}
+		}
+		Ok((sha256_hex(&data), issuer))
+	}
+
+	fn canonicalize(&self) -> Result<String, &'static str> {
+		let mut canonical = IdentifierIssuer::new(CANONICAL_PREFIX);
+
+		// Blank nodes with a unique first degree hash are labeled first, in hash order
+		let mut hash_to_blank: BTreeMap<&str, Vec<&'q str>> = BTreeMap::new();
+		for (id, hash) in &self.first_degree {
+			hash_to_blank.entry(hash).or_default().push(id);
+		}
+		let mut shared = vec![];
+		for ids in hash_to_blank.values() {
+			match ids.as_slice() {
+				[id] => {
+					canonical.issue(id);
+				}
+				ids => shared.push(ids),
+			}
+		}
+
+		for ids in shared {
+			let mut hash_paths = vec![];
+			for id in ids.iter() {
+				if canonical.get(id).is_some() {
+					continue;
+				}
+				let mut temporary = IdentifierIssuer::new(TEMPORARY_PREFIX);
+				temporary.issue(id);
+				hash_paths.push(self.hash_n_degree(id, &canonical, temporary)?);
+			}
+			hash_paths.sort_by(|a, b| a.0.cmp(&b.0));
+			for (_, issuer) in hash_paths {
+				for (id, _) in issuer.issued {
+					canonical.issue(&id);
+				}
+			}
+		}
+
+		let label = |id: &str| canonical.get(id).unwrap_or_default().to_string();
+		let mut nquads: Vec<String> = self.quads.iter().map(|q| nquad(q, &label)).collect();
+		nquads.sort();
+		Ok(nquads.concat())
+	}
+}
+
+/// Canonicalizes an RDF dataset into sorted N-Quads with canonical blank node labels.
+pub fn canonicalize(quads: &[Quad]) -> Result<String, &'static str> {
+	let mut quads = quads.to_vec();
+	quads.sort();
+	quads.dedup();
+	Canonicalizer::new(&quads).canonicalize()
+}
@@ -0,0 +1,531 @@
+use crate::lib::std::fmt;
+use crate::lib::std::str::FromStr;
+use crate::lib::std::string::{String, ToString};
+use crate::lib::std::vec::Vec;
+
+use crate::caip::AccountId;
+use crate::did_doc::{
+	DidDocument, PublicKey, PublicKeyEncoded, PublicKeyType, VerificationRelationship,
+};
+use crate::encoding::{
+	base58_decode, base64_decode, base64url_decode, hex_decode, multibase_decode,
+	multicodec_decode, ED25519_PUB_CODEC, P256_PUB_CODEC, SECP256K1_PUB_CODEC,
+};
+
+use json::JsonValue;
+
+// Verify signatures made with the verification methods of a DID document.
+
+const KTY_PROP: &str = "kty";
+const CRV_PROP: &str = "crv";
+const X_PROP: &str = "x";
+const Y_PROP: &str = "y";
+const N_PROP: &str = "n";
+const E_PROP: &str = "e";
+const OKP_KTY: &str = "OKP";
+const EC_KTY: &str = "EC";
+const RSA_KTY: &str = "RSA";
+const ED25519_CRV: &str = "Ed25519";
+const SECP256K1_CRV: &str = "secp256k1";
+const P256_CRV: &str = "P-256";
+#[cfg(feature = "ed25519")]
+const ED25519_KEY_LEN: usize = 32;
+const SEC1_UNCOMPRESSED_TAG: u8 = 0x04;
+#[cfg(any(feature = "secp256k1", feature = "p256"))]
+const ECDSA_COMPACT_SIG_LEN: usize = 64;
+#[cfg(feature = "secp256k1")]
+const ECDSA_RECOVERABLE_SIG_LEN: usize = 65;
+const ETH_ADDRESS_LEN: usize = 20;
+#[cfg(feature = "secp256k1")]
+const EIP191_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+const EIP155_NAMESPACE: &str = "eip155";
+#[cfg(feature = "rsa")]
+const PKCS1_PEM_LABEL: &str = "BEGIN RSA PUBLIC KEY";
+// Keys below 2048 bits are too weak for RS256 and PS256, see NIST SP 800-131A
+#[cfg(feature = "rsa")]
+const RSA_MIN_MODULUS_BITS: usize = 2048;
+
+/// Selects the verification method(s) a signature is checked against, either by id,
+/// by verification relationship, or by id restricted to a verification relationship.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeySelector<'s> {
+	Id(&'s str),
+	Relationship(VerificationRelationship),
+	IdFor(&'s str, VerificationRelationship),
+}
+
+impl<'s> From<&'s str> for KeySelector<'s> {
+	fn from(id: &'s str) -> Self {
+		KeySelector::Id(id)
+	}
+}
+
+impl From<VerificationRelationship> for KeySelector<'_> {
+	fn from(relationship: VerificationRelationship) -> Self {
+		KeySelector::Relationship(relationship)
+	}
+}
+
+impl<'s> From<(&'s str, VerificationRelationship)> for KeySelector<'s> {
+	fn from(s: (&'s str, VerificationRelationship)) -> Self {
+		KeySelector::IdFor(s.0, s.1)
+	}
+}
+
+/// Signature algorithms, named after their JSON Web Algorithms identifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+	EdDsa,
+	Es256K,
+	Es256KR,
+	Es256,
+	Rs256,
+	Ps256,
+}
+
+const EDDSA_ALG: &str = "EdDSA";
+const ES256K_ALG: &str = "ES256K";
+const ES256KR_ALG: &str = "ES256K-R";
+const ES256_ALG: &str = "ES256";
+const RS256_ALG: &str = "RS256";
+const PS256_ALG: &str = "PS256";
+
+impl SignatureAlgorithm {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::EdDsa => EDDSA_ALG,
+			Self::Es256K => ES256K_ALG,
+			Self::Es256KR => ES256KR_ALG,
+			Self::Es256 => ES256_ALG,
+			Self::Rs256 => RS256_ALG,
+			Self::Ps256 => PS256_ALG,
+		}
+	}
+}
+
+impl FromStr for SignatureAlgorithm {
+	type Err = ParseSignatureAlgorithmError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			EDDSA_ALG => Ok(Self::EdDsa),
+			ES256K_ALG => Ok(Self::Es256K),
+			ES256KR_ALG => Ok(Self::Es256KR),
+			ES256_ALG => Ok(Self::Es256),
+			RS256_ALG => Ok(Self::Rs256),
+			PS256_ALG => Ok(Self::Ps256),
+			_ => Err(ParseSignatureAlgorithmError(())),
+		}
+	}
+}
+
+impl fmt::Display for SignatureAlgorithm {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSignatureAlgorithmError(());
+
+impl fmt::Display for ParseSignatureAlgorithmError {
+	fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt.write_str("unsupported signature algorithm")
+	}
+}
+
+pub(crate) fn select_keys<'d, 'a>(
+	doc: &'d DidDocument<'a>,
+	selector: KeySelector,
+) -> Result<Vec<&'d PublicKey<'a>>, &'static str> {
+	match selector {
+		KeySelector::Id(id) => doc
+			.find_key(id)
+			.map(|key| vec![key])
+			.ok_or("verification method not found"),
+		KeySelector::Relationship(rel) => {
+			let keys: Vec<_> = doc.keys_for(rel).collect();
+			if keys.is_empty() {
+				Err("no verification method for relationship")
+			} else {
+				Ok(keys)
+			}
+		}
+		KeySelector::IdFor(id, rel) => doc
+			.keys_for(rel)
+			.find(|key| doc.matches_id(key.id(), id))
+			.map(|key| vec![key])
+			.ok_or_else(|| match select_keys(doc, KeySelector::Id(id)) {
+				Ok(_) => "verification method not authorized for relationship",
+				Err(err) => err,
+			}),
+	}
+}
+
+// Raw public key material decoded from a verification method.
+enum KeyMaterial {
+	Ed25519(Vec<u8>),
+	Secp256k1(Vec<u8>),
+	P256(Vec<u8>),
+	EthereumAddress(Vec<u8>),
+	Rsa(RsaKey),
+}
+
+#[cfg_attr(not(feature = "rsa"), allow(dead_code))]
+enum RsaKey {
+	Pem(String),
+	Components(Vec<u8>, Vec<u8>),
+}
+
+fn decode_jwk_coordinate(jwk: &JsonValue, prop: &str) -> Result<Vec<u8>, &'static str> {
+	jwk[prop]
+		.as_str()
+		.ok_or("invalid public key encoding")
+		.and_then(base64url_decode)
+}
+
+fn decode_jwk_point(jwk: &JsonValue) -> Result<Vec<u8>, &'static str> {
+	let mut point = vec![SEC1_UNCOMPRESSED_TAG];
+	point.extend(decode_jwk_coordinate(jwk, X_PROP)?);
+	point.extend(decode_jwk_coordinate(jwk, Y_PROP)?);
+	Ok(point)
+}
+
+fn decode_jwk(jwk: &JsonValue) -> Result<KeyMaterial, &'static str> {
+	match (jwk[KTY_PROP].as_str(), jwk[CRV_PROP].as_str()) {
+		(Some(OKP_KTY), Some(ED25519_CRV)) => {
+			Ok(KeyMaterial::Ed25519(decode_jwk_coordinate(jwk, X_PROP)?))
+		}
+		(Some(EC_KTY), Some(SECP256K1_CRV)) => Ok(KeyMaterial::Secp256k1(decode_jwk_point(jwk)?)),
+		(Some(EC_KTY), Some(P256_CRV)) => Ok(KeyMaterial::P256(decode_jwk_point(jwk)?)),
+		(Some(RSA_KTY), _) => Ok(KeyMaterial::Rsa(RsaKey::Components(
+			decode_jwk_coordinate(jwk, N_PROP)?,
+			decode_jwk_coordinate(jwk, E_PROP)?,
+		))),
+		_ => Err("unsupported verification method type"),
+	}
+}
+
+// Multibase keys must be prefixed with the multicodec of their key type.
+fn decode_bytes(encoded: &PublicKeyEncoded, codec: u64) -> Result<Vec<u8>, &'static str> {
+	match encoded {
+		PublicKeyEncoded::Base58(key) => base58_decode(key),
+		PublicKeyEncoded::Base64(key) => base64_decode(key),
+		PublicKeyEncoded::Hex(key) => hex_decode(key),
+		PublicKeyEncoded::Multibase(key) => match multicodec_decode(&multibase_decode(key)?) {
+			Ok((c, key)) if c == codec => Ok(key.to_vec()),
+			_ => Err("public key does not match verification method type"),
+		},
+		_ => Err("unsupported public key encoding"),
+	}
+}
+
+fn decode_ethereum_address(address: &str) -> Result<Vec<u8>, &'static str> {
+	match hex_decode(address) {
+		Ok(bytes) if bytes.len() == ETH_ADDRESS_LEN => Ok(bytes),
+		_ => Err("invalid Ethereum address"),
+	}
+}
+
+fn decode_account_id(account_id: &str) -> Result<Vec<u8>, &'static str> {
+	let account = AccountId::parse(account_id)?;
+	if account.chain_id().namespace() != EIP155_NAMESPACE {
+		return Err("unsupported blockchain account id");
+	}
+	decode_ethereum_address(account.address())
+}
+
+// Multikeys always carry the multicodec of their key type.
+fn decode_multikey(multikey: &str) -> Result<KeyMaterial, &'static str> {
+	let decoded = multibase_decode(multikey)?;
+	match multicodec_decode(&decoded)? {
+		(ED25519_PUB_CODEC, key) => Ok(KeyMaterial::Ed25519(key.to_vec())),
+		(SECP256K1_PUB_CODEC, key) => Ok(KeyMaterial::Secp256k1(key.to_vec())),
+		(P256_PUB_CODEC, key) => Ok(KeyMaterial::P256(key.to_vec())),
+		_ => Err("unsupported multikey codec"),
+	}
+}
+
+fn decode_key(key: &PublicKey) -> Result<KeyMaterial, &'static str> {
+	let material = match key.encoded_key() {
+		PublicKeyEncoded::EthrAddress(address) => {
+			KeyMaterial::EthereumAddress(decode_ethereum_address(address)?)
+		}
+		PublicKeyEncoded::BlockchainAccountId(account_id) => {
+			KeyMaterial::EthereumAddress(decode_account_id(account_id)?)
+		}
+		PublicKeyEncoded::Jwk(jwk) => {
+			decode_jwk(&json::parse(jwk).map_err(|_| "invalid public key encoding")?)?
+		}
+		PublicKeyEncoded::JwkObject(jwk) => decode_jwk(jwk)?,
+		PublicKeyEncoded::Pem(pem) => KeyMaterial::Rsa(RsaKey::Pem(pem.to_string())),
+		PublicKeyEncoded::Multibase(multikey) if key.key_type() == &PublicKeyType::Multikey => {
+			decode_multikey(multikey)?
+		}
+		encoded => match key.key_type() {
+			PublicKeyType::Ed25519 => {
+				KeyMaterial::Ed25519(decode_bytes(encoded, ED25519_PUB_CODEC)?)
+			}
+			PublicKeyType::EcdsaSecp256k1 | PublicKeyType::EcdsaSecp256k1Recovery => {
+				KeyMaterial::Secp256k1(decode_bytes(encoded, SECP256K1_PUB_CODEC)?)
+			}
+			_ => return Err("unsupported verification method type"),
+		},
+	};
+	match (key.key_type(), &material) {
+		(PublicKeyType::Ed25519, KeyMaterial::Ed25519(_))
+		| (PublicKeyType::EcdsaSecp256k1, KeyMaterial::Secp256k1(_))
+		| (PublicKeyType::EcdsaSecp256k1, KeyMaterial::EthereumAddress(_))
+		| (PublicKeyType::EcdsaSecp256k1Recovery, KeyMaterial::Secp256k1(_))
+		| (PublicKeyType::EcdsaSecp256k1Recovery, KeyMaterial::EthereumAddress(_))
+		| (PublicKeyType::Rsa, KeyMaterial::Rsa(_))
+		| (PublicKeyType::JsonWebKey, KeyMaterial::Rsa(_))
+		| (PublicKeyType::JsonWebKey, KeyMaterial::Ed25519(_))
+		| (PublicKeyType::JsonWebKey, KeyMaterial::Secp256k1(_))
+		| (PublicKeyType::JsonWebKey, KeyMaterial::P256(_))
+		| (PublicKeyType::Multikey, KeyMaterial::Ed25519(_))
+		| (PublicKeyType::Multikey, KeyMaterial::Secp256k1(_))
+		| (PublicKeyType::Multikey, KeyMaterial::P256(_)) => Ok(material),
+		(PublicKeyType::Ed25519, _)
+		| (PublicKeyType::Rsa, _)
+		| (PublicKeyType::EcdsaSecp256k1, _)
+		| (PublicKeyType::EcdsaSecp256k1Recovery, _) => {
+			Err("public key does not match verification method type")
+		}
+		_ => Err("unsupported verification method type"),
+	}
+}
+
+#[cfg(feature = "ed25519")]
+fn verify_ed25519(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), &'static str> {
+	use crate::lib::std::convert::TryFrom;
+	use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+	let key_bytes =
+		<[u8; ED25519_KEY_LEN]>::try_from(public_key).map_err(|_| "invalid Ed25519 public key")?;
+	let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_| "invalid Ed25519 public key")?;
+	let signature = Signature::try_from(signature).map_err(|_| "invalid Ed25519 signature")?;
+	key.verify(message, &signature)
+		.map_err(|_| "signature verification failed")
+}
+
+#[cfg(not(feature = "ed25519"))]
+fn verify_ed25519(_: &[u8], _: &[u8], _: &[u8]) -> Result<(), &'static str> {
+	Err("Ed25519 support is not enabled")
+}
+
+// ECDSA signatures are accepted in either compact (r || s) or DER form. High-S
+// signatures are deliberately normalized and accepted: JOSE does not require low-S
+// and signers such as WebCrypto do not normalize. Flipping S only yields another
+// signature over the same message, which matters only where signatures are used
+// as identifiers, never here.
+#[cfg(feature = "secp256k1")]
+fn verify_secp256k1(
+	public_key: &[u8],
+	message: &[u8],
+	signature: &[u8],
+) -> Result<(), &'static str> {
+	use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+	let key =
+		VerifyingKey::from_sec1_bytes(public_key).map_err(|_| "invalid secp256k1 public key")?;
+	// The recovery id of a recoverable signature is not needed when the key is known
+	let signature = if signature.len() == ECDSA_RECOVERABLE_SIG_LEN {
+		&signature[..ECDSA_COMPACT_SIG_LEN]
+	} else {
+		signature
+	};
+	let signature = if signature.len() == ECDSA_COMPACT_SIG_LEN {
+		Signature::from_slice(signature)
+	} else {
+		Signature::from_der(signature)
+	}
+	.map_err(|_| "invalid secp256k1 signature")?;
+	let signature = signature.normalize_s().unwrap_or(signature);
+	key.verify(message, &signature)
+		.map_err(|_| "signature verification failed")
+}
+
+#[cfg(not(feature = "secp256k1"))]
+fn verify_secp256k1(_: &[u8], _: &[u8], _: &[u8]) -> Result<(), &'static str> {
+	Err("secp256k1 support is not enabled")
+}
+
+// Recovers the secp256k1 key of a recoverable (r || s || v) signature the way Ethereum
+// wallets sign messages (EIP-191 `personal_sign`), i.e. over the Keccak-256 digest of
+// "\x19Ethereum Signed Message:\n" || decimal length || message, then compares its
+// Keccak-256 derived Ethereum address.
+#[cfg(feature = "secp256k1")]
+fn verify_recoverable(
+	address: &[u8],
+	message: &[u8],
+	signature: &[u8],
+) -> Result<(), &'static str> {
+	use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+	use sha3::{Digest, Keccak256};
+
+	if signature.len() != ECDSA_RECOVERABLE_SIG_LEN {
+		return Err("invalid recoverable secp256k1 signature");
+	}
+	let recovery_id = match signature[ECDSA_COMPACT_SIG_LEN] {
+		v @ 0..=1 => v,
+		v @ 27..=28 => v - 27,
+		_ => return Err("invalid recoverable secp256k1 signature"),
+	};
+	let mut recovery_id =
+		RecoveryId::from_byte(recovery_id).ok_or("invalid recoverable secp256k1 signature")?;
+	let mut signature = Signature::from_slice(&signature[..ECDSA_COMPACT_SIG_LEN])
+		.map_err(|_| "invalid recoverable secp256k1 signature")?;
+	if let Some(normalized) = signature.normalize_s() {
+		signature = normalized;
+		recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+	}
+
+	let digest = Keccak256::new()
+		.chain_update(EIP191_PREFIX)
+		.chain_update(message.len().to_string())
+		.chain_update(message)
+		.finalize();
+	let key = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+		.map_err(|_| "signature verification failed")?;
+	let point = key.to_encoded_point(false);
+	let hash = Keccak256::digest(&point.as_bytes()[1..]);
+	if &hash[hash.len() - ETH_ADDRESS_LEN..] == address {
+		Ok(())
+	} else {
+		Err("signature verification failed")
+	}
+}
+
+#[cfg(not(feature = "secp256k1"))]
+fn verify_recoverable(_: &[u8], _: &[u8], _: &[u8]) -> Result<(), &'static str> {
+	Err("secp256k1 support is not enabled")
+}
+
+#[cfg(feature = "p256")]
+fn verify_p256(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), &'static str> {
+	use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+	let key = VerifyingKey::from_sec1_bytes(public_key).map_err(|_| "invalid P-256 public key")?;
+	let signature = if signature.len() == ECDSA_COMPACT_SIG_LEN {
+		Signature::from_slice(signature)
+	} else {
+		Signature::from_der(signature)
+	}
+	.map_err(|_| "invalid P-256 signature")?;
+	let signature = signature.normalize_s().unwrap_or(signature);
+	key.verify(message, &signature)
+		.map_err(|_| "signature verification failed")
+}
+
+#[cfg(not(feature = "p256"))]
+fn verify_p256(_: &[u8], _: &[u8], _: &[u8]) -> Result<(), &'static str> {
+	Err("P-256 support is not enabled")
+}
+
+#[cfg(feature = "rsa")]
+fn verify_rsa(
+	public_key: &RsaKey,
+	algorithm: SignatureAlgorithm,
+	message: &[u8],
+	signature: &[u8],
+) -> Result<(), &'static str> {
+	use crate::lib::std::convert::TryFrom;
+	use rsa::pkcs1::DecodeRsaPublicKey;
+	use rsa::pkcs8::DecodePublicKey;
+	use rsa::signature::Verifier;
+	use rsa::traits::PublicKeyParts;
+	use rsa::{pkcs1v15, pss, BigUint, RsaPublicKey};
+	use sha2::Sha256;
+
+	let key = match public_key {
+		RsaKey::Pem(pem) if pem.contains(PKCS1_PEM_LABEL) => RsaPublicKey::from_pkcs1_pem(pem).ok(),
+		RsaKey::Pem(pem) => RsaPublicKey::from_public_key_pem(pem).ok(),
+		RsaKey::Components(n, e) => {
+			RsaPublicKey::new(BigUint::from_bytes_be(n), BigUint::from_bytes_be(e)).ok()
+		}
+	}
+	.ok_or("invalid RSA public key")?;
+	if key.n().bits() < RSA_MIN_MODULUS_BITS {
+		return Err("RSA public key is too small");
+	}
+	let result = match algorithm {
+		SignatureAlgorithm::Ps256 => {
+			let signature =
+				pss::Signature::try_from(signature).map_err(|_| "invalid RSA signature")?;
+			pss::VerifyingKey::<Sha256>::new(key).verify(message, &signature)
+		}
+		_ => {
+			let signature =
+				pkcs1v15::Signature::try_from(signature).map_err(|_| "invalid RSA signature")?;
+			pkcs1v15::VerifyingKey::<Sha256>::new(key).verify(message, &signature)
+		}
+	};
+	result.map_err(|_| "signature verification failed")
+}
+
+#[cfg(not(feature = "rsa"))]
+fn verify_rsa(_: &RsaKey, _: SignatureAlgorithm, _: &[u8], _: &[u8]) -> Result<(), &'static str> {
+	Err("RSA support is not enabled")
+}
+
+impl KeyMaterial {
+	fn default_algorithm(&self) -> SignatureAlgorithm {
+		match self {
+			KeyMaterial::Ed25519(_) => SignatureAlgorithm::EdDsa,
+			KeyMaterial::Secp256k1(_) => SignatureAlgorithm::Es256K,
+			KeyMaterial::P256(_) => SignatureAlgorithm::Es256,
+			KeyMaterial::EthereumAddress(_) => SignatureAlgorithm::Es256KR,
+			KeyMaterial::Rsa(_) => SignatureAlgorithm::Rs256,
+		}
+	}
+
+	fn supports(&self, algorithm: SignatureAlgorithm) -> bool {
+		match self {
+			KeyMaterial::Secp256k1(_) => {
+				algorithm == SignatureAlgorithm::Es256K || algorithm == SignatureAlgorithm::Es256KR
+			}
+			KeyMaterial::Rsa(_) => {
+				algorithm == SignatureAlgorithm::Rs256 || algorithm == SignatureAlgorithm::Ps256
+			}
+			_ => algorithm == self.default_algorithm(),
+		}
+	}
+}
+
+pub(crate) fn verify_with_key(
+	key: &PublicKey,
+	algorithm: Option<SignatureAlgorithm>,
+	message: &[u8],
+	signature: &[u8],
+) -> Result<(), &'static str> {
+	let material = decode_key(key)?;
+	let algorithm = algorithm.unwrap_or_else(|| material.default_algorithm());
+	if !material.supports(algorithm) {
+		return Err("signature algorithm does not match verification method");
+	}
+	match material {
+		KeyMaterial::Ed25519(public_key) => verify_ed25519(&public_key, message, signature),
+		KeyMaterial::Secp256k1(public_key) => verify_secp256k1(&public_key, message, signature),
+		KeyMaterial::P256(public_key) => verify_p256(&public_key, message, signature),
+		KeyMaterial::EthereumAddress(address) => verify_recoverable(&address, message, signature),
+		KeyMaterial::Rsa(public_key) => verify_rsa(&public_key, algorithm, message, signature),
+	}
+}
+
+pub(crate) fn verify(
+	doc: &DidDocument,
+	selector: KeySelector,
+	algorithm: Option<SignatureAlgorithm>,
+	message: &[u8],
+	signature: &[u8],
+) -> Result<(), &'static str> {
+	let mut result = Err("signature verification failed");
+	for key in select_keys(doc, selector)? {
+		result = verify_with_key(key, algorithm, message, signature);
+		if result.is_ok() {
+			break;
+		}
+	}
+	result
+}
@@ -0,0 +1,157 @@
+use did_common::did_doc::DidDocument;
+use did_common::jcs::{canonicalize, canonicalize_to_vec};
+use did_common::json_parse;
+use json::JsonValue;
+
+fn canonicalize_number(bits: u64) -> Result<String, &'static str> {
+	canonicalize(&JsonValue::from(f64::from_bits(bits)))
+}
+
+#[test]
+fn canonicalize_rfc8785_example() {
+	let json = json_parse(
+		r#"
+		{
+			"numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+			"string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+			"literals": [null, true, false]
+		}
+		"#,
+	)
+	.unwrap();
+	assert_eq!(
+		canonicalize(&json),
+		Ok(concat!(
+			r#"{"literals":[null,true,false],"#,
+			r#""numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"#,
+			r#""string":"€$\u000f\nA'B\"\\\\\"/"}"#
+		)
+		.to_string())
+	);
+}
+
+#[test]
+fn canonicalize_sorts_properties_by_utf16_code_units() {
+	let json = json_parse(
+		r#"
+		{
+			"\u20ac": "Euro Sign",
+			"\r": "Carriage Return",
+			"\ufb33": "Hebrew Letter Dalet With Dagesh",
+			"1": "One",
+			"\ud83d\ude00": "Emoji: Grinning Face",
+			"\u0080": "Control",
+			"\u00f6": "Latin Small Letter O With Diaeresis"
+		}
+		"#,
+	)
+	.unwrap();
+	assert_eq!(
+		canonicalize(&json),
+		Ok(concat!(
+			"{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",",
+			"\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",\"\u{20ac}\":\"Euro Sign\",",
+			"\"\u{1f600}\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+		)
+		.to_string())
+	);
+}
+
+#[test]
+fn canonicalize_rfc8785_numbers() {
+	let vectors = [
+		(0x0000000000000000, "0"),
+		(0x8000000000000000, "0"),
+		(0x0000000000000001, "5e-324"),
+		(0x8000000000000001, "-5e-324"),
+		(0x7fefffffffffffff, "1.7976931348623157e+308"),
+		(0xffefffffffffffff, "-1.7976931348623157e+308"),
+		(0x4340000000000000, "9007199254740992"),
+		(0xc340000000000000, "-9007199254740992"),
+		(0x4430000000000000, "295147905179352830000"),
+		(0x44b52d02c7e14af5, "9.999999999999997e+22"),
+		(0x44b52d02c7e14af6, "1e+23"),
+		(0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+		(0x444b1ae4d6e2ef4e, "999999999999999700000"),
+		(0x444b1ae4d6e2ef4f, "999999999999999900000"),
+		(0x444b1ae4d6e2ef50, "1e+21"),
+		(0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+		(0x3eb0c6f7a0b5ed8d, "0.000001"),
+		(0x41b3de4355555553, "333333333.3333332"),
+		(0x41b3de4355555554, "333333333.33333325"),
+		(0x41b3de4355555555, "333333333.3333333"),
+		(0x41b3de4355555556, "333333333.3333334"),
+		(0x41b3de4355555557, "333333333.33333343"),
+		(0xbecbf647612f3696, "-0.0000033333333333333333"),
+		(0x43143ff3c1cb0959, "1424953923781206.2"),
+	];
+	for (bits, expected) in vectors.iter() {
+		assert_eq!(canonicalize_number(*bits), Ok(expected.to_string()));
+	}
+}
+
+#[test]
+fn canonicalize_non_finite_numbers_fails() {
+	assert_eq!(
+		canonicalize_number(0x7fffffffffffffff),
+		Err("JSON number is not finite")
+	);
+	assert_eq!(
+		canonicalize_number(0x7ff0000000000000),
+		Err("JSON number is not finite")
+	);
+}
+
+#[test]
+fn canonicalize_escapes_control_characters() {
+	let json = JsonValue::from("\u{1}\u{8}\t\u{1f}\u{7f}");
+	assert_eq!(
+		canonicalize_to_vec(&json),
+		Ok(b"\"\\u0001\\b\\t\\u001f\x7f\"".to_vec())
+	);
+}
+
+#[test]
+fn canonicalize_did_document() {
+	let json = json_parse(
+		r#"
+		{
+			"service": [
+				{
+					"serviceEndpoint": "https://example.com/endpoint",
+					"type": "LinkedDomains",
+					"id": "did:example:123#svc-1"
+				}
+			],
+			"authentication": ["did:example:123#key-1"],
+			"publicKey": [
+				{
+					"publicKeyBase58": "FAe4sisG95oZ42w7buUn5qEE4TAnfTTFPiguZUHmhiF",
+					"controller": "did:example:123",
+					"type": "Ed25519VerificationKey2018",
+					"id": "did:example:123#key-1"
+				}
+			],
+			"id": "did:example:123",
+			"@context": "https://www.w3.org/2019/did/v1"
+		}
+		"#,
+	)
+	.unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.to_canonical_json(),
+		Ok(concat!(
+			r#"{"@context":"https://www.w3.org/2019/did/v1","#,
+			r#""authentication":["did:example:123#key-1"],"#,
+			r#""id":"did:example:123","#,
+			r#""publicKey":[{"controller":"did:example:123","id":"did:example:123#key-1","#,
+			r#""publicKeyBase58":"FAe4sisG95oZ42w7buUn5qEE4TAnfTTFPiguZUHmhiF","#,
+			r#""type":"Ed25519VerificationKey2018"}],"#,
+			r#""service":[{"id":"did:example:123#svc-1","#,
+			r#""serviceEndpoint":"https://example.com/endpoint","type":"LinkedDomains"}]}"#
+		)
+		.to_string())
+	);
+	assert_eq!(did_doc.to_canonical_json(), canonicalize(&json));
+}
@@ -0,0 +1,187 @@
+use did_common::data_integrity::RdfCanonicalizer;
+use did_common::json_parse;
+use did_common::jsonld::JsonLdCanonicalizer;
+
+fn canonicalize(document: &str) -> Result<String, &'static str> {
+	JsonLdCanonicalizer::new().canonicalize(&json_parse(document).unwrap())
+}
+
+#[test]
+fn canonicalize_did_document() {
+	let document = r#"
+	{
+		"@context": "https://www.w3.org/2019/did/v1",
+		"id": "did:example:123",
+		"publicKey": [
+			{
+				"id": "did:example:123#key-1",
+				"type": "Ed25519VerificationKey2018",
+				"controller": "did:example:123",
+				"publicKeyBase58": "FAe4sisG95oZ42w7buUn5qEE4TAnfTTFPiguZUHmhiF"
+			}
+		],
+		"authentication": ["did:example:123#key-1"]
+	}
+	"#;
+	assert_eq!(
+		canonicalize(document),
+		Ok(concat!(
+			"<did:example:123#key-1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#Ed25519VerificationKey2018> .\n",
+			"<did:example:123#key-1> <https://w3id.org/security#controller> <did:example:123> .\n",
+			"<did:example:123#key-1> <https://w3id.org/security#publicKeyBase58> \"FAe4sisG95oZ42w7buUn5qEE4TAnfTTFPiguZUHmhiF\" .\n",
+			"<did:example:123> <https://w3id.org/security#authenticationMethod> <did:example:123#key-1> .\n",
+			"<did:example:123> <https://w3id.org/security#publicKey> <did:example:123#key-1> .\n",
+		)
+		.to_string())
+	);
+}
+
+#[test]
+fn canonicalize_with_type_scoped_context() {
+	let document = r#"
+	{
+		"@context": ["https://www.w3.org/ns/did/v1", "https://w3id.org/security/suites/ed25519-2018/v1"],
+		"type": "Ed25519Signature2018",
+		"created": "2020-06-01T12:00:00Z",
+		"verificationMethod": "did:example:123#key-1",
+		"proofPurpose": "assertionMethod"
+	}
+	"#;
+	assert_eq!(
+		canonicalize(document),
+		Ok(concat!(
+			"_:c14n0 <http://purl.org/dc/terms/created> \"2020-06-01T12:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n",
+			"_:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#Ed25519Signature2018> .\n",
+			"_:c14n0 <https://w3id.org/security#proofPurpose> <https://w3id.org/security#assertionMethod> .\n",
+			"_:c14n0 <https://w3id.org/security#verificationMethod> <did:example:123#key-1> .\n",
+		)
+		.to_string())
+	);
+}
+
+#[test]
+fn canonicalize_literals_and_lists() {
+	let document = r#"
+	{
+		"@context": [
+			"https://www.w3.org/2018/credentials/v1",
+			{
+				"@language": "en",
+				"name": "https://schema.org/name",
+				"age": {"@id": "https://schema.org/age", "@type": "http://www.w3.org/2001/XMLSchema#integer"},
+				"tags": {"@id": "https://schema.org/tag", "@container": "@list"}
+			}
+		],
+		"id": "urn:uuid:1",
+		"type": ["VerifiableCredential"],
+		"issuer": "did:example:123",
+		"issuanceDate": "2021-01-01T00:00:00Z",
+		"credentialSubject": {
+			"name": "Alice \"A\"\n",
+			"age": 30,
+			"tags": ["a", 1.5, true, {"name": "x"}],
+			"https://schema.org/knows": {"name": "Bob"}
+		}
+	}
+	"#;
+	assert_eq!(
+		canonicalize(document),
+		Ok(concat!(
+			"<urn:uuid:1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/2018/credentials#VerifiableCredential> .\n",
+			"<urn:uuid:1> <https://www.w3.org/2018/credentials#credentialSubject> _:c14n6 .\n",
+			"<urn:uuid:1> <https://www.w3.org/2018/credentials#issuanceDate> \"2021-01-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n",
+			"<urn:uuid:1> <https://www.w3.org/2018/credentials#issuer> <did:example:123> .\n",
+			"_:c14n0 <https://schema.org/name> \"x\"@en .\n",
+			"_:c14n1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> _:c14n0 .\n",
+			"_:c14n1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n",
+			"_:c14n2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"a\"@en .\n",
+			"_:c14n2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:c14n3 .\n",
+			"_:c14n3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"1.5E0\"^^<http://www.w3.org/2001/XMLSchema#double> .\n",
+			"_:c14n3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:c14n5 .\n",
+			"_:c14n4 <https://schema.org/name> \"Bob\"@en .\n",
+			"_:c14n5 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .\n",
+			"_:c14n5 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:c14n1 .\n",
+			"_:c14n6 <https://schema.org/age> \"30\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n",
+			"_:c14n6 <https://schema.org/knows> _:c14n4 .\n",
+			"_:c14n6 <https://schema.org/name> \"Alice \\\"A\\\"\\n\"@en .\n",
+			"_:c14n6 <https://schema.org/tag> _:c14n2 .\n",
+		)
+		.to_string())
+	);
+}
+
+#[test]
+fn canonicalize_isomorphic_blank_nodes() {
+	let cycles = r#"
+	{
+		"@graph": [
+			{"@id": "_:a", "http://example.com/p": {"@id": "_:b"}},
+			{"@id": "_:b", "http://example.com/p": {"@id": "_:c"}},
+			{"@id": "_:c", "http://example.com/p": {"@id": "_:a"}},
+			{"@id": "_:d", "http://example.com/p": {"@id": "_:e"}},
+			{"@id": "_:e", "http://example.com/p": {"@id": "_:f"}},
+			{"@id": "_:f", "http://example.com/p": [{"@id": "_:d"}, {"@id": "_:a"}]}
+		]
+	}
+	"#;
+	let relabeled = r#"
+	{
+		"@graph": [
+			{"@id": "_:x3", "http://example.com/p": [{"@id": "_:x1"}, {"@id": "_:x6"}]},
+			{"@id": "_:x5", "http://example.com/p": {"@id": "_:x3"}},
+			{"@id": "_:x2", "http://example.com/p": {"@id": "_:x4"}},
+			{"@id": "_:x4", "http://example.com/p": {"@id": "_:x6"}},
+			{"@id": "_:x1", "http://example.com/p": {"@id": "_:x5"}},
+			{"@id": "_:x6", "http://example.com/p": {"@id": "_:x2"}}
+		]
+	}
+	"#;
+	let expected = concat!(
+		"_:c14n0 <http://example.com/p> _:c14n1 .\n",
+		"_:c14n0 <http://example.com/p> _:c14n3 .\n",
+		"_:c14n1 <http://example.com/p> _:c14n4 .\n",
+		"_:c14n2 <http://example.com/p> _:c14n0 .\n",
+		"_:c14n3 <http://example.com/p> _:c14n2 .\n",
+		"_:c14n4 <http://example.com/p> _:c14n5 .\n",
+		"_:c14n5 <http://example.com/p> _:c14n1 .\n",
+	);
+	assert_eq!(canonicalize(cycles), Ok(expected.to_string()));
+	assert_eq!(canonicalize(relabeled), Ok(expected.to_string()));
+}
+
+#[test]
+fn canonicalize_with_added_context() {
+	let canonicalizer = JsonLdCanonicalizer::new().with_context(
+		"https://example.com/context",
+		json_parse(r#"{"@context": {"@vocab": "https://example.com/vocab#"}}"#).unwrap(),
+	);
+	let document = json_parse(
+		r#"{"@context": "https://example.com/context", "@id": "urn:example:1", "name": "Alice"}"#,
+	)
+	.unwrap();
+	assert_eq!(
+		canonicalizer.canonicalize(&document),
+		Ok("<urn:example:1> <https://example.com/vocab#name> \"Alice\" .\n".to_string())
+	);
+}
+
+#[test]
+fn canonicalize_unknown_context_fails() {
+	assert_eq!(
+		canonicalize(r#"{"@context": "https://example.com/context", "name": "Alice"}"#),
+		Err("unknown JSON-LD context")
+	);
+}
+
+#[test]
+fn canonicalize_recursive_context_fails() {
+	let canonicalizer = JsonLdCanonicalizer::new().with_context(
+		"https://example.com/context",
+		json_parse(r#"{"@context": "https://example.com/context"}"#).unwrap(),
+	);
+	let document = json_parse(r#"{"@context": "https://example.com/context"}"#).unwrap();
+	assert_eq!(
+		canonicalizer.canonicalize(&document),
+		Err("recursive JSON-LD context inclusion")
+	);
+}
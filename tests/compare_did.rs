@@ -0,0 +1,65 @@
+use std::collections::{BTreeMap, HashSet};
+
+use did_common::did::{Did, DidBuilder};
+
+#[test]
+fn did_as_collection_key() {
+	let dids: HashSet<Did> = [
+		"did:example:123;service=agent",
+		"did:example:123#keys-1",
+		"did:example:123;service=agent",
+	]
+	.iter()
+	.map(|did| Did::parse(did).unwrap())
+	.collect();
+	assert_eq!(dids.len(), 2);
+
+	let mut keys = BTreeMap::new();
+	keys.insert(Did::parse("did:example:456").unwrap(), 2);
+	keys.insert(Did::parse("did:example:123#keys-1").unwrap(), 1);
+	keys.insert(Did::parse("did:ethr:0x123").unwrap(), 0);
+	assert_eq!(keys.values().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn normalize_did() {
+	assert_eq!(
+		Did::parse("did:web:example.com%3a8443:users%2falice")
+			.unwrap()
+			.normalize(),
+		"did:web:example.com%3A8443:users%2Falice"
+	);
+	assert_eq!(
+		Did::parse("did:example:123;service=;example:foo=%2fbar#")
+			.unwrap()
+			.normalize(),
+		"did:example:123;example:foo=%2Fbar"
+	);
+	assert_eq!(
+		DidBuilder::new("EXAMPLE", "123")
+			.with_params([("service", None)])
+			.with_fragment("keys-1")
+			.build()
+			.normalize(),
+		"did:example:123#keys-1"
+	);
+}
+
+#[test]
+fn compare_equivalent_dids() {
+	let did = Did::parse("did:web:example.com%3A8443").unwrap();
+	let lowercase_pct = Did::parse("did:web:example.com%3a8443;service=").unwrap();
+	assert_ne!(did, lowercase_pct);
+	assert!(did.is_equivalent(&lowercase_pct));
+	assert!(!did.is_equivalent(&Did::parse("did:web:example.com%3A8443#key").unwrap()));
+}
+
+#[test]
+fn compare_did_subjects() {
+	let did = Did::parse("did:web:example.com%3A8443").unwrap();
+	assert!(
+		did.same_subject(&Did::parse("did:web:example.com%3a8443;service=agent#keys-1").unwrap())
+	);
+	assert!(!did.same_subject(&Did::parse("did:web:example.com").unwrap()));
+	assert!(!did.same_subject(&Did::parse("did:example:example.com%3A8443").unwrap()));
+}
@@ -0,0 +1,608 @@
+#![cfg(feature = "ed25519")]
+
+use did_common::data_integrity::{
+	create_proof, verify_proof, verify_proof_resolved, Cryptosuite, ProofOptions, ProofValidation,
+};
+use did_common::did::Did;
+use did_common::did_doc::{DidDocument, VerificationRelationship};
+use did_common::json_parse;
+use did_common::jsonld::JsonLdCanonicalizer;
+use json::JsonValue;
+
+use std::str::FromStr;
+
+const VERIFICATION_METHOD: &str = "did:example:123#key-1";
+const CREATED: &str = "2023-02-24T23:36:38Z";
+const JCS_PROOF_VALUE: &str =
+	"z4CjVyirnq2YzptjbQdsZXViuJogepRLn4T7LA154kiKRpJG7DUtcgLhx1o4jXjQV9VLBLhKtnqFvoDFLtfsSdUSV";
+const DID_DOC_JWS: &str = "eyJhbGciOiJFZERTQSIsImI2NCI6ZmFsc2UsImNyaXQiOlsiYjY0Il19..W1_ZN5AHnZqTyv2ANBbIvqHwSiXItB-V36dQ0fvk9qodxlpNExREa42EUe19Je7gO68-tlDjLT3OQ8_L_b3vBg";
+const CREDENTIAL_JWS: &str = "eyJhbGciOiJFZERTQSIsImI2NCI6ZmFsc2UsImNyaXQiOlsiYjY0Il19..jkEOqhBWqPkW3MRBSXQgA2yG4abp5T3WmTU27DozhYoahBcdvwy1hwkfP1jHdtIQ-k3i32sYDtAgdfYiREaHAQ";
+const RDFC_PROOF_VALUE: &str =
+	"z2q8oQiy17UL6TbZQK3MdqapMQQqtPvCN3mhf57AmZN2k2U6zteGcCHkr4j8jgudTDeRn3i5Zt1NEnVg2MWUcGoXb";
+
+// Test vectors of the vc-di-eddsa specification, see:
+// https://www.w3.org/TR/vc-di-eddsa/#test-vectors
+const SPEC_VERIFICATION_METHOD: &str = "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2";
+const SPEC_JCS_PROOF_VALUE: &str =
+	"z2HnFSSPPBzR36zdDgK8PbEHeXbR56YF24jwMpt3R1eHXQzJDMWS93FCzpvJpwTWd3GAVFuUfjoJdcnTMuVor51aX";
+const SPEC_RDFC_PROOF_VALUE: &str =
+	"z2YwC8z3ap7yx1nZYCg4L3j3ApHsF8kgPdSb5xoS1VR7vPG3F561B52hYnQF9iseabecm3ijx4K1FBTQsCZahKZme";
+const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+const EXAMPLES_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/examples/v2";
+
+fn secret_key() -> Vec<u8> {
+	(0..32).collect()
+}
+
+fn did_doc_json() -> JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{
+					"id": "did:example:123#key-1",
+					"type": "Multikey",
+					"controller": "did:example:123",
+					"publicKeyMultibase": "z6MkehRgf7yJbgaGfYsdoAsKdBPE3dj2CYhowQdcjqSJgvVd"
+				}
+			],
+			"assertionMethod": ["did:example:123#key-1"]
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+fn credential() -> JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": ["https://www.w3.org/ns/credentials/v2"],
+			"id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+			"type": ["VerifiableCredential"],
+			"issuer": "did:example:123",
+			"credentialSubject": {
+				"id": "did:example:456",
+				"name": "Alice"
+			}
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+// Secret key of the specification's z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq key pair
+fn spec_secret_key() -> Vec<u8> {
+	vec![
+		0xc9, 0x6e, 0xf9, 0xea, 0x10, 0xc5, 0xe4, 0x14, 0xc4, 0x71, 0x72, 0x3a, 0xff, 0x9d, 0xe7,
+		0x2c, 0x35, 0xfa, 0x5b, 0x70, 0xfa, 0xe9, 0x7e, 0x88, 0x32, 0xec, 0xac, 0x7d, 0x2e, 0x2b,
+		0x8e, 0xd6,
+	]
+}
+
+fn spec_did_doc_json() -> JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+			"publicKey": [
+				{
+					"id": "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+					"type": "Multikey",
+					"controller": "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+					"publicKeyMultibase": "z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"
+				}
+			],
+			"assertionMethod": [
+				"did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2"
+			]
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+fn spec_credential() -> JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": [
+				"https://www.w3.org/ns/credentials/v2",
+				"https://www.w3.org/ns/credentials/examples/v2"
+			],
+			"id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+			"type": ["VerifiableCredential", "AlumniCredential"],
+			"name": "Alumni Credential",
+			"description": "A minimum viable example of an Alumni Credential.",
+			"issuer": "https://vc.example/issuers/5678",
+			"validFrom": "2023-01-01T00:00:00Z",
+			"credentialSubject": {
+				"id": "did:example:abcdefgh",
+				"alumniOf": "The School of Examples"
+			}
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+fn spec_secured_credential(cryptosuite: Cryptosuite, proof_value: &str) -> JsonValue {
+	let mut secured = spec_credential();
+	let mut proof = JsonValue::new_object();
+	proof["type"] = "DataIntegrityProof".into();
+	proof["cryptosuite"] = cryptosuite.as_str().into();
+	proof["created"] = CREATED.into();
+	proof["verificationMethod"] = SPEC_VERIFICATION_METHOD.into();
+	proof["proofPurpose"] = "assertionMethod".into();
+	if cryptosuite == Cryptosuite::EddsaJcs2022 {
+		proof["@context"] = secured["@context"].clone();
+	}
+	proof["proofValue"] = proof_value.into();
+	secured["proof"] = proof;
+	secured
+}
+
+// The credentials v2 and examples v2 contexts are not bundled: these reduced copies
+// map the terms used by the test vectors to the same IRIs.
+fn spec_canonicalizer() -> JsonLdCanonicalizer {
+	JsonLdCanonicalizer::new()
+		.with_context(
+			CREDENTIALS_V2_CONTEXT,
+			json_parse(
+				r#"
+				{
+					"@context": {
+						"@version": 1.1,
+						"@protected": true,
+						"id": "@id",
+						"type": "@type",
+						"description": "https://schema.org/description",
+						"name": "https://schema.org/name",
+						"VerifiableCredential": "https://www.w3.org/2018/credentials#VerifiableCredential",
+						"credentialSubject": {"@id": "https://www.w3.org/2018/credentials#credentialSubject", "@type": "@id"},
+						"issuer": {"@id": "https://www.w3.org/2018/credentials#issuer", "@type": "@id"},
+						"validFrom": {"@id": "https://www.w3.org/2018/credentials#validFrom", "@type": "http://www.w3.org/2001/XMLSchema#dateTime"},
+						"DataIntegrityProof": {
+							"@id": "https://w3id.org/security#DataIntegrityProof",
+							"@context": {
+								"@protected": true,
+								"id": "@id",
+								"type": "@type",
+								"created": {"@id": "http://purl.org/dc/terms/created", "@type": "http://www.w3.org/2001/XMLSchema#dateTime"},
+								"cryptosuite": {"@id": "https://w3id.org/security#cryptosuite", "@type": "https://w3id.org/security#cryptosuiteString"},
+								"proofPurpose": {
+									"@id": "https://w3id.org/security#proofPurpose",
+									"@type": "@vocab",
+									"@context": {
+										"@protected": true,
+										"assertionMethod": {"@id": "https://w3id.org/security#assertionMethod", "@type": "@id", "@container": "@set"}
+									}
+								},
+								"proofValue": {"@id": "https://w3id.org/security#proofValue", "@type": "https://w3id.org/security#multibase"},
+								"verificationMethod": {"@id": "https://w3id.org/security#verificationMethod", "@type": "@id"}
+							}
+						}
+					}
+				}
+				"#,
+			)
+			.unwrap(),
+		)
+		.with_context(
+			EXAMPLES_V2_CONTEXT,
+			json_parse(r#"{"@context": {"@vocab": "https://www.w3.org/ns/credentials/examples#"}}"#)
+				.unwrap(),
+		)
+}
+
+fn legacy_did_doc_json() -> JsonValue {
+	json_parse(&format!(
+		r#"
+		{{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{{
+					"id": "did:example:123#key-1",
+					"type": "Ed25519VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyBase58": "FAe4sisG95oZ42w7buUn5qEE4TAnfTTFPiguZUHmhiF"
+				}}
+			],
+			"authentication": ["did:example:123#key-1"],
+			"assertionMethod": ["did:example:123#key-1"],
+			"proof": {{
+				"type": "Ed25519Signature2018",
+				"created": "2020-06-01T12:00:00Z",
+				"verificationMethod": "did:example:123#key-1",
+				"proofPurpose": "authentication",
+				"jws": "{}"
+			}}
+		}}
+		"#,
+		DID_DOC_JWS
+	))
+	.unwrap()
+}
+
+fn legacy_credential() -> JsonValue {
+	json_parse(&format!(
+		r#"
+		{{
+			"@context": ["https://www.w3.org/2018/credentials/v1", {{"name": "https://schema.org/name"}}],
+			"id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+			"type": ["VerifiableCredential"],
+			"issuer": "did:example:123",
+			"issuanceDate": "2020-06-01T12:00:00Z",
+			"credentialSubject": {{
+				"id": "did:example:456",
+				"name": "Alice"
+			}},
+			"proof": {{
+				"type": "Ed25519Signature2018",
+				"created": "2020-06-01T12:00:00Z",
+				"verificationMethod": "did:example:123#key-1",
+				"proofPurpose": "assertionMethod",
+				"jws": "{}"
+			}}
+		}}
+		"#,
+		CREDENTIAL_JWS
+	))
+	.unwrap()
+}
+
+fn jcs_options() -> ProofOptions<'static> {
+	ProofOptions::new(
+		Cryptosuite::EddsaJcs2022,
+		VERIFICATION_METHOD,
+		VerificationRelationship::AssertionMethod,
+	)
+	.created_on(CREATED)
+}
+
+// Stands in for an RDF canonicalizer so that the eddsa-rdfc-2022 steps can be exercised offline.
+fn fake_canonicalizer(document: &JsonValue) -> Result<String, &'static str> {
+	Ok(document.dump())
+}
+
+#[test]
+fn cryptosuite_fromstr_and_display() {
+	assert_eq!(
+		Cryptosuite::from_str("eddsa-jcs-2022"),
+		Ok(Cryptosuite::EddsaJcs2022)
+	);
+	assert_eq!(
+		Cryptosuite::from_str("eddsa-rdfc-2022"),
+		Ok(Cryptosuite::EddsaRdfc2022)
+	);
+	assert!(Cryptosuite::from_str("ecdsa-jcs-2019").is_err());
+	assert_eq!(Cryptosuite::EddsaJcs2022.to_string(), "eddsa-jcs-2022");
+}
+
+#[test]
+fn create_eddsa_jcs_2022_proof() {
+	let secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	let proof = &secured["proof"];
+	assert_eq!(proof["type"], "DataIntegrityProof");
+	assert_eq!(proof["cryptosuite"], "eddsa-jcs-2022");
+	assert_eq!(proof["created"], CREATED);
+	assert_eq!(proof["verificationMethod"], VERIFICATION_METHOD);
+	assert_eq!(proof["proofPurpose"], "assertionMethod");
+	assert_eq!(proof["@context"], credential()["@context"]);
+	assert_eq!(proof["proofValue"], JCS_PROOF_VALUE);
+	assert_eq!(
+		secured["credentialSubject"],
+		credential()["credentialSubject"]
+	);
+}
+
+#[test]
+fn verify_eddsa_jcs_2022_proof() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Ok(())
+	);
+	assert_eq!(
+		verify_proof(
+			&secured,
+			&did_doc,
+			&ProofValidation::new().with_purpose(VerificationRelationship::AssertionMethod)
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_tampered_document_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	secured["credentialSubject"]["name"] = "Mallory".into();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_tampered_proof_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	secured["proof"]["created"] = "2024-01-01T00:00:00Z".into();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_proof_purpose_not_authorized_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let options = ProofOptions::new(
+		Cryptosuite::EddsaJcs2022,
+		VERIFICATION_METHOD,
+		VerificationRelationship::Authentication,
+	);
+	let secured = create_proof(&credential(), &options, &secret_key()).unwrap();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("verification method not authorized for relationship")
+	);
+}
+
+#[test]
+fn verify_unexpected_proof_purpose_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	assert_eq!(
+		verify_proof(
+			&secured,
+			&did_doc,
+			&ProofValidation::new().with_purpose(VerificationRelationship::Authentication)
+		),
+		Err("proof purpose does not match")
+	);
+}
+
+#[test]
+fn verify_foreign_verification_method_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let options = ProofOptions::new(
+		Cryptosuite::EddsaJcs2022,
+		"did:example:456#key-1",
+		VerificationRelationship::AssertionMethod,
+	);
+	let secured = create_proof(&credential(), &options, &secret_key()).unwrap();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("proof verificationMethod does not match DID document")
+	);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn verify_invalid_created_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	secured["proof"]["created"] = "yesterday".into();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("invalid proof created timestamp")
+	);
+	assert_eq!(
+		create_proof(
+			&credential(),
+			&jcs_options().created_on("yesterday"),
+			&secret_key()
+		),
+		Err("invalid proof created timestamp")
+	);
+}
+
+#[test]
+fn verify_mismatched_context_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	secured["@context"] = "https://example.com/context".into();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("proof @context does not match document")
+	);
+}
+
+#[test]
+fn verify_missing_proof_fails() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		verify_proof(&credential(), &did_doc, &ProofValidation::new()),
+		Err("missing Data Integrity proof")
+	);
+}
+
+#[test]
+fn verify_proof_set() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	let options = jcs_options().created_on("2024-01-01T00:00:00Z");
+	let secured = create_proof(&secured, &options, &secret_key()).unwrap();
+	assert_eq!(secured["proof"].len(), 2);
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Ok(())
+	);
+}
+
+#[test]
+fn create_and_verify_eddsa_rdfc_2022_proof() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let options = ProofOptions::new(
+		Cryptosuite::EddsaRdfc2022,
+		VERIFICATION_METHOD,
+		VerificationRelationship::AssertionMethod,
+	)
+	.created_on(CREATED)
+	.with_canonicalizer(&fake_canonicalizer);
+	let secured = create_proof(&credential(), &options, &secret_key()).unwrap();
+	assert!(secured["proof"]["@context"].is_null());
+	assert_eq!(secured["proof"]["proofValue"], RDFC_PROOF_VALUE);
+	assert_eq!(
+		verify_proof(
+			&secured,
+			&did_doc,
+			&ProofValidation::new().with_canonicalizer(&fake_canonicalizer)
+		),
+		Ok(())
+	);
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Err("unknown JSON-LD context")
+	);
+}
+
+#[test]
+fn verify_self_signed_did_document() {
+	let options = ProofOptions::new(
+		Cryptosuite::EddsaJcs2022,
+		VERIFICATION_METHOD,
+		VerificationRelationship::AssertionMethod,
+	);
+	let secured = create_proof(&did_doc_json(), &options, &secret_key()).unwrap();
+	let did_doc = DidDocument::parse(&secured).unwrap();
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_proof_with_resolver() {
+	let resolver = |did: &Did| {
+		if did.method_specific_id() == "123" {
+			Ok(did_doc_json())
+		} else {
+			Err("DID not found")
+		}
+	};
+	let secured = create_proof(&credential(), &jcs_options(), &secret_key()).unwrap();
+	assert_eq!(
+		verify_proof_resolved(&secured, &resolver, &ProofValidation::new()),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_ed25519_signature_2018_did_document() {
+	let json = legacy_did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		verify_proof(
+			&json,
+			&did_doc,
+			&ProofValidation::new().with_purpose(VerificationRelationship::Authentication)
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_ed25519_signature_2018_credential() {
+	let json = legacy_did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		verify_proof(&legacy_credential(), &did_doc, &ProofValidation::new()),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_tampered_ed25519_signature_2018_fails() {
+	let json = legacy_did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut credential = legacy_credential();
+	credential["credentialSubject"]["name"] = "Mallory".into();
+	assert_eq!(
+		verify_proof(&credential, &did_doc, &ProofValidation::new()),
+		Err("signature verification failed")
+	);
+	let mut credential = legacy_credential();
+	credential["proof"]["created"] = "2024-01-01T00:00:00Z".into();
+	assert_eq!(
+		verify_proof(&credential, &did_doc, &ProofValidation::new()),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_ed25519_signature_2018_encoded_payload_fails() {
+	let json = legacy_did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut credential = legacy_credential();
+	// {"alg":"EdDSA"} signs the encoded payload, which legacy proofs never do
+	credential["proof"]["jws"] = "eyJhbGciOiJFZERTQSJ9..c2ln".into();
+	assert_eq!(
+		verify_proof(&credential, &did_doc, &ProofValidation::new()),
+		Err("invalid proof jws")
+	);
+}
+
+#[test]
+fn eddsa_jcs_2022_test_vector() {
+	let options = ProofOptions::new(
+		Cryptosuite::EddsaJcs2022,
+		SPEC_VERIFICATION_METHOD,
+		VerificationRelationship::AssertionMethod,
+	)
+	.created_on(CREATED);
+	let secured = create_proof(&spec_credential(), &options, &spec_secret_key()).unwrap();
+	assert_eq!(secured["proof"]["proofValue"], SPEC_JCS_PROOF_VALUE);
+
+	let json = spec_did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let secured = spec_secured_credential(Cryptosuite::EddsaJcs2022, SPEC_JCS_PROOF_VALUE);
+	assert_eq!(
+		verify_proof(&secured, &did_doc, &ProofValidation::new()),
+		Ok(())
+	);
+}
+
+#[test]
+fn eddsa_rdfc_2022_test_vector() {
+	let canonicalizer = spec_canonicalizer();
+	let options = ProofOptions::new(
+		Cryptosuite::EddsaRdfc2022,
+		SPEC_VERIFICATION_METHOD,
+		VerificationRelationship::AssertionMethod,
+	)
+	.created_on(CREATED)
+	.with_canonicalizer(&canonicalizer);
+	let secured = create_proof(&spec_credential(), &options, &spec_secret_key()).unwrap();
+	assert_eq!(secured["proof"]["proofValue"], SPEC_RDFC_PROOF_VALUE);
+
+	let json = spec_did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let secured = spec_secured_credential(Cryptosuite::EddsaRdfc2022, SPEC_RDFC_PROOF_VALUE);
+	assert_eq!(
+		verify_proof(
+			&secured,
+			&did_doc,
+			&ProofValidation::new().with_canonicalizer(&canonicalizer)
+		),
+		Ok(())
+	);
+}
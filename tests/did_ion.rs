@@ -0,0 +1,123 @@
+use did_common::did::Did;
+use did_common::did_doc::{
+	DidDocumentBuilder, PublicKeyBuilder, PublicKeyEncoded, PublicKeyType, Service,
+	ServiceEndpoint, VerificationMethod,
+};
+use did_common::json_parse;
+use did_common::method::ion::resolve;
+
+const SUFFIX: &str = "EiAy7sKBDwafvCUVLFLehnFcRWl0puxjwyP1zpPXHCSTXQ";
+const INITIAL_STATE: &str = "eyJkZWx0YSI6eyJwYXRjaGVzIjpbeyJhY3Rpb24iOiJyZXBsYWNlIiwiZG9jdW1lbnQiOnsicHVibGljS2V5cyI6W3siaWQiOiJrZXktMSIsInB1YmxpY0tleUp3ayI6eyJjcnYiOiJzZWNwMjU2azEiLCJrdHkiOiJFQyIsIngiOiI1czMtYktqRDFFdV8zTkp1OHBrN3FJZE9QbDFHQnpVX1Y4YVIzeGlhY29NIiwieSI6InYwLVE1SDN2Y2ZBZlE0enNlYkpRdk1ySWczcGNzYUp6UnZ1SVlaM19VT1kifSwicHVycG9zZXMiOlsiYXV0aGVudGljYXRpb24iLCJhc3NlcnRpb25NZXRob2QiXSwidHlwZSI6IkVjZHNhU2VjcDI1NmsxVmVyaWZpY2F0aW9uS2V5MjAxOSJ9XSwic2VydmljZXMiOlt7ImlkIjoiZG9tYWluLTEiLCJzZXJ2aWNlRW5kcG9pbnQiOiJodHRwczovL2Zvby5leGFtcGxlLmNvbSIsInR5cGUiOiJMaW5rZWREb21haW5zIn1dfX1dLCJ1cGRhdGVDb21taXRtZW50IjoiRWlES0lrd3FPNjlJUEczcE9sSGtkYjg2bll0MGFOeFNIWnUyci1iaEV6bmpkQSJ9LCJzdWZmaXhEYXRhIjp7ImRlbHRhSGFzaCI6IkVpQXRjb1BVV3YxUGlYS1U0R05GSkFOWDlKLXZ5QldKS0Eyc0NXbVhqSklIYmciLCJyZWNvdmVyeUNvbW1pdG1lbnQiOiJFaUJmT1pkTXRVNk9CdzhQazg3OVF0Wi0ySi05RmJialNaeW9hQV9icUQ0emhBIn19";
+
+#[test]
+fn resolve_did_ion_long_form() {
+	let did = format!("did:ion:{}:{}", SUFFIX, INITIAL_STATE);
+	let (key_id, service_id) = (format!("{}#key-1", did), format!("{}#domain-1", did));
+	let jwk = json_parse(
+		r#"{
+			"kty": "EC",
+			"crv": "secp256k1",
+			"x": "5s3-bKjD1Eu_3NJu8pk7qIdOPl1GBzU_V8aR3xiacoM",
+			"y": "v0-Q5H3vcfAfQ4zsebJQvMrIg3pcsaJzRvuIYZ3_UOY"
+		}"#,
+	)
+	.unwrap();
+	let resolution = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(
+		resolution.did_document(),
+		Ok(DidDocumentBuilder::new(&did)
+			.with_pubkeys(vec![PublicKeyBuilder::new(
+				&key_id,
+				PublicKeyType::EcdsaSecp256k1,
+				&did
+			)
+			.with_encoded_key(PublicKeyEncoded::JwkObject(&jwk))
+			.build()])
+			.with_authentication(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_assertion_method(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_services(vec![Service::new(
+				&service_id,
+				"LinkedDomains",
+				ServiceEndpoint::Uri("https://foo.example.com")
+			)])
+			.build())
+	);
+}
+
+#[test]
+fn resolve_did_ion_long_form_metadata() {
+	let did = format!("did:ion:{}:{}", SUFFIX, INITIAL_STATE);
+	let resolution = resolve(&Did::parse(&did).unwrap()).unwrap();
+	let metadata = resolution.metadata();
+	assert!(!metadata.published());
+	assert_eq!(metadata.equivalent_id(), format!("did:ion:{}", SUFFIX));
+	assert_eq!(
+		metadata.recovery_commitment(),
+		"EiBfOZdMtU6OBw8Pk879QtZ-2J-9FbbjSZyoaA_bqD4zhA"
+	);
+	assert_eq!(
+		metadata.update_commitment(),
+		"EiDKIkwqO69IPG3pOlHkdb86nYt0aNxSHZu2r-bhEznjdA"
+	);
+}
+
+#[test]
+fn resolve_did_ion_long_form_on_test_network() {
+	let did = format!("did:ion:test:{}:{}", SUFFIX, INITIAL_STATE);
+	let resolution = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(resolution.document()["id"], did.as_str());
+	assert_eq!(
+		resolution.metadata().equivalent_id(),
+		format!("did:ion:test:{}", SUFFIX)
+	);
+}
+
+#[test]
+fn resolve_did_ion_with_mismatching_suffix() {
+	let did = format!(
+		"did:ion:EiDQt2FOKCkIfh3W8bKdxVLiTaIKCU9nQfNYYKWuOir8bQ:{}",
+		INITIAL_STATE
+	);
+	assert_eq!(
+		resolve(&Did::parse(&did).unwrap()),
+		Err("did:ion suffix does not match create operation")
+	);
+}
+
+#[test]
+fn resolve_did_ion_with_mismatching_delta() {
+	let did = format!("did:ion:{}:eyJkZWx0YSI6eyJwYXRjaGVzIjpbeyJhY3Rpb24iOiJyZXBsYWNlIiwiZG9jdW1lbnQiOnsicHVibGljS2V5cyI6W3siaWQiOiJrZXktMSIsInB1YmxpY0tleUp3ayI6eyJjcnYiOiJzZWNwMjU2azEiLCJrdHkiOiJFQyIsIngiOiI1czMtYktqRDFFdV8zTkp1OHBrN3FJZE9QbDFHQnpVX1Y4YVIzeGlhY29NIiwieSI6InYwLVE1SDN2Y2ZBZlE0enNlYkpRdk1ySWczcGNzYUp6UnZ1SVlaM19VT1kifSwicHVycG9zZXMiOlsiYXV0aGVudGljYXRpb24iLCJhc3NlcnRpb25NZXRob2QiXSwidHlwZSI6IkVjZHNhU2VjcDI1NmsxVmVyaWZpY2F0aW9uS2V5MjAxOSJ9XSwic2VydmljZXMiOlt7ImlkIjoiZG9tYWluLTEiLCJzZXJ2aWNlRW5kcG9pbnQiOiJodHRwczovL2Zvby5leGFtcGxlLmNvbSIsInR5cGUiOiJMaW5rZWREb21haW5zIn1dfX1dLCJ1cGRhdGVDb21taXRtZW50IjoiRWlEb3RoZXIifSwic3VmZml4RGF0YSI6eyJkZWx0YUhhc2giOiJFaUF0Y29QVVd2MVBpWEtVNEdORkpBTlg5Si12eUJXSktBMnNDV21YakpJSGJnIiwicmVjb3ZlcnlDb21taXRtZW50IjoiRWlCZk9aZE10VTZPQnc4UGs4NzlRdFotMkotOUZiYmpTWnlvYUFfYnFENHpoQSJ9fQ", SUFFIX);
+	assert_eq!(
+		resolve(&Did::parse(&did).unwrap()),
+		Err("did:ion delta hash does not match create operation")
+	);
+}
+
+#[test]
+fn resolve_did_ion_with_unsupported_patch() {
+	let did = "did:ion:EiDQt2FOKCkIfh3W8bKdxVLiTaIKCU9nQfNYYKWuOir8bQ:eyJkZWx0YSI6eyJwYXRjaGVzIjpbeyJhY3Rpb24iOiJpZXRmLWpzb24tcGF0Y2giLCJwYXRjaGVzIjpbXX1dLCJ1cGRhdGVDb21taXRtZW50IjoieCJ9LCJzdWZmaXhEYXRhIjp7ImRlbHRhSGFzaCI6IkVpQ3lZN04zekdrT1A4Mjh5OEJqRXM4elJfYnAyQWZqZ2pBU0RraTlrYWI4QnciLCJyZWNvdmVyeUNvbW1pdG1lbnQiOiJ5In19";
+	assert_eq!(
+		resolve(&Did::parse(did).unwrap()),
+		Err("unsupported did:ion patch action")
+	);
+}
+
+#[test]
+fn resolve_did_ion_with_invalid_did() {
+	assert_eq!(
+		resolve(&Did::parse(&format!("did:example:{}", SUFFIX)).unwrap()),
+		Err("not a did:ion DID")
+	);
+	assert_eq!(
+		resolve(&Did::parse(&format!("did:ion:{}", SUFFIX)).unwrap()),
+		Err("cannot resolve did:ion short form without an ION node")
+	);
+	assert_eq!(
+		resolve(&Did::parse(&format!("did:ion:test:{}", SUFFIX)).unwrap()),
+		Err("cannot resolve did:ion short form without an ION node")
+	);
+	assert_eq!(
+		resolve(&Did::parse(&format!("did:ion:{}:WzFd", SUFFIX)).unwrap()),
+		Err("invalid did:ion initial state")
+	);
+}
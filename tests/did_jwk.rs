@@ -0,0 +1,127 @@
+use did_common::did::Did;
+use did_common::did_doc::{
+	DidDocument, DidDocumentBuilder, PublicKeyBuilder, PublicKeyEncoded, PublicKeyType,
+	VerificationMethod,
+};
+use did_common::json_parse;
+use did_common::method::jwk::{generate, resolve};
+
+const P256_JWK: &str = r#"{"crv":"P-256","kty":"EC","x":"acbIQiuMs3i8_uszEjJ2tpTtRM4EU3yz91PH6CdH2V0","y":"_KcyLj9vWMptnmKtm46GqDz8wf74I5LKgrl2GzH3nSE"}"#;
+const P256_DID: &str = "did:jwk:eyJjcnYiOiJQLTI1NiIsImt0eSI6IkVDIiwieCI6ImFjYklRaXVNczNpOF91c3pFakoydHBUdFJNNEVVM3l6OTFQSDZDZEgyVjAiLCJ5IjoiX0tjeUxqOXZXTXB0bm1LdG00NkdxRHo4d2Y3NEk1TEtncmwyR3pIM25TRSJ9";
+const X25519_DID: &str = "did:jwk:eyJrdHkiOiJPS1AiLCJjcnYiOiJYMjU1MTkiLCJ1c2UiOiJlbmMiLCJ4IjoiM3A3YmZYdDl3YlRUVzJIQzdPUTFOei1EUThoYmVHZE5yZngtRkctSUswOCJ9";
+
+#[test]
+fn generate_did_jwk() {
+	assert_eq!(
+		generate(&json_parse(P256_JWK).unwrap()),
+		Ok(P256_DID.to_string())
+	);
+}
+
+#[test]
+fn generate_did_jwk_with_private_key() {
+	assert_eq!(
+		generate(
+			&json_parse(r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo","d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A"}"#)
+				.unwrap()
+		),
+		Err("did:jwk key must not contain private key material")
+	);
+}
+
+#[test]
+fn generate_did_jwk_with_symmetric_key() {
+	assert_eq!(
+		generate(&json_parse(r#"{"kty":"oct","k":"GawgguFyGrWKav7AX4VKUg"}"#).unwrap()),
+		Err("did:jwk key must not be a symmetric key")
+	);
+}
+
+#[test]
+fn generate_did_jwk_with_invalid_key() {
+	assert_eq!(
+		generate(&json_parse(r#"{"crv":"P-256"}"#).unwrap()),
+		Err("invalid did:jwk key")
+	);
+}
+
+#[test]
+fn resolve_did_jwk_without_key_use() {
+	let json = resolve(&Did::parse(P256_DID).unwrap()).unwrap();
+	let jwk = json_parse(P256_JWK).unwrap();
+	let key_id = format!("{}#0", P256_DID);
+	assert_eq!(
+		DidDocument::parse(&json),
+		Ok(DidDocumentBuilder::new(P256_DID)
+			.with_pubkeys(vec![PublicKeyBuilder::new(
+				&key_id,
+				PublicKeyType::JsonWebKey,
+				P256_DID
+			)
+			.with_encoded_key(PublicKeyEncoded::JwkObject(&jwk))
+			.build()])
+			.with_authentication(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_assertion_method(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_key_agreement(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_capability_invocation(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_capability_delegation(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.build())
+	);
+}
+
+#[test]
+fn resolve_did_jwk_with_enc_key_use() {
+	let json = resolve(&Did::parse(X25519_DID).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let key_id = format!("{}#0", X25519_DID);
+	assert_eq!(
+		did_doc.key_agreement(),
+		&[VerificationMethod::Reference(key_id.as_str().into())]
+	);
+	assert!(did_doc.authentication().is_empty());
+	assert!(did_doc.assertion_method().is_empty());
+	assert_eq!(json["publicKey"][0]["publicKeyJwk"]["crv"], "X25519");
+}
+
+#[test]
+fn resolve_did_jwk_with_sig_key_use() {
+	let did = generate(
+		&json_parse(
+			r#"{"kty":"OKP","crv":"Ed25519","use":"sig","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#,
+		)
+		.unwrap(),
+	)
+	.unwrap();
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let key_id = format!("{}#0", did);
+	assert!(did_doc.key_agreement().is_empty());
+	assert_eq!(
+		did_doc.authentication(),
+		&[VerificationMethod::Reference(key_id.as_str().into())]
+	);
+	assert_eq!(
+		did_doc.capability_delegation(),
+		&[VerificationMethod::Reference(key_id.as_str().into())]
+	);
+}
+
+#[test]
+fn resolve_did_jwk_with_invalid_did() {
+	assert_eq!(
+		resolve(&Did::parse("did:example:eyJrdHkiOiJFQyJ9").unwrap()),
+		Err("not a did:jwk DID")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:jwk:e").unwrap()),
+		Err("invalid base64url encoding")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:jwk:WzFd").unwrap()),
+		Err("invalid did:jwk key")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:jwk:eyJrdHkiOiJFQyIsInVzZSI6ImZvbyJ9").unwrap()),
+		Err("invalid did:jwk key use")
+	);
+}
@@ -1,5 +1,8 @@
+// Builders are called with borrowed arrays, as in the README
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use did_common::did;
-use did_common::did::{Did, DidBuilder};
+use did_common::did::{Did, DidBuildError, DidBuilder};
 
 #[test]
 fn did_macro_generic_did() {
@@ -104,3 +107,100 @@ fn did_macro_did_with_multiple_params_and_fragment() {
 			.build()
 	);
 }
+
+const AGENT_DID: Did<'static> = did!("did:example:1234;service=agent#keys-1");
+
+#[test]
+fn did_macro_in_const_context() {
+	assert_eq!(
+		AGENT_DID,
+		DidBuilder::new("example", "1234")
+			.with_service("agent")
+			.with_fragment("keys-1")
+			.build()
+	);
+}
+
+macro_rules! assert_same_as_parsed {
+	($($did:tt),*) => {
+		$(assert_eq!(did!($did), Did::parse($did).unwrap());)*
+	};
+}
+
+#[test]
+fn did_macro_same_as_parsed() {
+	assert_same_as_parsed!(
+		"did:example:1234;",
+		"did:example:12%3A34:ab-c_d.e",
+		"did:example:1234;service=",
+		"did:example:1234;service=a%2Fb;example:foo=#",
+		"did:example:1234#keys-1;x=y@z",
+		"did:web:example.com%3A8443:users:alice"
+	);
+}
+
+#[test]
+fn did_macro_from_parts() {
+	let id = String::from("1234");
+	assert_eq!(
+		did!(method = "example", id = &id),
+		Ok(DidBuilder::new("example", "1234").build())
+	);
+	assert_eq!(
+		did!(method = "example", id = &id # "keys-1"),
+		Ok(DidBuilder::new("example", "1234")
+			.with_fragment("keys-1")
+			.build())
+	);
+}
+
+#[test]
+fn did_macro_from_parts_with_params() {
+	let hl = "zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e";
+	let did = did!(method = "example", id = "1234"; service = "agent", hl = hl # "keys-1").unwrap();
+	assert_eq!(
+		did.to_string(),
+		"did:example:1234;service=agent;hl=zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e#keys-1"
+	);
+	assert_eq!(
+		did!(method = "example", id = "1234"; relativeRef = "%2Fdocs", "example:foo" = "bar"),
+		Ok(DidBuilder::new("example", "1234")
+			.with_relative_ref("%2Fdocs")
+			.with_param("example:foo", "bar")
+			.build())
+	);
+}
+
+#[test]
+fn did_macro_from_invalid_parts() {
+	let method = "EXAMPLE";
+	assert_eq!(
+		did!(method = method, id = "1234"),
+		Err(DidBuildError::InvalidMethodName)
+	);
+	assert_eq!(
+		did!(method = "example", id = "1234"; service = "a#gent"),
+		Err(DidBuildError::InvalidParamValue)
+	);
+	assert_eq!(
+		did!(method = "example", id = "1234"; "other:foo" = "bar"),
+		Err(DidBuildError::ForeignParam)
+	);
+	assert_eq!(
+		did!(method = "example", id = "1234" # "keys 1"),
+		Err(DidBuildError::InvalidFragment)
+	);
+}
+
+#[test]
+fn did_macro_without_imports() {
+	mod no_imports {
+		pub const DID: did_common::did::Did<'static> = did_common::did!("did:example:1234");
+	}
+	assert_eq!(no_imports::DID, DidBuilder::new("example", "1234").build());
+}
+
+#[test]
+fn did_macro_rejects_invalid_dids() {
+	trybuild::TestCases::new().compile_fail("tests/ui/did_macro_*.rs");
+}
@@ -0,0 +1,307 @@
+use did_common::did::Did;
+use did_common::did_doc::{
+	DidDocument, DidDocumentBuilder, PublicKeyBuilder, PublicKeyEncoded, PublicKeyType, Service,
+	ServiceEndpoint, VerificationMethod, VerificationRelationship,
+};
+use did_common::json_parse;
+use did_common::method::peer::{
+	generate_numalgo0, generate_numalgo2, generate_numalgo4, resolve, short_form,
+};
+
+const ED25519_KEY: [u8; 32] = [
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+	26, 27, 28, 29, 30, 31,
+];
+const ED25519_MULTIBASE: &str = "z6MkeTGwHmLmuCmgg4ABYhzWVh6ZX7hTwWt8gguAretUfc9c";
+const ED25519_BASE58: &str = "1thX6LZfHDZZKUs92febYZhYRcXddmzfzF2NvTkPNE";
+const X25519_KEY: [u8; 32] = [
+	32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55,
+	56, 57, 58, 59, 60, 61, 62, 63,
+];
+const X25519_MULTIBASE: &str = "z6LSdqbWoToXafWD7qhVMLz2HCTTMmhAnGnAki2vP11ANRTc";
+const X25519_BASE58: &str = "3ARMH9zfVCnU2TKiphU4xcEyWdA45fc1sjKEtYMdf3gr";
+const DIDCOMM_SERVICE: &str = "eyJ0IjoiZG0iLCJzIjp7InVyaSI6Imh0dHBzOi8vZXhhbXBsZS5jb20vZGlkY29tbSIsImEiOlsiZGlkY29tbS92MiJdLCJyIjpbImRpZDpleGFtcGxlOjEyMyNrZXktMSJdfX0";
+
+#[test]
+fn generate_did_peer_numalgo0() {
+	assert_eq!(
+		generate_numalgo0(PublicKeyType::Ed25519, &ED25519_KEY),
+		Ok(format!("did:peer:0{}", ED25519_MULTIBASE))
+	);
+}
+
+#[test]
+fn generate_did_peer_numalgo0_with_invalid_key() {
+	assert_eq!(
+		generate_numalgo0(PublicKeyType::Rsa, &ED25519_KEY),
+		Err("unsupported did:peer key type")
+	);
+	assert_eq!(
+		generate_numalgo0(PublicKeyType::Ed25519, &ED25519_KEY[1..]),
+		Err("invalid did:peer public key length")
+	);
+}
+
+#[test]
+fn resolve_did_peer_numalgo0() {
+	let did = format!("did:peer:0{}", ED25519_MULTIBASE);
+	let key_id = format!("{}#{}", did, ED25519_MULTIBASE);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(
+		DidDocument::parse(&json),
+		Ok(DidDocumentBuilder::new(&did)
+			.with_pubkeys(vec![PublicKeyBuilder::new(
+				&key_id,
+				PublicKeyType::Ed25519,
+				&did
+			)
+			.with_encoded_key(PublicKeyEncoded::Base58(ED25519_BASE58))
+			.build()])
+			.with_authentication(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_assertion_method(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_capability_invocation(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_capability_delegation(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.build())
+	);
+}
+
+#[test]
+fn resolve_did_peer_numalgo0_with_x25519_key() {
+	let did = generate_numalgo0(PublicKeyType::X25519, &X25519_KEY).unwrap();
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert!(did_doc.authentication().is_empty());
+	assert_eq!(
+		did_doc.key_agreement(),
+		&[VerificationMethod::Reference(
+			format!("{}#{}", did, X25519_MULTIBASE).into()
+		)]
+	);
+}
+
+#[test]
+fn generate_did_peer_numalgo2() {
+	let service = json_parse(
+		r#"
+		{
+			"type": "DIDCommMessaging",
+			"serviceEndpoint": {
+				"uri": "https://example.com/didcomm",
+				"accept": ["didcomm/v2"],
+				"routingKeys": ["did:example:123#key-1"]
+			}
+		}
+		"#,
+	)
+	.unwrap();
+	assert_eq!(
+		generate_numalgo2(
+			&[
+				(
+					VerificationRelationship::KeyAgreement,
+					PublicKeyType::X25519,
+					&X25519_KEY
+				),
+				(
+					VerificationRelationship::Authentication,
+					PublicKeyType::Ed25519,
+					&ED25519_KEY
+				)
+			],
+			&[service]
+		),
+		Ok(format!(
+			"did:peer:2.E{}.V{}.S{}",
+			X25519_MULTIBASE, ED25519_MULTIBASE, DIDCOMM_SERVICE
+		))
+	);
+}
+
+#[test]
+fn resolve_did_peer_numalgo2() {
+	let did = format!(
+		"did:peer:2.E{}.V{}.S{}",
+		X25519_MULTIBASE, ED25519_MULTIBASE, DIDCOMM_SERVICE
+	);
+	let (key_1, key_2, service) = (
+		format!("{}#key-1", did),
+		format!("{}#key-2", did),
+		format!("{}#service", did),
+	);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(
+		DidDocument::parse(&json),
+		Ok(DidDocumentBuilder::new(&did)
+			.with_pubkeys(vec![
+				PublicKeyBuilder::new(&key_1, PublicKeyType::X25519, &did)
+					.with_encoded_key(PublicKeyEncoded::Base58(X25519_BASE58))
+					.build(),
+				PublicKeyBuilder::new(&key_2, PublicKeyType::Ed25519, &did)
+					.with_encoded_key(PublicKeyEncoded::Base58(ED25519_BASE58))
+					.build()
+			])
+			.with_key_agreement(vec![VerificationMethod::Reference(key_1.as_str().into())])
+			.with_authentication(vec![VerificationMethod::Reference(key_2.as_str().into())])
+			.with_services(vec![Service::new(
+				&service,
+				"DIDCommMessaging",
+				ServiceEndpoint::Uri("https://example.com/didcomm")
+			)])
+			.build())
+	);
+	assert_eq!(json["service"][0]["accept"][0], "didcomm/v2");
+	assert_eq!(
+		json["service"][0]["routingKeys"][0],
+		"did:example:123#key-1"
+	);
+}
+
+#[test]
+fn resolve_did_peer_numalgo2_with_multiple_services() {
+	let did = generate_numalgo2(
+		&[],
+		&[
+			json_parse(r#"{ "type": "DIDCommMessaging", "serviceEndpoint": "https://a.example" }"#)
+				.unwrap(),
+			json_parse(r#"{ "type": "LinkedDomains", "serviceEndpoint": "https://b.example" }"#)
+				.unwrap(),
+		],
+	)
+	.unwrap();
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(did_doc.service()[0].id(), format!("{}#service", did));
+	assert_eq!(did_doc.service()[0].svc_type(), "DIDCommMessaging");
+	assert_eq!(did_doc.service()[1].id(), format!("{}#service-1", did));
+	assert_eq!(did_doc.service()[1].svc_type(), "LinkedDomains");
+}
+
+#[test]
+fn resolve_did_peer_numalgo2_with_invalid_elements() {
+	assert_eq!(
+		resolve(&Did::parse(&format!("did:peer:2.X{}", ED25519_MULTIBASE)).unwrap()),
+		Err("invalid did:peer purpose code")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:peer:2.VzTjsU3jY").unwrap()),
+		Err("invalid did:peer public key length")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:peer:2.SeyJ0").unwrap()),
+		Err("invalid did:peer service")
+	);
+}
+
+#[test]
+fn resolve_did_peer_with_invalid_did() {
+	assert_eq!(
+		resolve(&Did::parse("did:example:0z6Mk").unwrap()),
+		Err("not a did:peer DID")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:peer:1zQmZ").unwrap()),
+		Err("unsupported did:peer numalgo")
+	);
+}
+
+fn numalgo4_input_doc() -> json::JsonValue {
+	json_parse(&format!(
+		r##"
+		{{
+			"@context": ["https://www.w3.org/ns/did/v1", "https://w3id.org/security/multikey/v1"],
+			"verificationMethod": [
+				{{ "id": "#key-1", "type": "Multikey", "publicKeyMultibase": "{}" }},
+				{{ "id": "#key-2", "type": "Multikey", "publicKeyMultibase": "{}" }}
+			],
+			"authentication": ["#key-1"],
+			"keyAgreement": ["#key-2"],
+			"service": [
+				{{
+					"id": "#didcomm",
+					"type": "DIDCommMessaging",
+					"serviceEndpoint": {{ "uri": "https://example.com/didcomm", "accept": ["didcomm/v2"] }}
+				}}
+			]
+		}}
+		"##,
+		ED25519_MULTIBASE, X25519_MULTIBASE
+	))
+	.unwrap()
+}
+
+#[test]
+fn resolve_did_peer_numalgo4_long_form() {
+	let did = generate_numalgo4(&numalgo4_input_doc()).unwrap();
+	assert!(did.starts_with("did:peer:4zQm"));
+	let short = short_form(&Did::parse(&did).unwrap()).unwrap();
+	assert!(did.starts_with(&format!("{}:", short)));
+
+	let (key_1, key_2, service) = (
+		format!("{}#key-1", did),
+		format!("{}#key-2", did),
+		format!("{}#didcomm", did),
+	);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(json["alsoKnownAs"][0], short.as_str());
+	assert_eq!(
+		DidDocument::parse(&json),
+		Ok(DidDocumentBuilder::new(&did)
+			.with_pubkeys(vec![
+				PublicKeyBuilder::new(&key_1, PublicKeyType::Ed25519, &did)
+					.with_encoded_key(PublicKeyEncoded::Base58(ED25519_BASE58))
+					.build(),
+				PublicKeyBuilder::new(&key_2, PublicKeyType::X25519, &did)
+					.with_encoded_key(PublicKeyEncoded::Base58(X25519_BASE58))
+					.build()
+			])
+			.with_authentication(vec![VerificationMethod::Reference(key_1.as_str().into())])
+			.with_key_agreement(vec![VerificationMethod::Reference(key_2.as_str().into())])
+			.with_services(vec![Service::new(
+				&service,
+				"DIDCommMessaging",
+				ServiceEndpoint::Uri("https://example.com/didcomm")
+			)])
+			.build())
+	);
+}
+
+#[test]
+fn resolve_did_peer_numalgo4_short_form() {
+	let did = generate_numalgo4(&numalgo4_input_doc()).unwrap();
+	let short = short_form(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(
+		resolve(&Did::parse(&short).unwrap()),
+		Err("cannot resolve did:peer numalgo 4 short form without its long form")
+	);
+}
+
+#[test]
+fn resolve_did_peer_numalgo4_with_mismatching_hash() {
+	let did = generate_numalgo4(&numalgo4_input_doc()).unwrap();
+	let other = generate_numalgo4(&json_parse(r#"{ "service": [] }"#).unwrap()).unwrap();
+	let tampered = format!(
+		"{}:{}",
+		short_form(&Did::parse(&did).unwrap()).unwrap(),
+		other.rsplit(':').next().unwrap()
+	);
+	assert_eq!(
+		resolve(&Did::parse(&tampered).unwrap()),
+		Err("did:peer numalgo 4 hash does not match input document")
+	);
+}
+
+#[test]
+fn generate_did_peer_numalgo4_with_id() {
+	assert_eq!(
+		generate_numalgo4(&json_parse(r#"{ "id": "did:example:123" }"#).unwrap()),
+		Err("invalid did:peer input document")
+	);
+}
+
+#[test]
+fn did_peer_short_form_with_other_numalgo() {
+	assert_eq!(
+		short_form(&Did::parse(&format!("did:peer:0{}", ED25519_MULTIBASE)).unwrap()),
+		Err("not a did:peer numalgo 4 DID")
+	);
+}
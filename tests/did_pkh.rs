@@ -0,0 +1,135 @@
+use did_common::caip::{AccountId, ChainId};
+use did_common::did::Did;
+use did_common::did_doc::{
+	DidDocument, DidDocumentBuilder, PublicKeyBuilder, PublicKeyEncoded, PublicKeyType,
+	VerificationMethod,
+};
+use did_common::method::pkh::{account_id, generate, resolve};
+
+const ETH_ACCOUNT: &str = "eip155:1:0xb9c5714089478a327f09197987f16f9e5d936e8a";
+const SOL_ACCOUNT: &str =
+	"solana:4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZ:CKg5d12Jhpej1JqtmxLJgaFqqeYjxgPqToJ4LBdvG9Ev";
+const DOT_ACCOUNT: &str =
+	"polkadot:b0a8d493285c2df73290dfb7e61f870f:5hmuyxw9xdgbpptgypokw4thfyoe3ryenebr381z9iaegmfy";
+
+#[test]
+fn generate_did_pkh() {
+	assert_eq!(
+		generate(&AccountId::parse(ETH_ACCOUNT).unwrap()),
+		Ok(format!("did:pkh:{}", ETH_ACCOUNT))
+	);
+}
+
+#[test]
+fn generate_did_pkh_with_invalid_address() {
+	assert_eq!(
+		generate(&AccountId::new(ChainId::new("eip155", "1"), "0xb9c5")),
+		Err("invalid did:pkh eip155 address")
+	);
+	assert_eq!(
+		generate(&AccountId::new(
+			ChainId::new("solana", "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZ"),
+			"CKg5d12Jhpej1Jqt"
+		)),
+		Err("invalid did:pkh solana address")
+	);
+}
+
+#[test]
+fn did_pkh_account_id() {
+	let did = format!("did:pkh:{}", DOT_ACCOUNT);
+	let account = account_id(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(account.chain_id().namespace(), "polkadot");
+	assert_eq!(
+		account.chain_id().reference(),
+		"b0a8d493285c2df73290dfb7e61f870f"
+	);
+	assert_eq!(
+		account.address(),
+		"5hmuyxw9xdgbpptgypokw4thfyoe3ryenebr381z9iaegmfy"
+	);
+}
+
+#[test]
+fn resolve_did_pkh_eip155() {
+	let did = format!("did:pkh:{}", ETH_ACCOUNT);
+	let key_id = format!("{}#blockchainAccountId", did);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(
+		DidDocument::parse(&json),
+		Ok(DidDocumentBuilder::new(&did)
+			.with_pubkeys(vec![PublicKeyBuilder::new(
+				&key_id,
+				PublicKeyType::EcdsaSecp256k1Recovery,
+				&did
+			)
+			.with_encoded_key(PublicKeyEncoded::BlockchainAccountId(ETH_ACCOUNT))
+			.build()])
+			.with_authentication(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_assertion_method(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.build())
+	);
+}
+
+#[test]
+fn resolve_did_pkh_solana() {
+	let did = format!("did:pkh:{}", SOL_ACCOUNT);
+	let key_id = format!("{}#controller", did);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	assert_eq!(json["publicKey"][0]["blockchainAccountId"], SOL_ACCOUNT);
+	assert_eq!(
+		DidDocument::parse(&json),
+		Ok(DidDocumentBuilder::new(&did)
+			.with_pubkeys(vec![PublicKeyBuilder::new(
+				&key_id,
+				PublicKeyType::Ed25519,
+				&did
+			)
+			.with_encoded_key(PublicKeyEncoded::Base58(
+				"CKg5d12Jhpej1JqtmxLJgaFqqeYjxgPqToJ4LBdvG9Ev"
+			))
+			.with_blockchain_account_id(SOL_ACCOUNT)
+			.build()])
+			.with_authentication(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.with_assertion_method(vec![VerificationMethod::Reference(key_id.as_str().into())])
+			.build())
+	);
+}
+
+#[test]
+fn resolve_did_pkh_solana_round_trip() {
+	let did = format!("did:pkh:{}", SOL_ACCOUNT);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let key = &did_doc.pub_keys()[0];
+	assert_eq!(key.blockchain_account_id(), Some(SOL_ACCOUNT));
+	assert_eq!(did_doc.to_json(), json);
+}
+
+#[test]
+fn resolve_did_pkh_other_namespace() {
+	let did = format!("did:pkh:{}", DOT_ACCOUNT);
+	let json = resolve(&Did::parse(&did).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(did_doc.pub_keys()[0].key_type(), &PublicKeyType::Blockchain);
+	assert_eq!(
+		did_doc.pub_keys()[0].encoded_key(),
+		&PublicKeyEncoded::BlockchainAccountId(DOT_ACCOUNT)
+	);
+}
+
+#[test]
+fn resolve_did_pkh_with_invalid_did() {
+	assert_eq!(
+		resolve(&Did::parse(&format!("did:example:{}", ETH_ACCOUNT)).unwrap()),
+		Err("not a did:pkh DID")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:pkh:eip155:0xb9c5714089478a327f09197987f16f9e5d936e8a").unwrap()),
+		Err("invalid CAIP-10 account id")
+	);
+	assert_eq!(
+		resolve(&Did::parse("did:pkh:eip155:1:b9c5714089478a327f09197987f16f9e5d936e8a").unwrap()),
+		Err("invalid did:pkh eip155 address")
+	);
+}
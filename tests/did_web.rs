@@ -0,0 +1,220 @@
+use did_common::did::Did;
+use did_common::did_doc::DidDocument;
+use did_common::method::web::{did_to_url, resolve, url_to_did, HttpFetch};
+
+const ALICE_DOC: &str = r#"
+{
+	"@context": "https://www.w3.org/2019/did/v1",
+	"id": "did:web:example.com%3A8443:users:alice"
+}
+"#;
+
+struct Fixtures(&'static [(&'static str, &'static str)]);
+
+impl HttpFetch for Fixtures {
+	fn fetch(&self, url: &str) -> Result<String, &'static str> {
+		self.0
+			.iter()
+			.find(|(u, _)| *u == url)
+			.map(|(_, body)| body.to_string())
+			.ok_or("not found")
+	}
+}
+
+#[test]
+fn did_web_to_url_for_domain() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:w3c-ccg.github.io").unwrap()),
+		Ok("https://w3c-ccg.github.io/.well-known/did.json".to_string())
+	);
+}
+
+#[test]
+fn did_web_to_url_for_domain_with_path() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:w3c-ccg.github.io:user:alice").unwrap()),
+		Ok("https://w3c-ccg.github.io/user/alice/did.json".to_string())
+	);
+}
+
+#[test]
+fn did_web_to_url_for_domain_with_port_and_path() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example.com%3A8443:users:alice").unwrap()),
+		Ok("https://example.com:8443/users/alice/did.json".to_string())
+	);
+}
+
+#[test]
+fn did_web_to_url_ignores_fragment() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example.com#keys-1").unwrap()),
+		Ok("https://example.com/.well-known/did.json".to_string())
+	);
+}
+
+#[test]
+fn did_web_to_url_with_other_method() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:example:example.com").unwrap()),
+		Err("not a did:web DID")
+	);
+}
+
+#[test]
+fn did_web_to_url_with_invalid_domain() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:-example.com").unwrap()),
+		Err("invalid did:web domain name")
+	);
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example..com").unwrap()),
+		Err("invalid did:web domain name")
+	);
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:exa%2Fmple.com").unwrap()),
+		Err("invalid did:web domain name")
+	);
+}
+
+#[test]
+fn did_web_to_url_with_invalid_port() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example.com%3A99999").unwrap()),
+		Err("invalid did:web port")
+	);
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example.com%3A").unwrap()),
+		Err("invalid did:web port")
+	);
+}
+
+#[test]
+fn did_web_to_url_with_invalid_path() {
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example.com:..:alice").unwrap()),
+		Err("invalid did:web path segment")
+	);
+	assert_eq!(
+		did_to_url(&Did::parse("did:web:example.com:.:alice").unwrap()),
+		Err("invalid did:web path segment")
+	);
+}
+
+#[test]
+fn url_to_did_web_for_well_known() {
+	assert_eq!(
+		url_to_did("https://w3c-ccg.github.io/.well-known/did.json"),
+		Ok("did:web:w3c-ccg.github.io".to_string())
+	);
+}
+
+#[test]
+fn url_to_did_web_for_port_and_path() {
+	assert_eq!(
+		url_to_did("https://example.com:8443/users/alice/did.json"),
+		Ok("did:web:example.com%3A8443:users:alice".to_string())
+	);
+}
+
+#[test]
+fn url_to_did_web_with_invalid_url() {
+	assert_eq!(
+		url_to_did("http://example.com/.well-known/did.json"),
+		Err("did:web URL must use the https scheme")
+	);
+	assert_eq!(
+		url_to_did("https://example.com/users/alice"),
+		Err("did:web URL must point to a did.json document")
+	);
+	assert_eq!(
+		url_to_did("https://example.com/did.json"),
+		Err("invalid did:web URL path")
+	);
+	assert_eq!(
+		url_to_did("https://example.com/users//did.json"),
+		Err("invalid did:web path segment")
+	);
+	assert_eq!(
+		url_to_did("https://example.com:http/did.json"),
+		Err("invalid did:web port")
+	);
+}
+
+#[test]
+fn did_web_url_round_trip() {
+	let did = "did:web:example.com%3A8443:users:alice";
+	let url = did_to_url(&Did::parse(did).unwrap()).unwrap();
+	assert_eq!(url_to_did(&url), Ok(did.to_string()));
+}
+
+#[test]
+fn resolve_did_web() {
+	let fixtures = Fixtures(&[("https://example.com:8443/users/alice/did.json", ALICE_DOC)]);
+	let json = resolve(
+		&Did::parse("did:web:example.com%3A8443:users:alice").unwrap(),
+		&fixtures,
+	)
+	.unwrap();
+	assert_eq!(
+		DidDocument::parse(&json).unwrap().id(),
+		"did:web:example.com%3A8443:users:alice"
+	);
+}
+
+#[test]
+fn resolve_did_web_with_closure_fetcher() {
+	let fetcher = |url: &str| {
+		assert_eq!(url, "https://example.com:8443/users/alice/did.json");
+		Ok(ALICE_DOC.to_string())
+	};
+	assert!(resolve(
+		&Did::parse("did:web:example.com%3A8443:users:alice#keys-1").unwrap(),
+		&fetcher
+	)
+	.is_ok());
+}
+
+#[test]
+fn resolve_did_web_with_fetch_error() {
+	assert_eq!(
+		resolve(
+			&Did::parse("did:web:example.com:users:bob").unwrap(),
+			&Fixtures(&[])
+		),
+		Err("not found")
+	);
+}
+
+#[test]
+fn resolve_did_web_with_invalid_json() {
+	let fixtures = Fixtures(&[("https://example.com/.well-known/did.json", "{ id: ")]);
+	assert_eq!(
+		resolve(&Did::parse("did:web:example.com").unwrap(), &fixtures),
+		Err("invalid did:web DID document")
+	);
+}
+
+#[test]
+fn resolve_did_web_with_mismatching_id() {
+	let fixtures = Fixtures(&[("https://example.com/users/bob/did.json", ALICE_DOC)]);
+	assert_eq!(
+		resolve(
+			&Did::parse("did:web:example.com:users:bob").unwrap(),
+			&fixtures
+		),
+		Err("did:web DID document id does not match DID")
+	);
+}
+
+#[test]
+fn resolve_did_web_with_missing_id() {
+	let fixtures = Fixtures(&[(
+		"https://example.com/.well-known/did.json",
+		r#"{ "@context": "https://www.w3.org/2019/did/v1" }"#,
+	)]);
+	assert_eq!(
+		resolve(&Did::parse("did:web:example.com").unwrap(), &fixtures),
+		Err("missing did:web DID document id")
+	);
+}
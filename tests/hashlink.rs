@@ -0,0 +1,139 @@
+use did_common::did::{Did, DidBuilder};
+use did_common::did_doc::DidDocument;
+use did_common::hashlink::{HashAlgorithm, Hashlink};
+use did_common::json_parse;
+use json::JsonValue;
+
+const HELLO_WORLD_HL: &str = "zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e";
+const DID_DOC_HL: &str = "zQmXgzyFmzs3kRHhQdfQTZPTfjHrmwwLgoa5DZRgePruyXn";
+
+fn did_doc_json() -> JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{
+					"id": "did:example:123#key-1",
+					"type": "Ed25519VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyBase58": "FAe4sisG95oZ42w7buUn5qEE4TAnfTTFPiguZUHmhiF"
+				}
+			],
+			"authentication": ["did:example:123#key-1"]
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+#[test]
+fn parse_sha2_256_hashlink() {
+	let hl = Hashlink::parse(HELLO_WORLD_HL).unwrap();
+	assert_eq!(hl.algorithm(), HashAlgorithm::Sha2_256);
+	assert_eq!(hl.digest().len(), 32);
+	assert_eq!(hl.to_string(), HELLO_WORLD_HL);
+	assert_eq!(hl, Hashlink::new(HashAlgorithm::Sha2_256, b"Hello World!"));
+}
+
+#[test]
+fn parse_sha2_512_hashlink() {
+	let hl = Hashlink::new(HashAlgorithm::Sha2_512, b"Hello World!");
+	assert_eq!(
+		hl.to_string(),
+		"z8VvU2oXpxk7mhUE4Vv5rNAqBiYLZLay6tJoo3QAEzGSy14ymFxNNJQUFk5et2Q9AUon1BxqKzQGsQZhCxUKfoKdp1m"
+	);
+	assert_eq!(Hashlink::parse(&hl.to_string()), Ok(hl));
+}
+
+#[test]
+fn parse_invalid_hashlink_fails() {
+	assert_eq!(
+		Hashlink::parse("QmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e"),
+		Err("invalid hashlink encoding")
+	);
+	assert_eq!(
+		Hashlink::parse("z6PKZVY9M58wGtimvrDvwziSvUCo8YstQQV3dUK17jzFL3"),
+		Err("invalid hashlink digest length")
+	);
+	assert_eq!(
+		Hashlink::parse("z6PFf5teczF6nBK5osiRgYpXBEwnZnAwJVuBKaoookGSfh"),
+		Err("invalid hashlink digest length")
+	);
+	// identity multihash
+	assert_eq!(
+		Hashlink::parse("z1kvu"),
+		Err("unsupported hashlink hash algorithm")
+	);
+}
+
+#[test]
+fn verify_hashlink() {
+	let hl = Hashlink::parse(HELLO_WORLD_HL).unwrap();
+	assert_eq!(hl.verify(b"Hello World!"), Ok(()));
+	assert_eq!(
+		hl.verify(b"Hello World"),
+		Err("hashlink digest does not match")
+	);
+}
+
+#[test]
+fn did_url_hashlink() {
+	let did_url = format!("did:example:123;hl={}", HELLO_WORLD_HL);
+	let did = Did::parse(&did_url).unwrap();
+	assert_eq!(did.param("hl"), Some(Some(HELLO_WORLD_HL)));
+	assert_eq!(
+		did.hashlink(),
+		Ok(Some(Hashlink::parse(HELLO_WORLD_HL).unwrap()))
+	);
+
+	assert_eq!(Did::parse("did:example:123").unwrap().hashlink(), Ok(None));
+	assert_eq!(
+		DidBuilder::new("example", "123")
+			.with_params([("hl", None)])
+			.build()
+			.hashlink(),
+		Err("missing hl parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:123;hl=").unwrap().hashlink(),
+		Err("missing hl parameter value")
+	);
+}
+
+#[test]
+fn verify_did_document_against_did_url_hashlink() {
+	let json = did_doc_json();
+	let did_url = format!("did:example:123;hl={}", DID_DOC_HL);
+	let hl = Did::parse(&did_url).unwrap().hashlink().unwrap().unwrap();
+	assert_eq!(hl.verify_json(&json), Ok(()));
+	assert_eq!(
+		Hashlink::from_json(HashAlgorithm::Sha2_256, &json).map(|hl| hl.to_string()),
+		Ok(DID_DOC_HL.to_string())
+	);
+
+	let mut updated = did_doc_json();
+	updated["authentication"] = JsonValue::new_array();
+	assert_eq!(
+		hl.verify_json(&updated),
+		Err("hashlink digest does not match")
+	);
+}
+
+#[test]
+fn verify_hashlink_covers_unmodeled_did_document_properties() {
+	let json = did_doc_json();
+	let hl = Hashlink::from_json(HashAlgorithm::Sha2_256, &json).unwrap();
+
+	let mut updated = did_doc_json();
+	updated["alsoKnownAs"] = JsonValue::from(vec!["https://example.com/alice"]);
+	assert_eq!(
+		DidDocument::parse(&updated).unwrap(),
+		DidDocument::parse(&json).unwrap()
+	);
+	assert_eq!(
+		hl.verify_json(&updated),
+		Err("hashlink digest does not match")
+	);
+}
@@ -0,0 +1,71 @@
+use did_common::caip::{AccountId, ChainId};
+
+#[test]
+fn parse_chain_id() {
+	assert_eq!(ChainId::parse("eip155:1"), Ok(ChainId::new("eip155", "1")));
+	assert_eq!(
+		ChainId::parse("bip122:000000000019d6689c085ae165831e93"),
+		Ok(ChainId::new("bip122", "000000000019d6689c085ae165831e93"))
+	);
+}
+
+#[test]
+fn parse_invalid_chain_id() {
+	assert_eq!(ChainId::parse("eip155"), Err("invalid CAIP-2 chain id"));
+	assert_eq!(ChainId::parse("ei:1"), Err("invalid CAIP-2 chain id"));
+	assert_eq!(ChainId::parse("EIP155:1"), Err("invalid CAIP-2 chain id"));
+	assert_eq!(ChainId::parse("eip155:"), Err("invalid CAIP-2 chain id"));
+	assert_eq!(
+		ChainId::parse("eip155:000000000019d6689c085ae165831e930"),
+		Err("invalid CAIP-2 chain id")
+	);
+}
+
+#[test]
+fn parse_account_id() {
+	assert_eq!(
+		AccountId::parse("eip155:1:0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb"),
+		Ok(AccountId::new(
+			ChainId::new("eip155", "1"),
+			"0xab16a96D359eC26a11e2C2b3d8f8B8942d5Bfcdb"
+		))
+	);
+	assert_eq!(
+		AccountId::parse("polkadot:b0a8d493285c2df73290dfb7e61f870f:5hmuyxw9xdgbpptgypokw4thfyoe3ryenebr381z9iaegmfy"),
+		Ok(AccountId::new(
+			ChainId::new("polkadot", "b0a8d493285c2df73290dfb7e61f870f"),
+			"5hmuyxw9xdgbpptgypokw4thfyoe3ryenebr381z9iaegmfy"
+		))
+	);
+}
+
+#[test]
+fn parse_invalid_account_id() {
+	assert_eq!(
+		AccountId::parse("eip155:1"),
+		Err("invalid CAIP-10 account id")
+	);
+	assert_eq!(
+		AccountId::parse("eip155:1:0xab16:extra"),
+		Err("invalid CAIP-10 account id")
+	);
+	assert_eq!(
+		AccountId::parse("eip155:1:0x#ab16"),
+		Err("invalid CAIP-10 account id")
+	);
+}
+
+#[test]
+fn validate_account_id() {
+	assert!(AccountId::is_valid(
+		"cosmos:cosmoshub-3:cosmos1t2uflqwqe0fsj0shcfkrvpukewcw40yjj6hdc0"
+	));
+	assert!(!AccountId::is_valid("cosmos:cosmoshub-3"));
+}
+
+#[test]
+fn account_id_display_trait() {
+	let account = AccountId::new(ChainId::new("eip155", "1"), "0xab16");
+	assert_eq!(format!("{}", account.chain_id()), "eip155:1");
+	assert_eq!(format!("{}", account), "eip155:1:0xab16");
+}
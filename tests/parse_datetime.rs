@@ -0,0 +1,195 @@
+use did_common::datetime::XsdDateTime;
+use did_common::did_doc::DidDocument;
+use did_common::json_parse;
+
+fn datetime(input: &str) -> XsdDateTime {
+	XsdDateTime::parse(input).unwrap()
+}
+
+#[test]
+fn parse_utc_datetime() {
+	let created = datetime("2002-10-10T17:00:00Z");
+	assert_eq!(created.year(), 2002);
+	assert_eq!(created.month(), 10);
+	assert_eq!(created.day(), 10);
+	assert_eq!(created.hour(), 17);
+	assert_eq!(created.minute(), 0);
+	assert_eq!(created.second(), 0);
+	assert_eq!(created.nanosecond(), 0);
+	assert_eq!(created.offset_minutes(), 0);
+	assert_eq!(created.unix_timestamp(), 1_034_269_200);
+	assert_eq!(created.to_string(), "2002-10-10T17:00:00Z");
+}
+
+#[test]
+fn parse_datetime_with_offset() {
+	let created = datetime("2002-10-10T11:30:00-05:30");
+	assert_eq!(created.offset_minutes(), -330);
+	assert_eq!(created.unix_timestamp(), 1_034_269_200);
+	assert_eq!(created.to_string(), "2002-10-10T11:30:00-05:30");
+	assert_eq!(
+		datetime("2002-10-10T17:00:00-00:00").to_string(),
+		"2002-10-10T17:00:00Z"
+	);
+	assert_eq!(
+		datetime("1970-01-01T00:00:00+01:00").unix_timestamp(),
+		-3600
+	);
+}
+
+#[test]
+fn parse_datetime_with_fractional_seconds() {
+	let created = datetime("2002-10-10T17:00:00.1230Z");
+	assert_eq!(created.nanosecond(), 123_000_000);
+	assert_eq!(created.to_string(), "2002-10-10T17:00:00.123Z");
+	assert_eq!(
+		datetime("2002-10-10T17:00:00.000Z").to_string(),
+		"2002-10-10T17:00:00Z"
+	);
+	assert_eq!(datetime("2002-10-10T17:00:00.000000001Z").nanosecond(), 1);
+	assert_eq!(
+		datetime("2002-10-10T17:00:00.1234567899Z").to_string(),
+		"2002-10-10T17:00:00.123456789Z"
+	);
+}
+
+#[test]
+fn parse_end_of_day_datetime() {
+	assert_eq!(
+		datetime("1999-12-31T24:00:00Z"),
+		datetime("2000-01-01T00:00:00Z")
+	);
+	let leap_day = datetime("2000-02-28T24:00:00.000Z");
+	assert_eq!(leap_day.to_string(), "2000-02-29T00:00:00Z");
+	assert_eq!(leap_day.unix_timestamp(), 951_782_400);
+}
+
+#[test]
+fn parse_datetime_with_extended_years() {
+	assert_eq!(
+		datetime("0000-01-01T00:00:00Z").unix_timestamp(),
+		-62_167_219_200
+	);
+	assert_eq!(datetime("-0044-03-15T12:00:00Z").year(), -44);
+	assert_eq!(
+		datetime("-0044-03-15T12:00:00Z").to_string(),
+		"-0044-03-15T12:00:00Z"
+	);
+	assert_eq!(
+		datetime("12345-01-01T00:00:00Z").to_string(),
+		"12345-01-01T00:00:00Z"
+	);
+}
+
+#[test]
+fn parse_invalid_datetime_fails() {
+	for input in [
+		"",
+		"2002-10-10T17:00:00",
+		"2002-10-10 17:00:00Z",
+		"02002-10-10T17:00:00Z",
+		"202-10-10T17:00:00Z",
+		"2002-13-10T17:00:00Z",
+		"2002-10-32T17:00:00Z",
+		"2002-10-10T17:00:60Z",
+		"2002-10-10T17:00:00.Z",
+		"2002-10-10T24:00:01Z",
+		"2002-10-10T24:00:00.5Z",
+		"2002-10-10T17:00:00+14:01",
+		"2002-10-10T17:00:00+0100",
+		"2002-10-10T17:00:00Zjunk",
+	]
+	.iter()
+	{
+		assert_eq!(
+			XsdDateTime::parse(input),
+			Err("invalid xsd:dateTime"),
+			"{}",
+			input
+		);
+	}
+}
+
+#[test]
+fn order_datetimes_by_instant() {
+	let mut datetimes = [
+		datetime("2002-10-10T17:00:00.5Z"),
+		datetime("2002-10-10T18:00:00+02:00"),
+		datetime("2002-10-10T17:00:00Z"),
+		datetime("2002-10-09T24:00:00Z"),
+		datetime("2002-10-10T12:00:00-05:00"),
+	];
+	datetimes.sort();
+	assert_eq!(
+		datetimes
+			.iter()
+			.map(XsdDateTime::to_string)
+			.collect::<Vec<_>>(),
+		vec![
+			"2002-10-10T00:00:00Z",
+			"2002-10-10T18:00:00+02:00",
+			"2002-10-10T12:00:00-05:00",
+			"2002-10-10T17:00:00Z",
+			"2002-10-10T17:00:00.5Z",
+		]
+	);
+	assert_ne!(
+		datetime("2002-10-10T12:00:00-05:00"),
+		datetime("2002-10-10T17:00:00Z")
+	);
+}
+
+#[test]
+fn did_document_timestamps() {
+	let json = json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:21tDAKCERh95uGgKbJNHYp",
+			"created": "2002-10-10T17:00:00.00+01:00",
+			"updated": "2002-10-10T24:00:00Z"
+		}
+		"#,
+	)
+	.unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let created = did_doc.created().unwrap();
+	let updated = did_doc.updated().unwrap();
+	assert!(created < updated);
+	assert_eq!(updated.unix_timestamp() - created.unix_timestamp(), 28_800);
+	assert_eq!(
+		did_doc.to_json()["created"].as_str(),
+		Some("2002-10-10T17:00:00+01:00")
+	);
+	assert_eq!(
+		did_doc.to_json()["updated"].as_str(),
+		Some("2002-10-11T00:00:00Z")
+	);
+}
+
+#[test]
+fn parse_impossible_dates_fails() {
+	for input in [
+		"2002-02-30T17:00:00Z",
+		"2001-02-29T17:00:00Z",
+		"1900-02-29T17:00:00Z",
+		"2002-04-31T17:00:00Z",
+		"2002-11-31T24:00:00Z",
+	]
+	.iter()
+	{
+		assert_eq!(
+			XsdDateTime::parse(input),
+			Err("invalid xsd:dateTime"),
+			"{}",
+			input
+		);
+	}
+	assert_eq!(datetime("2000-02-29T17:00:00Z").day(), 29);
+	assert_eq!(datetime("2024-02-29T17:00:00Z").day(), 29);
+	assert_eq!(datetime("-0004-02-29T17:00:00Z").day(), 29);
+	assert_eq!(
+		datetime("2002-02-28T24:00:00Z").to_string(),
+		"2002-03-01T00:00:00Z"
+	);
+}
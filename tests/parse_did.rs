@@ -1,4 +1,7 @@
-use did_common::did::{Did, DidBuilder};
+// Builders are called with borrowed arrays, as in the README
+#![allow(clippy::needless_borrows_for_generic_args)]
+
+use did_common::did::{Did, DidBuilder, DidParam};
 
 #[test]
 fn parse_generic_did() {
@@ -109,3 +112,198 @@ fn parse_did_with_multiple_params_and_fragment() {
 			.build())
 	);
 }
+
+#[test]
+fn parse_did_with_pct_encoded_method_id() {
+	assert_eq!(
+		Did::parse("did:web:example.com%3A8443:users:alice"),
+		Ok(DidBuilder::new("web", "example.com%3A8443:users:alice").build())
+	);
+}
+
+#[test]
+fn parse_did_with_trailing_characters() {
+	assert_eq!(
+		Did::parse("did:example:1234 extra"),
+		Err("Failed to parse did.")
+	);
+}
+
+#[test]
+fn parse_did_with_pct_encoded_param() {
+	assert_eq!(
+		Did::parse("did:example:1234;relativeRef=%2Fpath%2Fto%3Fq"),
+		Ok(DidBuilder::new("example", "1234")
+			.with_params(&[("relativeRef", "%2Fpath%2Fto%3Fq")])
+			.build())
+	);
+	assert!(Did::parse("did:example:1234;relativeRef=%2").is_err());
+}
+
+#[test]
+fn parse_did_with_standard_params() {
+	let did = Did::parse(concat!(
+		"did:example:1234;service=files;relativeRef=%2Fresources%2Fsomefile.json",
+		";versionId=4;versionTime=2021-05-10T17:00:00Z",
+		";hl=zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e"
+	))
+	.unwrap();
+	assert_eq!(did.service(), Ok(Some("files")));
+	assert_eq!(
+		did.relative_ref(),
+		Ok(Some("/resources/somefile.json".to_string()))
+	);
+	assert_eq!(did.version_id(), Ok(Some("4")));
+	assert_eq!(
+		did.version_time()
+			.map(|time| time.map(|time| time.unix_timestamp())),
+		Ok(Some(1_620_666_000))
+	);
+	assert!(did.hashlink().unwrap().is_some());
+
+	let did = Did::parse("did:example:1234").unwrap();
+	assert_eq!(did.service(), Ok(None));
+	assert_eq!(did.relative_ref(), Ok(None));
+	assert_eq!(did.version_id(), Ok(None));
+	assert_eq!(did.version_time(), Ok(None));
+}
+
+#[test]
+fn parse_did_with_invalid_standard_params_fails() {
+	assert_eq!(
+		Did::parse("did:example:1234;service=").unwrap().service(),
+		Err("missing service parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;relativeRef=https:%2F%2Fexample.com")
+			.unwrap()
+			.relative_ref(),
+		Err("invalid relativeRef parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;relativeRef=%2Fa%20b")
+			.unwrap()
+			.relative_ref(),
+		Err("invalid relativeRef parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;versionTime=2021-02-30T17:00:00Z")
+			.unwrap()
+			.version_time(),
+		Err("invalid versionTime parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;versionTime=2021-05-10T17:00:00.5Z")
+			.unwrap()
+			.version_time(),
+		Err("invalid versionTime parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;versionTime=2021-01-01T00:00:00.000Z")
+			.unwrap()
+			.version_time(),
+		Err("invalid versionTime parameter value")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;versionTime=2021-05-10T17:00:00-00:00")
+			.unwrap()
+			.version_time(),
+		Err("invalid versionTime parameter value")
+	);
+	assert_eq!(
+		DidBuilder::new("example", "1234")
+			.with_params(&[("versionTime", "2021-05-10T17:00:00+00:00")])
+			.build()
+			.version_time(),
+		Err("invalid versionTime parameter value")
+	);
+}
+
+#[test]
+fn build_did_with_standard_params() {
+	let did = DidBuilder::new("example", "1234")
+		.with_service("files")
+		.with_relative_ref("%2Fresources%2Fsomefile.json")
+		.with_version_id("3")
+		.with_version_time("2021-05-10T17:00:00Z")
+		.with_hashlink("zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e")
+		.with_version_id("4")
+		.build();
+	assert_eq!(
+		did.to_string(),
+		concat!(
+			"did:example:1234;service=files;relativeRef=%2Fresources%2Fsomefile.json",
+			";versionId=4;versionTime=2021-05-10T17:00:00Z",
+			";hl=zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e"
+		)
+	);
+	assert_eq!(Did::parse(&did.to_string()), Ok(did));
+}
+
+#[test]
+fn parse_did_with_foreign_method_param_fails() {
+	assert_eq!(
+		Did::parse("did:example:1234;other:foo=bar"),
+		Err("Failed to parse did.")
+	);
+}
+
+#[test]
+fn parse_did_with_duplicate_params_fails() {
+	assert_eq!(
+		Did::parse("did:example:1234;service=agent;service=files"),
+		Err("Failed to parse did.")
+	);
+	assert_eq!(
+		Did::parse("did:example:1234;example:foo=bar;example:foo=baz"),
+		Err("Failed to parse did.")
+	);
+}
+
+#[test]
+fn did_method_params() {
+	let did =
+		Did::parse("did:example:1234;service=agent;example:foo:bar=baz;example:qux=").unwrap();
+	assert_eq!(
+		did.method_params("example"),
+		vec![
+			DidParam::new("foo:bar", Some("baz")),
+			DidParam::new("qux", Some(""))
+		]
+	);
+	assert_eq!(did.method_params("exam"), vec![]);
+	assert_eq!(did.method_params("other"), vec![]);
+}
+
+#[test]
+fn join_relative_did_url() {
+	let base = Did::parse("did:example:1234;service=agent#keys-1").unwrap();
+	assert_eq!(
+		base.join("#keys-2"),
+		Ok("did:example:1234;service=agent#keys-2".to_string())
+	);
+	assert_eq!(
+		base.join(";service=files#endpoint"),
+		Ok("did:example:1234;service=files#endpoint".to_string())
+	);
+	assert_eq!(
+		base.join(""),
+		Ok("did:example:1234;service=agent".to_string())
+	);
+	assert_eq!(
+		base.join("did:other:5678#keys-1"),
+		Ok("did:other:5678#keys-1".to_string())
+	);
+}
+
+#[test]
+fn join_invalid_relative_did_url_fails() {
+	let base = Did::parse("did:example:1234").unwrap();
+	assert_eq!(base.join("keys-1"), Err("invalid relative DID URL"));
+	assert_eq!(
+		base.join("https://example.com"),
+		Err("invalid relative DID URL")
+	);
+	assert_eq!(base.join("#keys 1"), Err("invalid relative DID URL"));
+	assert_eq!(base.join(";other:foo=bar"), Err("invalid relative DID URL"));
+}
@@ -110,7 +110,7 @@ fn parse_did_doc_with_created() {
 		)),
 		Ok(
 			DidDocumentBuilder::new("did:example:21tDAKCERh95uGgKbJNHYp")
-				.created_on("2002-10-10T17:00:00Z")
+				.created_on("2002-10-10T17:00:00Z".parse().unwrap())
 				.build()
 		)
 	);
@@ -132,6 +132,22 @@ fn parse_did_doc_with_invalid_updated() {
 	);
 }
 
+#[test]
+fn parse_did_doc_with_impossible_updated() {
+	assert_eq!(
+		DidDocument::parse(&json_parse(
+			r#"
+        {
+            "@context": "https://www.w3.org/2019/did/v1",
+            "id": "did:example:21tDAKCERh95uGgKbJNHYp",
+			"updated": "2002-02-30T17:00:00Z"
+        }
+        "#
+		)),
+		Err("invalid updated timestamp")
+	);
+}
+
 #[test]
 fn parse_did_doc_with_updated() {
 	assert_eq!(
@@ -146,7 +162,7 @@ fn parse_did_doc_with_updated() {
 		)),
 		Ok(
 			DidDocumentBuilder::new("did:example:21tDAKCERh95uGgKbJNHYp")
-				.updated_on("2002-10-10T17:00:00Z")
+				.updated_on("2002-10-10T17:00:00Z".parse().unwrap())
 				.build()
 		)
 	);
@@ -239,7 +255,7 @@ fn parse_did_doc_with_auth_reference_verif_method() {
 		)),
 		Ok(DidDocumentBuilder::new("did:example:123456789abcdefghi")
 			.with_authentication(vec![VerificationMethod::Reference(
-				"did:example:123456789abcdefghi#keys-1"
+				"did:example:123456789abcdefghi#keys-1".into()
 			)])
 			.with_pubkeys(vec![PublicKeyBuilder::new(
 				"did:example:123456789abcdefghi#keys-1",
@@ -336,6 +352,117 @@ fn parse_did_doc_with_duplicate_id_from_auth_embedded() {
 	);
 }
 
+#[test]
+fn parse_did_doc_with_duplicate_embedded_ids() {
+	let key = r#"{
+        "id": "did:example:123456789abcdefghi#keys-2",
+        "type": "Ed25519VerificationKey2018",
+        "controller": "did:example:123456789abcdefghi",
+        "publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+    }"#;
+	// Across verification relationships
+	assert_eq!(
+		DidDocument::parse(&json_parse(&format!(
+			r#"
+        {{
+            "@context": "https://www.w3.org/2019/did/v1",
+            "id": "did:example:123456789abcdefghi",
+            "authentication": [{0}],
+            "assertionMethod": [{0}]
+        }}
+        "#,
+			key
+		))),
+		Err("duplicate public key id from embedded verification method")
+	);
+	// Within a verification relationship
+	assert_eq!(
+		DidDocument::parse(&json_parse(&format!(
+			r#"
+        {{
+            "@context": "https://www.w3.org/2019/did/v1",
+            "id": "did:example:123456789abcdefghi",
+            "authentication": [{0}, {0}]
+        }}
+        "#,
+			key
+		))),
+		Err("duplicate public key id from embedded verification method")
+	);
+}
+
+#[test]
+fn parse_did_doc_with_verification_relationships() {
+	assert_eq!(
+		DidDocument::parse(&json_parse(
+			r#"
+        {
+            "@context": "https://www.w3.org/2019/did/v1",
+            "id": "did:example:123456789abcdefghi",
+            "publicKey": [
+                {
+                    "id": "did:example:123456789abcdefghi#keys-1",
+                    "type": "X25519KeyAgreementKey2019",
+                    "controller": "did:example:123456789abcdefghi",
+                    "publicKeyBase58": "JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr"
+                }
+            ],
+            "keyAgreement": ["did:example:123456789abcdefghi#keys-1"],
+            "assertionMethod": [
+                {
+                    "id": "did:example:123456789abcdefghi#keys-2",
+                    "type": "Ed25519VerificationKey2018",
+                    "controller": "did:example:123456789abcdefghi",
+                    "publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+                }
+            ]
+        }
+        "#
+		)),
+		Ok(DidDocumentBuilder::new("did:example:123456789abcdefghi")
+			.with_pubkeys(vec![PublicKeyBuilder::new(
+				"did:example:123456789abcdefghi#keys-1",
+				PublicKeyType::X25519,
+				"did:example:123456789abcdefghi"
+			)
+			.with_encoded_key(PublicKeyEncoded::Base58(
+				"JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr"
+			))
+			.build()])
+			.with_key_agreement(vec![VerificationMethod::Reference(
+				"did:example:123456789abcdefghi#keys-1".into()
+			)])
+			.with_assertion_method(vec![VerificationMethod::Embedded(
+				PublicKeyBuilder::new(
+					"did:example:123456789abcdefghi#keys-2",
+					PublicKeyType::Ed25519,
+					"did:example:123456789abcdefghi"
+				)
+				.with_encoded_key(PublicKeyEncoded::Base58(
+					"H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+				))
+				.build(),
+			)])
+			.build())
+	);
+}
+
+#[test]
+fn parse_did_doc_with_unknown_key_agreement_reference() {
+	assert_eq!(
+		DidDocument::parse(&json_parse(
+			r#"
+        {
+            "@context": "https://www.w3.org/2019/did/v1",
+            "id": "did:example:123456789abcdefghi",
+            "keyAgreement": ["did:example:123456789abcdefghi#keys-1"]
+        }
+        "#
+		)),
+		Err("unknown reference verification method")
+	);
+}
+
 #[test]
 fn parse_did_doc_with_service_uri_endpoints() {
 	assert_eq!(
@@ -417,3 +544,97 @@ fn parse_did_doc_with_service_object_endpoint() {
 		Err("invalid service endpoint JSON-LD object : unimplemented")
 	);
 }
+
+#[test]
+fn parse_did_doc_with_relative_ids() {
+	let json = json_parse(
+		r##"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123456789abcdefghi",
+			"publicKey": [
+				{
+					"id": "#keys-1",
+					"type": "Ed25519VerificationKey2018",
+					"controller": "did:example:123456789abcdefghi",
+					"publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+				}
+			],
+			"authentication": [
+				"#keys-1",
+				{
+					"id": "did:example:123456789abcdefghi#keys-2",
+					"type": "Ed25519VerificationKey2018",
+					"controller": "did:example:123456789abcdefghi",
+					"publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+				}
+			],
+			"service": [
+				{
+					"id": ";service=openid",
+					"type": "OpenIdConnectVersion1.0Service",
+					"serviceEndpoint": "https://openid.example.com/"
+				}
+			]
+		}
+		"##,
+	);
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.pub_keys()[0].id(),
+		"did:example:123456789abcdefghi#keys-1"
+	);
+	assert_eq!(
+		did_doc.authentication()[0],
+		VerificationMethod::Reference("did:example:123456789abcdefghi#keys-1".into())
+	);
+	assert_eq!(
+		did_doc.service()[0].id(),
+		"did:example:123456789abcdefghi;service=openid"
+	);
+}
+
+#[test]
+fn parse_did_doc_with_invalid_relative_id() {
+	assert_eq!(
+		DidDocument::parse(&json_parse(
+			r#"
+			{
+				"@context": "https://www.w3.org/2019/did/v1",
+				"id": "did:example:123456789abcdefghi",
+				"authentication": ["keys-1"]
+			}
+			"#
+		)),
+		Err("invalid reference verification method")
+	);
+}
+
+#[test]
+fn parse_did_doc_with_duplicate_relative_key_id() {
+	assert_eq!(
+		DidDocument::parse(&json_parse(
+			r##"
+			{
+				"@context": "https://www.w3.org/2019/did/v1",
+				"id": "did:example:123456789abcdefghi",
+				"publicKey": [
+					{
+						"id": "#keys-1",
+						"type": "Ed25519VerificationKey2018",
+						"controller": "did:example:123456789abcdefghi",
+						"publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+					},
+					{
+						"id": "did:example:123456789abcdefghi#keys-1",
+						"type": "Ed25519VerificationKey2018",
+						"controller": "did:example:123456789abcdefghi",
+						"publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+					}
+				]
+			}
+			"##
+		)),
+		Err("duplicate DID public key id")
+	);
+}
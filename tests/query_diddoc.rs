@@ -0,0 +1,178 @@
+use did_common::did_doc::{
+	DidDocument, PublicKey, PublicKeyType, VerificationMethod, VerificationRelationship,
+};
+use did_common::json_parse;
+
+const DID_DOC: &str = r##"
+{
+	"@context": "https://www.w3.org/2019/did/v1",
+	"id": "did:example:123456789abcdefghi",
+	"publicKey": [
+		{
+			"id": "#keys-1",
+			"type": "Ed25519VerificationKey2018",
+			"controller": "did:example:123456789abcdefghi",
+			"publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+		},
+		{
+			"id": "did:example:123456789abcdefghi#keys-2",
+			"type": "Secp256k1VerificationKey2018",
+			"controller": "did:example:pqrstuvwxyz0987654321",
+			"publicKeyHex": "02b97c30de767f084ce3080168ee293053ba33b235d7116a3263d29f1450936b71"
+		}
+	],
+	"authentication": [
+		"#keys-1",
+		{
+			"id": "#keys-3",
+			"type": "Ed25519VerificationKey2018",
+			"controller": "did:example:123456789abcdefghi",
+			"publicKeyBase58": "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"
+		}
+	],
+	"assertionMethod": ["did:example:123456789abcdefghi#keys-2"],
+	"service": [
+		{
+			"id": "#openid",
+			"type": "OpenIdConnectVersion1.0Service",
+			"serviceEndpoint": "https://openid.example.com/"
+		},
+		{
+			"id": ";service=agent",
+			"type": "AgentService",
+			"serviceEndpoint": "https://agent.example.com/"
+		},
+		{
+			"id": ";service=backup",
+			"type": "AgentService",
+			"serviceEndpoint": "https://backup.example.com/"
+		}
+	]
+}
+"##;
+
+fn ids<'k>(keys: impl Iterator<Item = &'k PublicKey<'k>>) -> Vec<&'k str> {
+	keys.map(|key| key.id()).collect()
+}
+
+#[test]
+fn query_all_keys() {
+	let json = json_parse(DID_DOC).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		ids(did_doc.keys()),
+		vec![
+			"did:example:123456789abcdefghi#keys-1",
+			"did:example:123456789abcdefghi#keys-2",
+			"did:example:123456789abcdefghi#keys-3",
+		]
+	);
+}
+
+#[test]
+fn query_key_by_id() {
+	let json = json_parse(DID_DOC).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.find_key("#keys-2").map(|key| key.key_type()),
+		Some(&PublicKeyType::EcdsaSecp256k1)
+	);
+	assert_eq!(
+		did_doc
+			.find_key("did:example:123456789abcdefghi#keys-3")
+			.map(|key| key.id()),
+		Some("did:example:123456789abcdefghi#keys-3")
+	);
+	assert_eq!(did_doc.find_key("#keys-4"), None);
+	assert_eq!(did_doc.find_key("did:example:other#keys-1"), None);
+}
+
+#[test]
+fn query_keys_by_type_and_controller() {
+	let json = json_parse(DID_DOC).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		ids(did_doc.keys_by_type(PublicKeyType::Ed25519)),
+		vec![
+			"did:example:123456789abcdefghi#keys-1",
+			"did:example:123456789abcdefghi#keys-3",
+		]
+	);
+	assert_eq!(
+		ids(did_doc.keys_by_controller("did:example:pqrstuvwxyz0987654321")),
+		vec!["did:example:123456789abcdefghi#keys-2"]
+	);
+	assert_eq!(did_doc.keys_by_type(PublicKeyType::Rsa).count(), 0);
+}
+
+#[test]
+fn query_keys_by_relationship() {
+	let json = json_parse(DID_DOC).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		ids(did_doc.keys_for(VerificationRelationship::Authentication)),
+		vec![
+			"did:example:123456789abcdefghi#keys-1",
+			"did:example:123456789abcdefghi#keys-3",
+		]
+	);
+	assert_eq!(
+		ids(did_doc.keys_for(VerificationRelationship::AssertionMethod)),
+		vec!["did:example:123456789abcdefghi#keys-2"]
+	);
+	assert_eq!(
+		did_doc
+			.keys_for(VerificationRelationship::KeyAgreement)
+			.count(),
+		0
+	);
+}
+
+#[test]
+fn resolve_verification_method() {
+	let json = json_parse(DID_DOC).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let reference = &did_doc.authentication()[0];
+	assert_eq!(did_doc.resolve(reference), Some(&did_doc.pub_keys()[0]));
+	assert_eq!(
+		did_doc.resolve(&VerificationMethod::Reference("#keys-2".into())),
+		Some(&did_doc.pub_keys()[1])
+	);
+	assert_eq!(
+		did_doc
+			.resolve(&VerificationMethod::Reference("#keys-3".into()))
+			.map(|key| key.id()),
+		Some("did:example:123456789abcdefghi#keys-3")
+	);
+	assert_eq!(
+		did_doc.resolve(&VerificationMethod::Reference("#keys-4".into())),
+		None
+	);
+}
+
+#[test]
+fn query_services() {
+	let json = json_parse(DID_DOC).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc
+			.services_by_type("AgentService")
+			.map(|svc| svc.id())
+			.collect::<Vec<_>>(),
+		vec![
+			"did:example:123456789abcdefghi;service=agent",
+			"did:example:123456789abcdefghi;service=backup",
+		]
+	);
+	assert_eq!(
+		did_doc.find_service("#openid").map(|svc| svc.svc_type()),
+		Some("OpenIdConnectVersion1.0Service")
+	);
+	assert_eq!(
+		did_doc
+			.find_service(";service=agent")
+			.map(|svc| svc.svc_type()),
+		Some("AgentService")
+	);
+	assert_eq!(did_doc.find_service("#agent"), None);
+}
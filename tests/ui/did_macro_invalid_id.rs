@@ -0,0 +1,5 @@
+use did_common::did;
+
+fn main() {
+	let _ = did!("did:example:12 34");
+}
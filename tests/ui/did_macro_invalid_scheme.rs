@@ -0,0 +1,5 @@
+use did_common::did;
+
+fn main() {
+	let _ = did!("dad:example:1234");
+}
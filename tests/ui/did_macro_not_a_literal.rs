@@ -0,0 +1,6 @@
+use did_common::did;
+
+fn main() {
+	let did = "did:example:1234";
+	let _ = did!(did);
+}
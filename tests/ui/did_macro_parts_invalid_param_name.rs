@@ -0,0 +1,5 @@
+use did_common::did;
+
+fn main() {
+	let _ = did!(method = "example", id = "1234"; "serv ice" = "agent");
+}
@@ -0,0 +1,5 @@
+use did_common::did;
+
+fn main() {
+	let _ = did!(id = "1234", method = "example");
+}
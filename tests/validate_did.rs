@@ -1,3 +1,6 @@
+// Validation results are compared with assert_eq! for readability
+#![allow(clippy::bool_assert_comparison)]
+
 use did_common::did::Did;
 
 #[test]
@@ -48,3 +51,31 @@ fn validate_did_with_multiple_params_and_fragment() {
 		true
 	);
 }
+
+#[test]
+fn validate_did_with_pct_encoded_method_id() {
+	assert_eq!(Did::is_valid("did:web:example.com%3A8443"), true);
+}
+
+#[test]
+fn validate_did_with_foreign_method_param() {
+	assert_eq!(Did::is_valid("did:example:1234;other:foo:bar=baz"), false);
+}
+
+#[test]
+fn validate_did_with_duplicate_params() {
+	assert_eq!(
+		Did::is_valid("did:example:1234;service=agent;service=agent"),
+		false
+	);
+}
+
+#[test]
+fn validate_did_with_trailing_characters() {
+	assert_eq!(Did::is_valid("did:example:1234 trailing"), false);
+	assert_eq!(
+		Did::is_valid("did:example:1234;service=agent trailing"),
+		false
+	);
+	assert_eq!(Did::is_valid("did:example:1234#keys-1 trailing"), false);
+}
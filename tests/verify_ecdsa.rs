@@ -0,0 +1,234 @@
+#![cfg(all(feature = "secp256k1", feature = "p256"))]
+
+use did_common::did_doc::{DidDocument, VerificationRelationship};
+use did_common::json_parse;
+
+const MESSAGE: &[u8] = b"hello did";
+const SECP256K1_DER_SIG: &str = "3045022100c32f0d7ce7e10ab7617996d0852afb595a5426817be16ee4d92faf0edbe2baaa022045e588aa0372b3466c4a3c4301cd3178bf8606f25ab182a26c99c179fb0e9229";
+const SECP256K1_COMPACT_SIG: &str = "c32f0d7ce7e10ab7617996d0852afb595a5426817be16ee4d92faf0edbe2baaa45e588aa0372b3466c4a3c4301cd3178bf8606f25ab182a26c99c179fb0e9229";
+const SECP256K1_HIGH_S_SIG: &str = "c32f0d7ce7e10ab7617996d0852afb595a5426817be16ee4d92faf0edbe2baaaba1a7755fc8d4cb993b5c3bcfe32ce85fb28d5f454971d9953389d12d527af18";
+const P256_DER_SIG: &str = "3045022100ee3de39d2d501b808afcf05312946d9fc5abc59448f1ae6d397ec6ec7ee27fd10220201ed7a7338fd03f6ccb9c06dacc924a03d7850b800e5a625ffe723bc3d378df";
+const P256_COMPACT_SIG: &str = "ee3de39d2d501b808afcf05312946d9fc5abc59448f1ae6d397ec6ec7ee27fd1201ed7a7338fd03f6ccb9c06dacc924a03d7850b800e5a625ffe723bc3d378df";
+const P256_HIGH_S_SIG: &str = "ee3de39d2d501b808afcf05312946d9fc5abc59448f1ae6d397ec6ec7ee27fd1dfe12857cc702fc1933463f925336db5b90f75a22709442293bb5887388fac72";
+
+fn hex(s: &str) -> Vec<u8> {
+	(0..s.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+		.collect()
+}
+
+fn did_doc_json() -> json::JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{
+					"id": "did:example:123#k1-compressed",
+					"type": "Secp256k1VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyHex": "02bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d"
+				},
+				{
+					"id": "did:example:123#k1-uncompressed",
+					"type": "Secp256k1VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyHex": "04bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020decddbf6e00192011648d13b1c00af770c0c1bb609d4d3a5c98a43772e0e18ef4"
+				},
+				{
+					"id": "did:example:123#k1-multibase",
+					"type": "Secp256k1VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyMultibase": "zQ3sha1zXpkjT7WC6ybbE8udVDQXr1pMHiMNqLLHRGRCfKTba"
+				},
+				{
+					"id": "did:example:123#k1-multibase-ed25519",
+					"type": "Secp256k1VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyMultibase": "z6MkiaMbhXHNA4eJVCCj8dbzKzTgYDKf6crKgHVHid1F1WCT"
+				},
+				{
+					"id": "did:example:123#k1-multibase-raw",
+					"type": "Secp256k1VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyMultibase": "zp4qWycFynryDjVuokpkSPpAQN4AKRoLJQQXcy5XY6yjr"
+				},
+				{
+					"id": "did:example:123#k1-jwk",
+					"type": "JsonWebKey2020",
+					"controller": "did:example:123",
+					"publicKeyJwk": {
+						"kty": "EC",
+						"crv": "secp256k1",
+						"x": "u1Di2JpO1wZj0IBln-CtS5vD4GwXoidDOWbLWc7uAg0",
+						"y": "7N2_bgAZIBFkjROxwAr3cMDBu2CdTTpcmKQ3cuDhjvQ"
+					}
+				},
+				{
+					"id": "did:example:123#p256-jwk",
+					"type": "JsonWebKey2020",
+					"controller": "did:example:123",
+					"publicKeyJwk": {
+						"kty": "EC",
+						"crv": "P-256",
+						"x": "YP7UuiVanTHJYet0xjVtaMBJuJI7Yfps5mliLmDyn7Y",
+						"y": "eQP-EAi4vJmkGunpVii8ZPLxsgwtfp9Rd6PClNRGIpk"
+					}
+				},
+				{
+					"id": "did:example:123#k1-mismatch",
+					"type": "Secp256k1VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyJwk": {
+						"kty": "EC",
+						"crv": "P-256",
+						"x": "YP7UuiVanTHJYet0xjVtaMBJuJI7Yfps5mliLmDyn7Y",
+						"y": "eQP-EAi4vJmkGunpVii8ZPLxsgwtfp9Rd6PClNRGIpk"
+					}
+				}
+			],
+			"authentication": ["did:example:123#k1-jwk"],
+			"assertionMethod": ["did:example:123#p256-jwk"]
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+#[test]
+fn verify_secp256k1_signature_with_hex_keys() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	for id in ["#k1-compressed", "#k1-uncompressed"].iter() {
+		assert_eq!(
+			did_doc.verify(*id, MESSAGE, &hex(SECP256K1_DER_SIG)),
+			Ok(())
+		);
+		assert_eq!(
+			did_doc.verify(*id, MESSAGE, &hex(SECP256K1_COMPACT_SIG)),
+			Ok(())
+		);
+	}
+}
+
+#[test]
+fn verify_secp256k1_signature_with_multibase_key() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#k1-multibase", MESSAGE, &hex(SECP256K1_COMPACT_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_secp256k1_signature_with_multibase_key_without_codec() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	for id in ["#k1-multibase-ed25519", "#k1-multibase-raw"].iter() {
+		assert_eq!(
+			did_doc.verify(*id, MESSAGE, &hex(SECP256K1_COMPACT_SIG)),
+			Err("public key does not match verification method type")
+		);
+	}
+}
+
+#[test]
+fn verify_secp256k1_signature_with_jwk() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::Authentication,
+			MESSAGE,
+			&hex(SECP256K1_DER_SIG)
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_secp256k1_signature_with_high_s() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#k1-compressed", MESSAGE, &hex(SECP256K1_HIGH_S_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_p256_signature_with_jwk() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::AssertionMethod,
+			MESSAGE,
+			&hex(P256_DER_SIG)
+		),
+		Ok(())
+	);
+	assert_eq!(
+		did_doc.verify("#p256-jwk", MESSAGE, &hex(P256_COMPACT_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_p256_signature_with_high_s() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#p256-jwk", MESSAGE, &hex(P256_HIGH_S_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_ecdsa_signature_with_wrong_key() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#p256-jwk", MESSAGE, &hex(SECP256K1_COMPACT_SIG)),
+		Err("signature verification failed")
+	);
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::Authentication,
+			MESSAGE,
+			&hex(P256_DER_SIG)
+		),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_ecdsa_invalid_signature() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#k1-compressed", b"hello", &hex(SECP256K1_DER_SIG)),
+		Err("signature verification failed")
+	);
+	assert_eq!(
+		did_doc.verify("#k1-compressed", MESSAGE, &hex(SECP256K1_DER_SIG)[1..]),
+		Err("invalid secp256k1 signature")
+	);
+	assert_eq!(
+		did_doc.verify("#p256-jwk", MESSAGE, &hex(P256_COMPACT_SIG)[1..]),
+		Err("invalid P-256 signature")
+	);
+}
+
+#[test]
+fn verify_ecdsa_signature_with_mismatching_key_type() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#k1-mismatch", MESSAGE, &hex(P256_COMPACT_SIG)),
+		Err("public key does not match verification method type")
+	);
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "secp256k1")]
+
+use did_common::did::Did;
+use did_common::did_doc::{DidDocument, VerificationRelationship};
+use did_common::json_parse;
+use did_common::method::pkh;
+
+// Signature of an EIP-191 personal message by a wallet, from the web3.js documentation
+// of `web3.eth.accounts.sign("Some data", privateKey)`
+const MESSAGE: &[u8] = b"Some data";
+const ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+const RECOVERABLE_SIG: &str = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+// ES256K signature with a recovery id, verified with the known public key
+const ES256K_MESSAGE: &[u8] = b"hello did";
+const ES256K_RECOVERABLE_SIG: &str = "33dd58f5b533cfbc329ac18784677756d4ef788ff63b630fafbb048952cfe9d252d3f6a10a686182938384075d51e6a8de18bee355f7f403e91b44bc500cc8b200";
+
+fn hex(s: &str) -> Vec<u8> {
+	(0..s.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+		.collect()
+}
+
+fn did_doc_json() -> json::JsonValue {
+	json_parse(&format!(
+		r#"
+		{{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{{
+					"id": "did:example:123#eth-address",
+					"type": "EcdsaSecp256k1RecoveryMethod2020",
+					"controller": "did:example:123",
+					"ethereumAddress": "{0}"
+				}},
+				{{
+					"id": "did:example:123#account-id",
+					"type": "EcdsaSecp256k1RecoveryMethod2020",
+					"controller": "did:example:123",
+					"blockchainAccountId": "eip155:1:{0}"
+				}},
+				{{
+					"id": "did:example:123#public-key",
+					"type": "EcdsaSecp256k1RecoveryMethod2020",
+					"controller": "did:example:123",
+					"publicKeyHex": "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+				}},
+				{{
+					"id": "did:example:123#other-address",
+					"type": "EcdsaSecp256k1RecoveryMethod2020",
+					"controller": "did:example:123",
+					"ethereumAddress": "0xb9c5714089478a327f09197987f16f9e5d936e8a"
+				}},
+				{{
+					"id": "did:example:123#other-chain",
+					"type": "EcdsaSecp256k1RecoveryMethod2020",
+					"controller": "did:example:123",
+					"blockchainAccountId": "bip122:000000000019d6689c085ae165831e93:128Lkh3S7CkDTBZ8W7BbpsN3YYizJMp8p6"
+				}}
+			],
+			"authentication": ["did:example:123#account-id"]
+		}}
+		"#,
+		ADDRESS
+	))
+	.unwrap()
+}
+
+#[test]
+fn verify_recoverable_signature_with_ethereum_address() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#eth-address", MESSAGE, &hex(RECOVERABLE_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_with_blockchain_account_id() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::Authentication,
+			MESSAGE,
+			&hex(RECOVERABLE_SIG)
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_with_raw_recovery_id() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut signature = hex(RECOVERABLE_SIG);
+	signature[64] -= 27;
+	assert_eq!(did_doc.verify("#eth-address", MESSAGE, &signature), Ok(()));
+}
+
+#[test]
+fn verify_recoverable_signature_with_public_key() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#public-key", ES256K_MESSAGE, &hex(ES256K_RECOVERABLE_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_with_did_pkh() {
+	let did = format!("did:pkh:eip155:1:{}", ADDRESS);
+	let json = pkh::resolve(&Did::parse(&did).unwrap()).unwrap();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::AssertionMethod,
+			MESSAGE,
+			&hex(RECOVERABLE_SIG)
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_with_other_address() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#other-address", MESSAGE, &hex(RECOVERABLE_SIG)),
+		Err("signature verification failed")
+	);
+	assert_eq!(
+		did_doc.verify("#eth-address", b"hello", &hex(RECOVERABLE_SIG)),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_with_invalid_address() {
+	// Signs are not hex digits, even though "+0" would parse as a byte
+	let mut json = did_doc_json();
+	json["publicKey"][0]["ethereumAddress"] = "0x2c7536E3+05D9C16a7a3D7b1898e529396a65c23".into();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#eth-address", MESSAGE, &hex(RECOVERABLE_SIG)),
+		Err("invalid Ethereum address")
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_over_unprefixed_message() {
+	// Signatures must be over the EIP-191 digest, not the SHA-256 digest of the message
+	let mut json = did_doc_json();
+	json["publicKey"][0]["ethereumAddress"] = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf".into();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#eth-address", ES256K_MESSAGE, &hex(ES256K_RECOVERABLE_SIG)),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_invalid_recoverable_signature() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	let mut signature = hex(RECOVERABLE_SIG);
+	assert_eq!(
+		did_doc.verify("#eth-address", MESSAGE, &signature[..64]),
+		Err("invalid recoverable secp256k1 signature")
+	);
+	signature[64] = 4;
+	assert_eq!(
+		did_doc.verify("#eth-address", MESSAGE, &signature),
+		Err("invalid recoverable secp256k1 signature")
+	);
+}
+
+#[test]
+fn verify_recoverable_signature_with_unsupported_account_id() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#other-chain", MESSAGE, &hex(RECOVERABLE_SIG)),
+		Err("unsupported blockchain account id")
+	);
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "rsa")]
+
+use did_common::did_doc::{DidDocument, VerificationRelationship};
+use did_common::json_parse;
+use did_common::verify::SignatureAlgorithm;
+use std::str::FromStr;
+
+const MESSAGE: &[u8] = b"hello did";
+const RS256_SIG: &str = "23e6495677c7c94394ce8b4a558128c7166ed06dcf090dd56595f8ee057f431b6599ff6604af8612d40efea01cc844247a5acbcc2c912c44f54b370aa17492865498a0b5ce9a92f6433c68909295867196b85b565728ce4aee58d85097f02a398cdae9816191886e87726d9b9bb79440e95e899e1032461b9831b3e8ece63f03595567bb4b192d24d8880c337598b4c41efc7dfeb6404524bd009410d40947350f50a417e6b07c43416985a706da360a66b88c80b6766c0a463fb9d6385a34d805d52e2b3d9eb00621364585e3f7cf1558c3e8f398e24841d78c569820348059877bbea420babeeb9e428539f94886c92d11b8bddcd78422d7b01f8c0981bce1";
+const RS256_1024_SIG: &str = "462ae637ea7a3e78cd01abed049e6db22a8e54e830cb5b3b0ca977bf9254c458c14985c772b44c1e19d2f161d4b72e8914f65b715a27deaf780dfd600c3a737a416269c995a10889f66feff4a22a1c922c545c245eac32f5b1897c57da63a505d0aad38f597f0e4a302c48c17f75a5ca1acb6ee7dd3ca143d035e3475005ff91";
+const PS256_SIG: &str = "b3fa2f33784c63011b05ab179b52f7fefaf3dad83c23d5867d7f20295d60ab5d217375d7951f4572c96436dcae1b2c10279b6e20dde0f6670f3e0ac1b8e23511233b7ef0da23c2f0a7f6262433cc00277ecd08d9166c8065d9e0bd232532d81bb387954735182ce0036420a0b9ade6a0af077bfdab0642221a49a0ab46f804ddeffd274a8d017c29ea17281a095c8aca7794fbe69048de0fe9d01996fa75e2f018e317cbeb18e83f0de1abd72ff32453829ccf822066ea601f2e144a79d17fbe0ec25ebc5f55132f8acf85059698f3742de0e9fe49b9a82525554de2fe5a019ebf87315f481262687f86ed3123257e3109ad80ee0f12537bba5104ea1b523760";
+
+fn hex(s: &str) -> Vec<u8> {
+	(0..s.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+		.collect()
+}
+
+fn did_doc_json() -> json::JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{
+					"id": "did:example:123#spki",
+					"type": "RsaVerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyPem": "-----BEGIN PUBLIC KEY-----\nMIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA3aQ+3KbQVaUUIYwv6gss\ncaOVSTC+8AYQgdAG+bQsel79t/AW3pixnkmnLZ9ktN5K5NuTEpVWUJC1bwieNGJ1\nEkRB7MDsxO0V1sR/p7HZBj7fLDRefAJMLs0xSgN8QOcw6aoj1o3vUaEM+dEHtMzC\npi5lpHYx9Yez958DXoAjgdMJwKG8IOynMJx4aCQjV6UsY5AwLLw/QaK8zt+nao/1\nbhrkXdSU5WJprNlNAaH9qLGxSX70/0rSJ1oO0FRQHL0QaZDU9Bea3ruUPNjiVnPT\nIOuSRHxfmoY38N+yBGLD7VRUCRUzNem4XkBcwrsOETDC0L8OwLn7iWV1doA9wzoP\nIQIDAQAB\n-----END PUBLIC KEY-----"
+				},
+				{
+					"id": "did:example:123#pkcs1",
+					"type": "RsaVerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyPem": "-----BEGIN RSA PUBLIC KEY-----\nMIIBCgKCAQEA3aQ+3KbQVaUUIYwv6gsscaOVSTC+8AYQgdAG+bQsel79t/AW3pix\nnkmnLZ9ktN5K5NuTEpVWUJC1bwieNGJ1EkRB7MDsxO0V1sR/p7HZBj7fLDRefAJM\nLs0xSgN8QOcw6aoj1o3vUaEM+dEHtMzCpi5lpHYx9Yez958DXoAjgdMJwKG8IOyn\nMJx4aCQjV6UsY5AwLLw/QaK8zt+nao/1bhrkXdSU5WJprNlNAaH9qLGxSX70/0rS\nJ1oO0FRQHL0QaZDU9Bea3ruUPNjiVnPTIOuSRHxfmoY38N+yBGLD7VRUCRUzNem4\nXkBcwrsOETDC0L8OwLn7iWV1doA9wzoPIQIDAQAB\n-----END RSA PUBLIC KEY-----"
+				},
+				{
+					"id": "did:example:123#jwk",
+					"type": "JsonWebKey2020",
+					"controller": "did:example:123",
+					"publicKeyJwk": {
+						"kty": "RSA",
+						"e": "AQAB",
+						"n": "3aQ-3KbQVaUUIYwv6gsscaOVSTC-8AYQgdAG-bQsel79t_AW3pixnkmnLZ9ktN5K5NuTEpVWUJC1bwieNGJ1EkRB7MDsxO0V1sR_p7HZBj7fLDRefAJMLs0xSgN8QOcw6aoj1o3vUaEM-dEHtMzCpi5lpHYx9Yez958DXoAjgdMJwKG8IOynMJx4aCQjV6UsY5AwLLw_QaK8zt-nao_1bhrkXdSU5WJprNlNAaH9qLGxSX70_0rSJ1oO0FRQHL0QaZDU9Bea3ruUPNjiVnPTIOuSRHxfmoY38N-yBGLD7VRUCRUzNem4XkBcwrsOETDC0L8OwLn7iWV1doA9wzoPIQ"
+					}
+				},
+				{
+					"id": "did:example:123#rsa-1024",
+					"type": "RsaVerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyPem": "-----BEGIN PUBLIC KEY-----\nMIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQDuvuOFM2htuv5lqRNq3N7s7IbD\n3ywBpgRglyPJgayD4WwaxhB1F2sbLZGahT7HU4YKmrQpixv+E4kfluuHuaDsb8FV\n3trMn2jZOAVKyNSRU2RiZnLn8mRdiraIMdHR3CDUPaAzUL/o0vgxb/tZpRxujsRq\nNuQDwBOVaiXYy0kZowIDAQAB\n-----END PUBLIC KEY-----"
+				},
+				{
+					"id": "did:example:123#invalid",
+					"type": "RsaVerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyPem": "-----BEGIN PUBLIC KEY-----\nMIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8A\n-----END PUBLIC KEY-----"
+				}
+			],
+			"authentication": ["did:example:123#spki"]
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+#[test]
+fn verify_rs256_signature_with_pem_keys() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::Authentication,
+			MESSAGE,
+			&hex(RS256_SIG)
+		),
+		Ok(())
+	);
+	assert_eq!(did_doc.verify("#pkcs1", MESSAGE, &hex(RS256_SIG)), Ok(()));
+	assert_eq!(
+		did_doc.verify_with("#spki", SignatureAlgorithm::Rs256, MESSAGE, &hex(RS256_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_ps256_signature_with_pem_key() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify_with("#spki", SignatureAlgorithm::Ps256, MESSAGE, &hex(PS256_SIG)),
+		Ok(())
+	);
+	assert_eq!(
+		did_doc.verify("#spki", MESSAGE, &hex(PS256_SIG)),
+		Err("signature verification failed")
+	);
+}
+
+#[test]
+fn verify_rsa_signature_with_jwk() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(did_doc.verify("#jwk", MESSAGE, &hex(RS256_SIG)), Ok(()));
+	assert_eq!(
+		did_doc.verify_with("#jwk", SignatureAlgorithm::Ps256, MESSAGE, &hex(PS256_SIG)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_rsa_signature_with_mismatching_algorithm() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify_with("#spki", SignatureAlgorithm::EdDsa, MESSAGE, &hex(RS256_SIG)),
+		Err("signature algorithm does not match verification method")
+	);
+}
+
+#[test]
+fn verify_rsa_signature_with_small_key() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#rsa-1024", MESSAGE, &hex(RS256_1024_SIG)),
+		Err("RSA public key is too small")
+	);
+}
+
+#[test]
+fn verify_invalid_rsa_signature() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#spki", b"hello", &hex(RS256_SIG)),
+		Err("signature verification failed")
+	);
+	assert_eq!(
+		did_doc.verify("#invalid", MESSAGE, &hex(RS256_SIG)),
+		Err("invalid RSA public key")
+	);
+}
+
+#[test]
+fn signature_algorithm_fromstr_trait() {
+	assert_eq!(
+		SignatureAlgorithm::from_str("ES256K-R"),
+		Ok(SignatureAlgorithm::Es256KR)
+	);
+	assert_eq!(
+		SignatureAlgorithm::from_str("PS256"),
+		Ok(SignatureAlgorithm::Ps256)
+	);
+	assert!(SignatureAlgorithm::from_str("HS256").is_err());
+}
+
+#[test]
+fn signature_algorithm_display_trait() {
+	assert_eq!(format!("{}", SignatureAlgorithm::EdDsa), "EdDSA");
+	assert_eq!(format!("{}", SignatureAlgorithm::Rs256), "RS256");
+}
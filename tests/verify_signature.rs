@@ -0,0 +1,210 @@
+#![cfg(feature = "ed25519")]
+
+use did_common::did_doc::{DidDocument, VerificationRelationship};
+use did_common::json_parse;
+use did_common::verify::KeySelector;
+
+// Test vectors from RFC 8032, section 7.1
+const SIGNATURE_1: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+const MESSAGE_2: [u8; 1] = [0x72];
+const SIGNATURE_2: &str = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+fn hex(s: &str) -> Vec<u8> {
+	(0..s.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+		.collect()
+}
+
+fn did_doc_json() -> json::JsonValue {
+	json_parse(
+		r#"
+		{
+			"@context": "https://www.w3.org/2019/did/v1",
+			"id": "did:example:123",
+			"publicKey": [
+				{
+					"id": "did:example:123#key-1",
+					"type": "Ed25519VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyBase58": "FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"
+				},
+				{
+					"id": "did:example:123#key-3",
+					"type": "JsonWebKey2020",
+					"controller": "did:example:123",
+					"publicKeyJwk": {
+						"kty": "OKP",
+						"crv": "Ed25519",
+						"x": "PUAXw-hDiVqStwqnTRt-vJyYLM8uxJaMwM1V8Sr0Zgw"
+					}
+				},
+				{
+					"id": "did:example:123#key-4",
+					"type": "X25519KeyAgreementKey2019",
+					"controller": "did:example:123",
+					"publicKeyBase58": "FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z"
+				}
+			],
+			"authentication": ["did:example:123#key-1"],
+			"assertionMethod": [
+				{
+					"id": "did:example:123#key-2",
+					"type": "Ed25519VerificationKey2018",
+					"controller": "did:example:123",
+					"publicKeyMultibase": "z6MkiaMbhXHNA4eJVCCj8dbzKzTgYDKf6crKgHVHid1F1WCT"
+				}
+			],
+			"capabilityInvocation": ["did:example:123#key-3", "did:example:123#key-4"]
+		}
+		"#,
+	)
+	.unwrap()
+}
+
+#[test]
+fn verify_signature_by_method_id() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("did:example:123#key-1", b"", &hex(SIGNATURE_1)),
+		Ok(())
+	);
+	assert_eq!(did_doc.verify("#key-1", b"", &hex(SIGNATURE_1)), Ok(()));
+}
+
+#[test]
+fn verify_signature_by_embedded_method_id() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#key-2", &MESSAGE_2, &hex(SIGNATURE_2)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_signature_with_jwk() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#key-3", &MESSAGE_2, &hex(SIGNATURE_2)),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_signature_by_relationship() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::Authentication,
+			b"",
+			&hex(SIGNATURE_1)
+		),
+		Ok(())
+	);
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::AssertionMethod,
+			&MESSAGE_2,
+			&hex(SIGNATURE_2)
+		),
+		Ok(())
+	);
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::CapabilityInvocation,
+			&MESSAGE_2,
+			&hex(SIGNATURE_2)
+		),
+		Ok(())
+	);
+}
+
+#[test]
+fn verify_signature_by_method_id_for_relationship() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			("#key-1", VerificationRelationship::Authentication),
+			b"",
+			&hex(SIGNATURE_1)
+		),
+		Ok(())
+	);
+	assert_eq!(
+		did_doc.verify(
+			KeySelector::IdFor("#key-1", VerificationRelationship::AssertionMethod),
+			b"",
+			&hex(SIGNATURE_1)
+		),
+		Err("verification method not authorized for relationship")
+	);
+}
+
+#[test]
+fn verify_signature_refuses_key_not_under_relationship() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::AssertionMethod,
+			b"",
+			&hex(SIGNATURE_1)
+		),
+		Err("signature verification failed")
+	);
+	assert_eq!(
+		did_doc.verify(
+			VerificationRelationship::KeyAgreement,
+			b"",
+			&hex(SIGNATURE_1)
+		),
+		Err("no verification method for relationship")
+	);
+}
+
+#[test]
+fn verify_invalid_signature() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#key-1", &MESSAGE_2, &hex(SIGNATURE_1)),
+		Err("signature verification failed")
+	);
+	assert_eq!(
+		did_doc.verify("#key-1", b"", &hex(SIGNATURE_1)[1..]),
+		Err("invalid Ed25519 signature")
+	);
+}
+
+#[test]
+fn verify_signature_with_unknown_method() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#key-5", b"", &hex(SIGNATURE_1)),
+		Err("verification method not found")
+	);
+	assert_eq!(
+		did_doc.verify(
+			("#key-5", VerificationRelationship::Authentication),
+			b"",
+			&hex(SIGNATURE_1)
+		),
+		Err("verification method not found")
+	);
+}
+
+#[test]
+fn verify_signature_with_unsupported_method_type() {
+	let json = did_doc_json();
+	let did_doc = DidDocument::parse(&json).unwrap();
+	assert_eq!(
+		did_doc.verify("#key-4", b"", &hex(SIGNATURE_1)),
+		Err("unsupported verification method type")
+	);
+}